@@ -1,27 +1,127 @@
 use crate::codegen::models::asm::{ASM, SectionCode};
+use crate::codegen::models::runtime::Runtime;
+use crate::codegen::models::target::Target;
+use crate::lex::models::name::Name;
 use crate::parser::models::ast::AST;
-use crate::parser::models::expression::{Expression, FunctionCall};
+use crate::parser::models::expression::Expression;
+use crate::semantic::const_eval::{
+    eval_const, eval_const_int, try_eval_const, try_eval_const_with, ConstError, ConstValue,
+};
+use crate::semantic::models::semantic::{SymbolTable, SymbolType};
+use crate::semantic::models::type_context::TypeContext;
 use crate::parser::models::statement::{
-    ForStatement, FunctionDeclaration, IfStatement, Statement, SwitchCase, SwitchStatement,
+    ForStatement, FunctionDeclaration, IfStatement, Return, Statement, SwitchStatement,
     VarAffection, VarDeclaration, WhileStatement,
 };
 use std::collections::HashMap;
 
+/// Retourne le symbole assembleur d'une variable globale de l'utilisateur.
+///
+/// Les globales sont préfixées par `g_` et les fonctions par `f_` : les autres symboles
+/// générés (`format`, `format_str`, `format_float`, `text_true`, `text_false`, `str_N`, `L<n>`, point d'entrée, routines d'arrêt, fonctions
+/// de la libc) ne commencent jamais par ces préfixes, un identifiant ne peut donc pas
+/// entrer en collision avec eux, ni avec un mot réservé de NASM comme `rax`.
+///
+/// # Arguments
+///
+/// * `name` - Le nom de la variable dans le source.
+fn global_symbol(name: &str) -> String {
+    format!("g_{}", name)
+}
+
+/// Retourne le label d'une fonction : `f_` suivi de son nom, le `::` d'un nom qualifié
+/// par un module devenant `.` (`math::sqrt` -> `f_math.sqrt`). Un identifiant ne contient
+/// jamais de point, le label ne peut donc pas coïncider avec celui d'une fonction `math_sqrt`.
+///
+/// # Arguments
+///
+/// * `name` - Le nom de la fonction, éventuellement qualifié.
+fn function_symbol(name: &str) -> String {
+    format!("f_{}", name.replace("::", "."))
+}
+
+/// Retourne l'instruction `setcc` correspondant à un opérateur de comparaison ordonnée.
+///
+/// # Arguments
+///
+/// * `op` - L'opérateur (`<`, `<=`, `>` ou `>=`).
+fn set_instruction(op: &str) -> &'static str {
+    match op {
+        "<" => "setl",
+        "<=" => "setle",
+        ">" => "setg",
+        _ => "setge",
+    }
+}
+
+/// Retourne le littéral correspondant à une valeur constante, pour la générer comme immédiat.
+///
+/// # Arguments
+///
+/// * `value` - La valeur calculée à la compilation.
+fn const_literal(value: ConstValue) -> Expression {
+    match value {
+        ConstValue::Int(value) => Expression::Int(value),
+        ConstValue::Float(value) => Expression::Float(value),
+        ConstValue::Bool(value) => Expression::Bool(value),
+    }
+}
+
 /// Le générateur de code produit l'assembleur NASM pour x86 à partir d'un AST.
 /// 
 /// Ce module parcourt l'AST et génère des instructions d'assembleur en organisant le
 /// code en sections (data, bss, text, et sections spécifiques aux fonctions). Il gère également
 /// l'allocation des variables locales et des paramètres ainsi que la génération de labels uniques.
+///
+/// Tous les accès aux données globales utilisent l'adressage relatif à RIP (`[rel ...]`) et les
+/// appels à la libc passent par la PLT, afin que le code reste compatible avec une édition de
+/// liens en exécutable indépendant de la position (PIE).
 pub struct CodeGenerator {
     pub asm: ASM,
     label_counter: usize,
     local_offset: i32,
     in_function: bool,
-    local_vars: HashMap<String, i32>,
+    /// Nombre de blocs ouverts : une déclaration dans un bloc du programme principal est
+    /// une variable locale de `f_main`, pas une globale.
+    block_depth: usize,
+    local_vars: HashMap<Name, i32>,
+    /// Valeurs des constantes globales calculables à la compilation, insérées comme immédiats.
+    constants: HashMap<Name, ConstValue>,
     string_literals: HashMap<String, String>,
-    nb_for_boucle: usize,
-    current_loop_var: Option<(String, String)>,
+    loop_exit_labels: Vec<String>,
+    /// Cibles de `continue` : l'incrément d'une boucle for, la condition d'une boucle while.
+    loop_continue_labels: Vec<String>,
+    /// Étiquette donnée dans le source à chaque boucle englobante, le cas échéant.
+    loop_names: Vec<Option<String>>,
     current_section: SectionCode,
+    /// Plateforme visée (`--target`).
+    pub target: Target,
+    /// Représentation des valeurs à l'exécution, notamment des chaînes.
+    pub runtime: Runtime,
+    /// Active la vérification des index de tableaux à l'exécution (`--bounds-check`).
+    pub bounds_check: bool,
+    /// Active la détection des dépassements arithmétiques sur les entiers (`--checked-arith`).
+    pub checked_arith: bool,
+    /// Types des expressions résolus par l'analyse sémantique, pour l'AST à générer. Une
+    /// expression absente du contexte est typée d'après les déclarations connues ici.
+    pub type_context: TypeContext,
+    global_array_lengths: HashMap<Name, i64>,
+    local_array_lengths: HashMap<Name, i64>,
+    /// Types déclarés des variables globales, puis des variables locales et paramètres de la
+    /// fonction en cours : ils choisissent les instructions entières ou flottantes.
+    global_var_types: HashMap<Name, String>,
+    local_var_types: HashMap<Name, String>,
+    function_return_types: HashMap<Name, String>,
+    abort_routines: Vec<(&'static str, &'static str)>,
+    /// Fonctions de bibliothèque appelées seulement par certaines constructions (`pow` pour
+    /// `**` sur des flottants) : elles ne sont déclarées `extern` que si le code les appelle.
+    extern_functions: Vec<&'static str>,
+}
+
+impl Default for CodeGenerator {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl CodeGenerator {
@@ -30,13 +130,28 @@ impl CodeGenerator {
         CodeGenerator {
             asm: ASM::new(),
             label_counter: 0,
-            local_offset: 4, // On démarre à 4 pour la première variable locale.
+            local_offset: 0,
             in_function: false,
+            block_depth: 0,
             local_vars: HashMap::new(),
+            constants: HashMap::new(),
             string_literals: HashMap::new(),
-            nb_for_boucle: 0,
-            current_loop_var: None,
+            loop_exit_labels: Vec::new(),
+            loop_continue_labels: Vec::new(),
+            loop_names: Vec::new(),
             current_section: SectionCode::new("".to_string()),
+            target: Target::default(),
+            runtime: Runtime::default(),
+            bounds_check: false,
+            checked_arith: false,
+            type_context: TypeContext::default(),
+            global_array_lengths: HashMap::new(),
+            local_array_lengths: HashMap::new(),
+            global_var_types: HashMap::new(),
+            local_var_types: HashMap::new(),
+            function_return_types: HashMap::new(),
+            abort_routines: Vec::new(),
+            extern_functions: Vec::new(),
         }
     }
 
@@ -57,18 +172,17 @@ impl CodeGenerator {
     /// # Arguments
     ///
     /// * `ast` - L'AST (Abstract Syntax Tree) du programme à compiler.
-    pub fn generate(&mut self, ast: &AST) {
+    /// * `symbols` - La table des symboles globale laissée par l'analyse sémantique : les types
+    ///   de retour des fonctions y sont lus plutôt que déduits de l'AST.
+    pub fn generate(&mut self, ast: &AST, symbols: &SymbolTable) {
         // Récupération des déclarations globales (hors fonctions)
         let global_vars: Vec<&VarDeclaration> = ast
             .statements
             .iter()
-            .filter_map(|stmt| {
-                if let Statement::VarDeclaration(var_decl) = stmt {
-                    if !self.in_function {
-                        return Some(var_decl);
-                    }
-                }
-                None
+            .flat_map(|stmt| match stmt {
+                Statement::VarDeclaration(var_decl) => std::slice::from_ref(var_decl),
+                Statement::VarDeclarations(declarations) => declarations.as_slice(),
+                _ => &[],
             })
             .collect();
 
@@ -77,57 +191,284 @@ impl CodeGenerator {
         self.asm
             .section_data
             .push("    format: db \"%d\", 10, 0".to_string());
-        for (literal, label) in &self.string_literals {
-            self.asm
-                .section_data
-                .push(format!("    {}: db \"{}\", 0", label, literal));
+        self.asm
+            .section_data
+            .push("    format_str: db \"%s\", 10, 0".to_string());
+        self.asm
+            .section_data
+            .push("    format_float: db \"%f\", 10, 0".to_string());
+        self.asm
+            .section_data
+            .push("    format_read_int: db \"%ld\", 0".to_string());
+        self.asm
+            .section_data
+            .push("    text_true: db \"true\", 0".to_string());
+        self.asm
+            .section_data
+            .push("    text_false: db \"false\", 0".to_string());
+
+        // Évaluation des constantes globales (utilisées notamment pour la taille des tableaux)
+        for var in &global_vars {
+            if let (true, Some(init_expr)) = (var.is_const, &var.init) {
+                if let Ok(value) = try_eval_const(init_expr, &self.constants) {
+                    self.constants.insert(var.name, value);
+                }
+            }
+        }
+
+        for var in &global_vars {
+            self.global_var_types.insert(var.name, var.type_name.clone());
+        }
+        // Fonctions de l'utilisateur, fonctions de modules et fonctions intégrées
+        for symbol in symbols.all_visible() {
+            if let SymbolType::Function { return_type, .. } = &symbol.symbol_type {
+                self.function_return_types
+                    .insert(symbol.name, return_type.clone());
+            }
         }
 
         // Génération de la section .bss pour les variables globales
         self.asm.section_bss.push("section .bss".to_string());
         for var in &global_vars {
+            let slots = self.array_slots(var);
+            if var.array_size.is_some() {
+                self.global_array_lengths.insert(var.name, slots);
+            }
             self.asm
                 .section_bss
-                .push(format!("    {} resq 1", var.name));
+                .push(format!("    {} resq {}", global_symbol(&var.name), slots));
         }
 
         // Génération de la section .text et du point d'entrée
         self.asm.section_text.push("section .text".to_string());
-        self.asm.section_text.push("global _start".to_string());
-        self.asm.section_text.push("extern printf".to_string());
+        let entry = self.target.entry_symbol();
+        self.asm.section_text.push(format!("global {}", entry));
+        self.asm
+            .section_text
+            .push(format!("extern {}", self.target.c_symbol("printf")));
+        self.asm
+            .section_text
+            .push(format!("extern {}", self.target.c_symbol("exit")));
+        self.asm
+            .section_text
+            .push(format!("extern {}", self.target.c_symbol("scanf")));
+        self.asm
+            .section_text
+            .push(format!(
+                "extern {}",
+                self.target.c_symbol(self.runtime.string_length_function())
+            ));
+        let extern_index = self.asm.section_text.len();
         self.asm.section_text.push("".to_string());
-        self.asm.section_text.push("_start:".to_string());
-        self.asm.section_text.push("    jmp f_main".to_string());
+        self.asm.section_text.push(format!("{}:", entry));
+        // Le point d'entrée appelle `f_main` comme une fonction ordinaire, sur une pile alignée,
+        // puis passe sa valeur de retour à `exit` (qui vide les tampons de stdout)
+        self.asm.section_text.push("    xor rbp, rbp".to_string());
+        self.asm.section_text.push("    and rsp, -16".to_string());
+        self.asm.section_text.push("    call f_main".to_string());
+        self.asm.section_text.push("    mov rdi, rax".to_string());
+        self.asm.section_text.push(self.target.c_call("exit"));
 
         // Création de la section principale pour le code d'exécution (f_main)
         self.current_section = SectionCode::new("f_main:".to_string());
+        self.emit("    push rbp".to_string());
+        self.emit("    mov rbp, rsp".to_string());
+        let frame_index = self.current_section.code.len();
+        self.local_offset = 0;
 
         // Initialisation des variables globales ayant une valeur d'initiation
         for var in &global_vars {
-            if let Some(init_expr) = &var.init {
+            if let (Some(Expression::ArrayLiteral(elements)), Some(_)) = (&var.init, &var.array_size) {
+                self.generate_array_literal(var.name, elements);
+            } else if let Some(init_expr) = &var.init {
                 self.generate_expression(init_expr);
-                self.emit(format!("    mov [{}], rax", var.name));
+                self.emit(format!("    mov [rel {}], rax", global_symbol(&var.name)));
             }
         }
 
         // Génération des autres instructions (hors déclarations globales)
         for stmt in &ast.statements {
-            if let Statement::VarDeclaration(_) = stmt {
+            if let Statement::VarDeclaration(_) | Statement::VarDeclarations(_) = stmt {
                 continue;
             }
             self.generate_statement(stmt);
         }
 
-        // Code pour terminer le programme (syscall exit)
-        self.emit("    mov rax, 60".to_string());
-        self.emit("    xor rdi, rdi".to_string());
-        self.emit("    syscall".to_string());
+        // Les variables déclarées dans les blocs du programme principal vivent dans son cadre
+        if self.local_offset > 0 {
+            let frame_size = (self.local_offset + 15) / 16 * 16;
+            self.current_section
+                .code
+                .insert(frame_index, format!("    sub rsp, {}", frame_size));
+        }
+
+        // Fin du programme principal sans `return` : code de sortie 0
+        self.emit("    xor rax, rax".to_string());
+        self.emit("    mov rsp, rbp".to_string());
+        self.emit("    pop rbp".to_string());
+        self.emit("    ret".to_string());
 
         // Ajoute la section principale générée aux sections de code de l'ASM
         self.asm.sections_code.push(std::mem::replace(
             &mut self.current_section,
             SectionCode::new("".to_string()),
         ));
+
+        let routines = std::mem::take(&mut self.abort_routines);
+        for (label, message) in routines {
+            self.generate_abort_routine(label, message);
+        }
+
+        // Les fonctions appelées à la demande ne sont connues qu'une fois tout le code généré
+        let externs = std::mem::take(&mut self.extern_functions);
+        for (offset, name) in externs.into_iter().enumerate() {
+            self.asm
+                .section_text
+                .insert(extern_index + offset, format!("extern {}", self.target.c_symbol(name)));
+        }
+
+        // Les littéraux de chaîne ne sont connus qu'une fois tout le code généré
+        let mut literals: Vec<(&String, &String)> = self.string_literals.iter().collect();
+        literals.sort_by_key(|(_, label)| (label.len(), label.as_str()));
+        for (literal, label) in literals {
+            self.asm
+                .section_data
+                .push(self.runtime.string_literal(label, literal));
+        }
+    }
+
+    /// Enregistre une routine d'arrêt utilisée par le code généré ; elle n'est émise qu'une fois.
+    ///
+    /// # Arguments
+    ///
+    /// * `label` - Le label de la routine.
+    /// * `message` - Le message écrit sur la sortie d'erreur avant l'arrêt.
+    fn require_abort_routine(&mut self, label: &'static str, message: &'static str) {
+        if !self.abort_routines.iter().any(|(known, _)| *known == label) {
+            self.abort_routines.push((label, message));
+        }
+    }
+
+    /// Enregistre une fonction de bibliothèque appelée par le code généré ; elle n'est déclarée
+    /// `extern` qu'une fois.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Le nom C de la fonction, sans le préfixe de la plateforme.
+    fn require_extern(&mut self, name: &'static str) {
+        if !self.extern_functions.contains(&name) {
+            self.extern_functions.push(name);
+        }
+    }
+
+    /// Génère une routine d'arrêt : elle écrit le message sur la sortie d'erreur et termine
+    /// le programme avec le code 1.
+    ///
+    /// # Arguments
+    ///
+    /// * `label` - Le label de la routine.
+    /// * `message` - Le message à afficher.
+    fn generate_abort_routine(&mut self, label: &str, message: &str) {
+        self.asm
+            .section_data
+            .push(format!("    {}_msg: db \"{}\", 10", label, message));
+        let mut section = SectionCode::new(format!("{}:", label));
+        section.code.push(format!("    mov rax, {}", self.target.write_syscall()));
+        section.code.push("    mov rdi, 2".to_string());
+        section.code.push(format!("    lea rsi, [rel {}_msg]", label));
+        section.code.push(format!("    mov rdx, {}", message.len() + 1));
+        section.code.push("    syscall".to_string());
+        // L'appel à `exit` exige une pile alignée, quel que soit le point de saut
+        section.code.push("    and rsp, -16".to_string());
+        section.code.push("    mov rdi, 1".to_string());
+        section.code.push(self.target.c_call("exit"));
+        self.asm.sections_code.push(section);
+    }
+
+    /// Émet, si `--checked-arith` est actif, un saut vers la routine d'arrêt lorsque la
+    /// dernière opération entière a positionné l'indicateur de dépassement (OF).
+    fn generate_overflow_check(&mut self) {
+        if !self.checked_arith {
+            return;
+        }
+        self.emit("    jo overflow_error".to_string());
+        self.require_abort_routine("overflow_error", "Arithmetic overflow");
+    }
+
+    /// Émet, si `--bounds-check` est actif, la comparaison de l'index contenu dans `index_reg`
+    /// avec la longueur du tableau. La comparaison non signée rejette aussi les index négatifs.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Le nom du tableau indexé.
+    /// * `index_reg` - Le registre contenant l'index.
+    fn generate_bounds_check(&mut self, name: Name, index_reg: &str) {
+        if !self.bounds_check {
+            return;
+        }
+        if let Some(length) = self.array_length(name) {
+            self.emit(format!("    cmp {}, {}", index_reg, length));
+            self.emit("    jae bounds_error".to_string());
+            self.require_abort_routine("bounds_error", "Index out of bounds");
+        }
+    }
+
+    /// Retourne la longueur d'un tableau, ou `None` si `name` n'est pas un tableau.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Le nom de la variable.
+    fn array_length(&self, name: Name) -> Option<i64> {
+        // Une variable locale masque un tableau global de même nom
+        if self.local_vars.contains_key(&name) {
+            self.local_array_lengths.get(&name).copied()
+        } else {
+            self.global_array_lengths.get(&name).copied()
+        }
+    }
+
+    /// Retourne la valeur d'une constante globale désignée par `name`, ou `None` si le nom
+    /// n'est pas une constante à cet endroit : un paramètre, une variable locale ou un compteur
+    /// de boucle de même nom masque la constante.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Le nom lu dans le source.
+    fn visible_constant(&self, name: Name) -> Option<ConstValue> {
+        if self.local_vars.contains_key(&name) {
+            return None;
+        }
+        self.constants.get(&name).copied()
+    }
+
+    /// Évalue une expression constante dans la portée courante (voir `visible_constant`).
+    ///
+    /// # Arguments
+    ///
+    /// * `expr` - L'expression à évaluer.
+    fn const_value(&self, expr: &Expression) -> Result<ConstValue, ConstError> {
+        try_eval_const_with(expr, &|name| self.visible_constant(name))
+    }
+
+    /// Charge dans `rax` l'index d'un élément de tableau. Un index constant négatif compte à
+    /// partir de la fin (`xs[-1]` est le dernier élément) : il est converti à la compilation
+    /// en `longueur + index`.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Le nom du tableau indexé.
+    /// * `index` - L'expression de l'index.
+    fn generate_array_index(&mut self, name: Name, index: &Expression) {
+        let from_end = match self.const_value(index) {
+            Ok(ConstValue::Int(index)) if index < 0 => {
+                self.array_length(name).map(|length| length + index)
+            }
+            _ => None,
+        };
+        match from_end {
+            Some(index) => self.emit(format!("    mov rax, {}", index)),
+            None => self.generate_expression(index),
+        }
     }
 
     /// Génère le code pour une instruction (statement) donnée.
@@ -141,48 +482,164 @@ impl CodeGenerator {
         use Statement::*;
         match stmt {
             VarDeclaration(var_decl) => {
-                if self.in_function {
+                if self.in_function || self.block_depth > 0 {
                     self.generate_local_var_declaration(var_decl);
                 }
             }
+            VarDeclarations(declarations) => {
+                if self.in_function || self.block_depth > 0 {
+                    for var_decl in declarations {
+                        self.generate_local_var_declaration(var_decl);
+                    }
+                }
+            }
             VarAffection(var_affection) => self.generate_var_affection(var_affection),
             ExpressionStatement(expr) => self.generate_expression(expr),
-            Return(expr_opt) => self.generate_return(expr_opt),
+            Return(return_stmt) => self.generate_return(return_stmt),
             If(if_stmt) => self.generate_if_statement(if_stmt),
             For(for_stmt) => self.generate_for_statement(for_stmt),
             While(while_stmt) => self.generate_while_statement(while_stmt),
             Switch(switch_stmt) => self.generate_switch_statement(switch_stmt),
+            Block(body) => self.generate_block(body),
             FunctionDeclaration(func_decl) => self.generate_function_declaration(func_decl),
+            Module(module) => {
+                for func_decl in &module.functions {
+                    self.generate_function_declaration(func_decl);
+                }
+            }
+            Break(label) => {
+                // `break` saute à la sortie de la boucle désignée, la plus interne par défaut
+                if let Some(index) = self.loop_index(label.as_deref()) {
+                    self.emit(format!("    jmp {}", self.loop_exit_labels[index]));
+                }
+            }
+            Continue(label) => {
+                // `continue` saute à l'étape suivante de la boucle désignée
+                if let Some(index) = self.loop_index(label.as_deref()) {
+                    self.emit(format!("    jmp {}", self.loop_continue_labels[index]));
+                }
+            }
+        }
+    }
+
+    /// Retourne la position dans les piles de boucles de la boucle visée par `break` ou
+    /// `continue` : celle qui porte l'étiquette donnée, ou la plus interne.
+    ///
+    /// # Arguments
+    ///
+    /// * `label` - L'étiquette éventuelle de l'instruction.
+    fn loop_index(&self, label: Option<&str>) -> Option<usize> {
+        match label {
+            Some(label) => self
+                .loop_names
+                .iter()
+                .rposition(|name| name.as_deref() == Some(label)),
+            None => self.loop_names.len().checked_sub(1),
+        }
+    }
+
+    /// Génère un bloc d'instructions dans son propre scope, comme l'analyse sémantique.
+    ///
+    /// Les variables locales déclarées dans le bloc ne sont plus visibles à sa sortie : une
+    /// variable ou un compteur de boucle masqué retrouve son emplacement et son type.
+    ///
+    /// # Arguments
+    ///
+    /// * `body` - Les instructions du bloc.
+    fn generate_block(&mut self, body: &[Statement]) {
+        let saved_vars = self.local_vars.clone();
+        let saved_types = self.local_var_types.clone();
+        let saved_array_lengths = self.local_array_lengths.clone();
+        self.block_depth += 1;
+        for stmt in body {
+            self.generate_statement(stmt);
         }
+        self.block_depth -= 1;
+        self.local_vars = saved_vars;
+        self.local_var_types = saved_types;
+        self.local_array_lengths = saved_array_lengths;
     }
 
     /// Génère le code pour la déclaration d'une variable locale.
     ///
     /// Cette méthode gère l'initialisation et l'allocation d'un offset négatif pour la variable.
+    /// Chaque variable occupe un emplacement de 8 octets ; un tableau en occupe un par élément.
     ///
     /// # Arguments
     ///
     /// * `var_decl` - Une référence à une déclaration de variable.
     fn generate_local_var_declaration(&mut self, var_decl: &VarDeclaration) {
+        self.local_var_types
+            .insert(var_decl.name, var_decl.type_name.clone());
+        // Chaque déclaration reçoit son propre emplacement : une variable d'un bloc interne
+        // ne réutilise pas celui d'une variable de même nom qu'elle masque.
+        self.local_offset += 8 * self.array_slots(var_decl) as i32;
+        self.local_vars
+            .insert(var_decl.name, -self.local_offset);
+        if var_decl.array_size.is_some() {
+            let length = self.array_slots(var_decl);
+            self.local_array_lengths.insert(var_decl.name, length);
+        }
+        // Les éléments d'un tableau ne sont initialisés que par un littéral `[a, b, ...]`
+        if var_decl.array_size.is_some() {
+            if let Some(Expression::ArrayLiteral(elements)) = &var_decl.init {
+                self.generate_array_literal(var_decl.name, elements);
+            }
+            return;
+        }
         // Génère le code pour l'initialisation de la variable, ou 0 par défaut.
         if let Some(init_expr) = &var_decl.init {
             self.generate_expression(init_expr);
         } else {
             self.emit("    mov rax, 0".to_string());
         }
-        // Affecte un offset négatif si la variable n'est pas déjà définie.
-        if !self.local_vars.contains_key(&var_decl.name) {
-            self.local_vars
-                .insert(var_decl.name.clone(), -self.local_offset);
-            self.emit(format!("    mov [rbp - {}], rax", self.local_offset));
-            self.local_offset += 4; // On suppose des int sur 4 octets.
+        let off = self.local_vars[&var_decl.name];
+        if off < 0 {
+            self.emit(format!("    mov [rbp - {}], rax", -off));
         } else {
-            let off = self.local_vars[&var_decl.name];
-            if off < 0 {
-                self.emit(format!("    mov [rbp - {}], rax", -off));
-            } else {
-                self.emit(format!("    mov [rbp + {}], rax", off));
+            self.emit(format!("    mov [rbp + {}], rax", off));
+        }
+    }
+
+    /// Retourne le nombre d'emplacements de 8 octets à réserver pour une variable.
+    ///
+    /// # Arguments
+    ///
+    /// * `var_decl` - Une référence à une déclaration de variable.
+    fn array_slots(&self, var_decl: &VarDeclaration) -> i64 {
+        var_decl
+            .array_size
+            .as_ref()
+            .and_then(|size| eval_const_int(size, &|name| self.visible_constant(name)))
+            .unwrap_or(1)
+    }
+
+    /// Range chaque élément d'un littéral `[a, b, ...]` dans l'emplacement correspondant
+    /// d'un tableau ; l'analyse a vérifié que le littéral a la taille du tableau.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Le nom du tableau.
+    /// * `elements` - Les éléments du littéral, dans l'ordre.
+    fn generate_array_literal(&mut self, name: Name, elements: &[Expression]) {
+        for (index, element) in elements.iter().enumerate() {
+            self.generate_expression(element);
+            self.generate_array_base(name);
+            self.emit(format!("    mov [rbx + {}], rax", index * 8));
+        }
+    }
+
+    /// Charge dans `rbx` l'adresse du premier élément d'un tableau (local ou global).
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Le nom du tableau.
+    fn generate_array_base(&mut self, name: Name) {
+        match self.local_vars.get(&name) {
+            Some(off) if *off < 0 => {
+                self.emit(format!("    lea rbx, [rbp - {}]", -off))
             }
+            _ => self.emit(format!("    lea rbx, [rel {}]", global_symbol(&name))),
         }
     }
 
@@ -192,40 +649,51 @@ impl CodeGenerator {
     ///
     /// * `var_affection` - Une référence à une affectation de variable.
     fn generate_var_affection(&mut self, var_affection: &VarAffection) {
-        self.generate_expression(&var_affection.value);
-        // Si la variable correspond à celle d'une boucle for, utiliser le nom interne
-        if let Some((user_var, internal_var)) = &self.current_loop_var {
-            if &var_affection.name == user_var {
-                self.emit(format!("    mov [{}], rax", internal_var));
-                return;
-            }
+        // Affectation d'un élément de tableau : l'index est évalué avant la valeur
+        if let Some(index) = &var_affection.index {
+            self.generate_array_index(var_affection.name, index);
+            self.emit("    push rax".to_string());
+            self.generate_expression(&var_affection.value);
+            self.emit("    pop rcx".to_string());
+            self.generate_bounds_check(var_affection.name, "rcx");
+            self.generate_array_base(var_affection.name);
+            self.emit("    mov [rbx + rcx * 8], rax".to_string());
+            return;
         }
-        // Traitement standard pour une variable locale ou globale
-        if self.in_function {
-            if let Some(offset) = self.local_vars.get(&var_affection.name) {
-                if *offset < 0 {
-                    self.emit(format!("    mov [rbp - {}], rax", -offset));
-                } else {
-                    self.emit(format!("    mov [rbp + {}], rax", offset));
-                }
+        self.generate_expression(&var_affection.value);
+        // Variable locale (compteur de boucle compris) ou globale
+        if let Some(offset) = self.local_vars.get(&var_affection.name) {
+            if *offset < 0 {
+                self.emit(format!("    mov [rbp - {}], rax", -offset));
             } else {
-                self.emit(format!("    mov [{}], rax", var_affection.name));
+                self.emit(format!("    mov [rbp + {}], rax", offset));
             }
         } else {
-            self.emit(format!("    mov [{}], rax", var_affection.name));
+            self.emit(format!("    mov [rel {}], rax", global_symbol(&var_affection.name)));
         }
     }
 
     /// Génère le code pour une instruction de retour.
     ///
     /// Si une expression est fournie, elle est évaluée avant d'exécuter l'épilogue de fonction.
+    /// L'épilogue restaure `rsp` depuis `rbp` : ce qui a pu être empilé depuis le prologue est
+    /// abandonné, un `return` est donc correct à n'importe quelle profondeur de boucles.
     ///
     /// # Arguments
     ///
-    /// * `expr_opt` - Option contenant l'expression à retourner.
-    fn generate_return(&mut self, expr_opt: &Option<Expression>) {
-        if let Some(expr) = expr_opt {
-            self.generate_expression(expr);
+    /// * `return_stmt` - L'instruction `return`, avec ou sans valeur.
+    fn generate_return(&mut self, return_stmt: &Return) {
+        match &return_stmt.value {
+            // Dans le programme principal, `true` signale un succès (code de sortie 0) et
+            // `false` un échec (code de sortie 1)
+            Some(expr) if !self.in_function && self.is_bool_expression(expr) => {
+                self.generate_expression(expr);
+                self.emit("    xor rax, 1".to_string());
+            }
+            Some(expr) => self.generate_expression(expr),
+            // Dans le programme principal, `return;` vaut un code de sortie 0
+            None if !self.in_function => self.emit("    xor rax, rax".to_string()),
+            None => {}
         }
         self.emit("    mov rsp, rbp".to_string());
         self.emit("    pop rbp".to_string());
@@ -241,20 +709,26 @@ impl CodeGenerator {
     ///
     /// * `if_stmt` - Une référence à une instruction if.
     fn generate_if_statement(&mut self, if_stmt: &IfStatement) {
+        // Condition constante : seule la branche prise est générée (l'autre a tout de même
+        // été vérifiée par l'analyse sémantique)
+        if let Ok(ConstValue::Bool(taken)) = self.const_value(&if_stmt.condition) {
+            if taken {
+                self.generate_block(&if_stmt.then_branch);
+            } else if let Some(else_branch) = &if_stmt.else_branch {
+                self.generate_block(else_branch);
+            }
+            return;
+        }
         self.generate_expression(&if_stmt.condition);
         let else_label = self.new_label();
         let end_label = self.new_label();
         self.emit("    cmp rax, 0".to_string());
         self.emit(format!("    je {}", else_label));
-        for stmt in &if_stmt.then_branch {
-            self.generate_statement(stmt);
-        }
+        self.generate_block(&if_stmt.then_branch);
         self.emit(format!("    jmp {}", end_label));
         self.emit(format!("{}:", else_label));
         if let Some(else_branch) = &if_stmt.else_branch {
-            for stmt in else_branch {
-                self.generate_statement(stmt);
-            }
+            self.generate_block(else_branch);
         }
         self.emit(format!("{}:", end_label));
     }
@@ -262,54 +736,81 @@ impl CodeGenerator {
     /// Génère le code pour une boucle for.
     ///
     /// La méthode gère l'initialisation, la condition, le corps et l'incrémentation.
-    /// Elle génère un nom interne pour la variable de boucle (par exemple "i1").
+    /// Le compteur déclaré dans l'initialisation est une variable locale du cadre de pile,
+    /// propre à chaque appel : un appel récursif depuis le corps ne l'écrase pas.
     ///
     /// # Arguments
     ///
     /// * `for_stmt` - Une référence à une instruction for.
     fn generate_for_statement(&mut self, for_stmt: &ForStatement) {
-        if let Statement::VarDeclaration(var_decl) = &*for_stmt.init {
-            let user_var = var_decl.name.clone();
-            self.nb_for_boucle += 1;
-            // Nom interne sans underscore (exemple "i1")
-            let internal_var = format!("{}_{}", user_var, self.nb_for_boucle);
-            self.current_loop_var = Some((user_var.clone(), internal_var.clone()));
-            if let Some(init_expr) = &var_decl.init {
-                self.generate_expression(init_expr);
-            } else {
-                self.emit("    mov rax, 0".to_string());
+        // Le compteur n'est visible que dans la boucle : le nom qu'il masque (compteur d'une
+        // boucle englobante, variable ou constante) est rétabli à la sortie
+        let saved_vars = self.local_vars.clone();
+        let saved_types = self.local_var_types.clone();
+        let saved_array_lengths = self.local_array_lengths.clone();
+        match for_stmt.init.as_deref() {
+            Some(Statement::VarDeclaration(var_decl)) => {
+                // L'initialiseur est évalué avant que le compteur ne masque un nom englobant
+                if let Some(init_expr) = &var_decl.init {
+                    self.generate_expression(init_expr);
+                } else {
+                    self.emit("    mov rax, 0".to_string());
+                }
+                self.local_offset += 8;
+                self.local_vars.insert(var_decl.name, -self.local_offset);
+                self.local_var_types.insert(var_decl.name, var_decl.type_name.clone());
+                self.local_array_lengths.remove(&var_decl.name);
+                self.emit(format!("    mov [rbp - {}], rax", self.local_offset));
             }
-            self.emit(format!("    mov [{}], rax", internal_var));
-            self.asm
-                .section_bss
-                .push(format!("    {} resq 1", internal_var));
-        } else {
-            self.generate_statement(&for_stmt.init);
+            Some(init) => self.generate_statement(init),
+            None => {}
         }
 
         let start_label = self.new_label();
+        let exit_label = self.new_label();
         self.emit(format!("{}:", start_label));
 
-        if let Statement::ExpressionStatement(cond_expr) = &*for_stmt.cond {
-            self.generate_expression(cond_expr);
-            let exit_label = self.new_label();
-            self.emit("    cmp rax, 0".to_string());
-            self.emit(format!("    je {}", exit_label));
-            for stmt in &for_stmt.body {
-                self.generate_statement(stmt);
-            }
-            self.generate_statement(&for_stmt.incr);
-            self.emit(format!("    jmp {}", start_label));
-            self.emit(format!("{}:", exit_label));
-        } else {
-            self.emit("    ; For-loop condition must be an expression statement".to_string());
+        // Sans condition, la boucle ne se termine que par un `break`
+        match for_stmt.cond.as_deref() {
+            Some(Statement::ExpressionStatement(cond_expr)) => {
+                self.generate_expression(cond_expr);
+                self.emit("    cmp rax, 0".to_string());
+                self.emit(format!("    je {}", exit_label));
+            }
+            Some(_) => {
+                self.emit("    ; For-loop condition must be an expression statement".to_string());
+            }
+            None => {}
         }
 
-        self.current_loop_var = None;
+        // `continue` passe par l'incrément : sauter directement à la condition ne ferait
+        // jamais avancer la variable de boucle
+        let incr_label = self.new_label();
+        self.loop_exit_labels.push(exit_label.clone());
+        self.loop_continue_labels.push(incr_label.clone());
+        self.loop_names.push(for_stmt.label.clone());
+        self.generate_block(&for_stmt.body);
+        self.loop_names.pop();
+        self.loop_continue_labels.pop();
+        self.loop_exit_labels.pop();
+        self.emit(format!("{}:", incr_label));
+        // Les instructions de l'incrément s'exécutent dans l'ordre, avant de revenir à la condition
+        for incr in &for_stmt.incr {
+            self.generate_statement(incr);
+        }
+        self.emit(format!("    jmp {}", start_label));
+        self.emit(format!("{}:", exit_label));
+
+        self.local_vars = saved_vars;
+        self.local_var_types = saved_types;
+        self.local_array_lengths = saved_array_lengths;
     }
 
     /// Génère le code pour une boucle while.
     ///
+    /// La condition n'est générée qu'une fois, en tête de boucle : un appel de fonction qu'elle
+    /// contient s'exécute exactement une fois par vérification.
+    ///
     /// # Arguments
     ///
     /// * `while_stmt` - Une référence à une instruction while.
@@ -320,42 +821,100 @@ impl CodeGenerator {
         let exit_label = self.new_label();
         self.emit("    cmp rax, 0".to_string());
         self.emit(format!("    je {}", exit_label));
-        for stmt in &while_stmt.body {
-            self.generate_statement(stmt);
-        }
+        self.loop_exit_labels.push(exit_label.clone());
+        self.loop_continue_labels.push(start_label.clone());
+        self.loop_names.push(while_stmt.label.clone());
+        self.generate_block(&while_stmt.body);
+        self.loop_names.pop();
+        self.loop_continue_labels.pop();
+        self.loop_exit_labels.pop();
         self.emit(format!("    jmp {}", start_label));
         self.emit(format!("{}:", exit_label));
     }
 
     /// Génère le code pour une instruction switch.
     ///
+    /// Chaque cas est comparé par valeur avec `cmp` ; l'analyse sémantique garantit que la
+    /// condition est un `int` ou un `bool` (jamais un pointeur de chaîne). La condition n'est
+    /// évaluée qu'une fois : sa valeur est conservée dans un emplacement du cadre de pile, relu
+    /// pour chaque cas, si bien qu'un appel de fonction qu'elle contient ne s'exécute qu'une fois.
+    ///
     /// # Arguments
     ///
     /// * `switch_stmt` - Une référence à une instruction switch.
     fn generate_switch_statement(&mut self, switch_stmt: &SwitchStatement) {
         self.generate_expression(&switch_stmt.condition);
+        self.local_offset += 8;
+        let condition_slot = self.local_offset;
+        self.emit(format!("    mov [rbp - {}], rax", condition_slot));
         let end_label = self.new_label();
         for case in &switch_stmt.cases {
             let case_label = self.new_label();
-            self.generate_expression(&case.value);
+            if let Some(range_end) = &case.range_end {
+                self.generate_case_range(
+                    condition_slot,
+                    &case.value,
+                    range_end,
+                    &case_label,
+                );
+                self.generate_block(&case.body);
+                self.emit(format!("    jmp {}", end_label));
+                self.emit(format!("{}:", case_label));
+                continue;
+            }
+            // Une valeur constante, négative comprise (`case -1`), est chargée comme immédiat,
+            // sans négation ni contrôle de dépassement à l'exécution
+            match self.const_value(&case.value) {
+                Ok(value) => self.generate_expression(&const_literal(value)),
+                Err(_) => self.generate_expression(&case.value),
+            }
             self.emit("    mov rbx, rax".to_string());
-            self.generate_expression(&switch_stmt.condition);
+            self.emit(format!("    mov rax, [rbp - {}]", condition_slot));
             self.emit("    cmp rax, rbx".to_string());
             self.emit(format!("    jne {}", case_label));
-            for stmt in &case.body {
-                self.generate_statement(stmt);
-            }
+            self.generate_block(&case.body);
             self.emit(format!("    jmp {}", end_label));
             self.emit(format!("{}:", case_label));
         }
         if let Some(default_body) = &switch_stmt.default {
-            for stmt in default_body {
-                self.generate_statement(stmt);
-            }
+            self.generate_block(default_body);
         }
         self.emit(format!("{}:", end_label));
     }
 
+    /// Génère le test d'un cas `case low..high` : la condition est comparée aux deux bornes,
+    /// incluses, et le cas est sauté (`jl`/`jg`) si elle est hors de l'intervalle.
+    ///
+    /// L'analyse sémantique garantit que les bornes sont des entiers constants.
+    ///
+    /// # Arguments
+    ///
+    /// * `condition_slot` - L'emplacement du cadre de pile contenant la valeur de la condition.
+    /// * `low` - La borne inférieure de l'intervalle.
+    /// * `high` - La borne supérieure de l'intervalle.
+    /// * `case_label` - Le label qui suit le corps du cas.
+    fn generate_case_range(
+        &mut self,
+        condition_slot: i32,
+        low: &Expression,
+        high: &Expression,
+        case_label: &str,
+    ) {
+        let bounds = (self.const_value(low), self.const_value(high));
+        let (Ok(ConstValue::Int(low)), Ok(ConstValue::Int(high))) = bounds else {
+            self.emit(format!("    ; case range {}..{} with non-constant bounds", low, high));
+            self.emit(format!("    jmp {}", case_label));
+            return;
+        };
+        self.emit(format!("    mov rax, [rbp - {}]", condition_slot));
+        self.emit(format!("    mov rbx, {}", low));
+        self.emit("    cmp rax, rbx".to_string());
+        self.emit(format!("    jl {}", case_label));
+        self.emit(format!("    mov rbx, {}", high));
+        self.emit("    cmp rax, rbx".to_string());
+        self.emit(format!("    jg {}", case_label));
+    }
+
     /// Génère la définition d'une fonction.
     ///
     /// La méthode produit le prologue (avec allocation de pile), l'insertion des paramètres,
@@ -368,38 +927,51 @@ impl CodeGenerator {
         // Sauvegarde de la section courante
         let saved_section = std::mem::replace(
             &mut self.current_section,
-            SectionCode::new(format!("f_{}:", func_decl.name)),
+            SectionCode::new(format!("{}:", function_symbol(&func_decl.name))),
         );
 
         // Prologue de fonction
         self.emit("    push rbp".to_string());
         self.emit("    mov rbp, rsp".to_string());
-        // Allocation fixe de 16 octets pour les variables locales
+        // Allocation des variables locales : la taille est corrigée une fois le corps généré
+        let frame_index = self.current_section.code.len();
         self.emit("    sub rsp, 16".to_string());
 
         // Insertion des paramètres dans la table des variables.
-        // Correction : le premier paramètre est à [rbp+16] (puisque [rbp+8] contient l'adresse de retour)
-        let mut param_offset = 16;
-        for param in &func_decl.parameters {
-            self.local_vars.insert(param.name.clone(), param_offset);
-            param_offset += 4; // on suppose des int sur 4 octets
+        // L'appelant pousse les arguments de gauche à droite : le dernier est juste au-dessus
+        // de l'adresse de retour ([rbp + 16]) et chaque argument précédent 8 octets plus haut.
+        let param_count = func_decl.parameters.len() as i32;
+        for (index, param) in func_decl.parameters.iter().enumerate() {
+            let param_offset = 16 + 8 * (param_count - 1 - index as i32);
+            self.local_vars.insert(param.name, param_offset);
+            self.local_var_types
+                .insert(param.name, param.type_name.clone());
         }
 
         self.in_function = true;
-        // Pour les variables locales, on démarre à 4 (les premiers locaux seront à [rbp - 4], [rbp - 8], etc.)
-        self.local_offset = 4;
+        // Les variables locales sont placées à [rbp - 8], [rbp - 16], etc. ; le cadre du
+        // programme principal reprend sa taille une fois la fonction générée
+        let saved_offset = std::mem::replace(&mut self.local_offset, 0);
 
         // Génération du corps de la fonction
         for stmt in &func_decl.body {
             self.generate_statement(stmt);
         }
 
-        // Épilogue de fonction
+        // Taille du cadre arrondie à 16 octets pour conserver l'alignement de la pile
+        let frame_size = ((self.local_offset + 15) / 16 * 16).max(16);
+        self.current_section.code[frame_index] = format!("    sub rsp, {}", frame_size);
+
+        // Épilogue de fonction, atteint aussi en fin de corps : une fonction `void` sans
+        // `return` rend simplement la main à l'appelant
         self.emit("    mov rsp, rbp".to_string());
         self.emit("    pop rbp".to_string());
         self.emit("    ret".to_string());
         self.in_function = false;
+        self.local_offset = saved_offset;
         self.local_vars.clear();
+        self.local_array_lengths.clear();
+        self.local_var_types.clear();
 
         // Remet la section précédente et ajoute la fonction générée aux sections
         let function_section = std::mem::replace(&mut self.current_section, saved_section);
@@ -421,43 +993,114 @@ impl CodeGenerator {
                 self.emit(format!("    lea rax, [rel {}]", label));
             }
             Expression::Int(val) => self.emit(format!("    mov rax, {}", val)),
-            Expression::Float(val) => self.emit(format!("    mov rax, {}", val)),
+            Expression::Float(val) => {
+                // Le motif binaire IEEE 754 est exact, quelle que soit la magnitude du littéral
+                self.emit(format!("    mov rax, 0x{:016x} ; {:e}", val.to_bits(), val))
+            }
             Expression::Bool(val) => {
                 self.emit(format!("    mov rax, {}", if *val { 1 } else { 0 }))
             }
             Expression::Ident(name) => {
-                // Si la variable correspond à celle d'une boucle for, on utilise le nom interne
-                if let Some((user_var, internal_var)) = &self.current_loop_var {
-                    if name == user_var {
-                        self.emit(format!("    mov rax, [{}]", internal_var));
-                        return;
-                    }
-                }
-                // Sinon, récupère l'offset dans les variables locales ou accède à la variable globale
+                // Récupère l'offset dans les variables locales ou accède à la variable globale
                 if let Some(off) = self.local_vars.get(name) {
                     if *off >= 0 {
                         self.emit(format!("    mov rax, [rbp + {}]", off));
                     } else {
                         self.emit(format!("    mov rax, [rbp - {}]", -off));
                     }
+                } else if let Some(value) = self.visible_constant(*name) {
+                    // Une constante globale connue à la compilation est insérée comme immédiat,
+                    // sans lecture en mémoire
+                    self.generate_expression(&const_literal(value));
+                } else {
+                    self.emit(format!("    mov rax, [rel {}]", global_symbol(name)));
+                }
+            }
+            Expression::Binary(bin_expr) if bin_expr.op == "&&" || bin_expr.op == "||" => {
+                // Évaluation court-circuitée : l'opérande droit n'est évalué que si le gauche
+                // ne suffit pas à déterminer le résultat
+                let end_label = self.new_label();
+                self.generate_expression(&bin_expr.left);
+                self.emit("    cmp rax, 0".to_string());
+                let jump = if bin_expr.op == "&&" { "je" } else { "jne" };
+                self.emit(format!("    {} {}", jump, end_label));
+                self.generate_expression(&bin_expr.right);
+                self.emit(format!("{}:", end_label));
+                // Normalise le résultat en 0/1 quel que soit le chemin emprunté
+                self.emit("    cmp rax, 0".to_string());
+                self.emit("    setne al".to_string());
+                self.emit("    movzx rax, al".to_string());
+            }
+            Expression::Binary(bin_expr) if bin_expr.op == "**" => {
+                self.generate_expression(&bin_expr.left);
+                self.emit("    push rax".to_string());
+                self.generate_expression(&bin_expr.right);
+                self.emit("    mov rbx, rax".to_string());
+                self.emit("    pop rax".to_string());
+                if self.is_float_expression(&bin_expr.left) {
+                    // Puissance flottante : pow(base, exposant) de la libm, avec une pile
+                    // alignée sur 16 octets ; rbx, préservé par l'appel, garde l'ancien rsp
+                    self.emit("    movq xmm0, rax".to_string());
+                    self.emit("    movq xmm1, rbx".to_string());
+                    self.emit("    mov rbx, rsp".to_string());
+                    self.emit("    and rsp, -16".to_string());
+                    self.emit(self.target.c_call("pow"));
+                    self.require_extern("pow");
+                    self.emit("    mov rsp, rbx".to_string());
+                    self.emit("    movq rax, xmm0".to_string());
                 } else {
-                    self.emit(format!("    mov rax, [{}]", name));
+                    // Puissance entière par multiplications successives ; un exposant négatif
+                    // donne 0
+                    let loop_label = self.new_label();
+                    let negative_label = self.new_label();
+                    let end_label = self.new_label();
+                    self.emit("    mov rcx, rbx".to_string());
+                    self.emit("    mov rbx, rax".to_string());
+                    self.emit("    mov rax, 1".to_string());
+                    self.emit("    cmp rcx, 0".to_string());
+                    self.emit(format!("    jl {}", negative_label));
+                    self.emit(format!("{}:", loop_label));
+                    self.emit("    cmp rcx, 0".to_string());
+                    self.emit(format!("    je {}", end_label));
+                    self.emit("    imul rax, rbx".to_string());
+                    self.generate_overflow_check();
+                    self.emit("    dec rcx".to_string());
+                    self.emit(format!("    jmp {}", loop_label));
+                    self.emit(format!("{}:", negative_label));
+                    self.emit("    xor rax, rax".to_string());
+                    self.emit(format!("{}:", end_label));
                 }
             }
             Expression::Binary(bin_expr) => {
+                // L'opérande gauche est toujours évalué avant le droit
                 self.generate_expression(&bin_expr.left);
                 self.emit("    push rax".to_string());
                 self.generate_expression(&bin_expr.right);
                 self.emit("    pop rbx".to_string());
                 self.emit("    xchg rax, rbx".to_string());
+                if self.is_float_expression(&bin_expr.left) {
+                    self.generate_float_binary(&bin_expr.op);
+                    return;
+                }
                 match bin_expr.op.as_str() {
-                    "+" => self.emit("    add rax, rbx".to_string()),
-                    "-" => self.emit("    sub rax, rbx".to_string()),
-                    "*" => self.emit("    imul rax, rbx".to_string()),
+                    "+" => {
+                        self.emit("    add rax, rbx".to_string());
+                        self.generate_overflow_check();
+                    }
+                    "-" => {
+                        self.emit("    sub rax, rbx".to_string());
+                        self.generate_overflow_check();
+                    }
+                    "*" => {
+                        self.emit("    imul rax, rbx".to_string());
+                        self.generate_overflow_check();
+                    }
                     "/" => {
                         self.emit("    cqo".to_string());
                         self.emit("    idiv rbx".to_string());
                     }
+                    // Les booléens sont toujours matérialisés en 0/1 (littéraux, `setcc` + `movzx`),
+                    // donc `==`/`!=` entre deux booléens se réduit à une comparaison d'entiers.
                     "==" => {
                         self.emit("    cmp rax, rbx".to_string());
                         self.emit("    sete al".to_string());
@@ -488,12 +1131,6 @@ impl CodeGenerator {
                         self.emit("    setge al".to_string());
                         self.emit("    movzx rax, al".to_string());
                     }
-                    "&&" => {
-                        self.emit("    and rax, rbx".to_string());
-                    }
-                    "||" => {
-                        self.emit("    or rax, rbx".to_string());
-                    }
                     "%" => {
                         self.emit("    cqo".to_string());
                         self.emit("    idiv rbx".to_string());
@@ -502,37 +1139,357 @@ impl CodeGenerator {
                     _ => self.emit("    ; Unsupported binary operator".to_string()),
                 }
             }
-            Expression::FunctionCall(call) if call.name == "print" => {
-                if call.arguments.len() == 1 {
-                    self.generate_expression(&call.arguments[0]);
-                    match &call.arguments[0] {
-                        Expression::Str(_) => self.emit("    lea rdi, [rel format]".to_string()),
-                        Expression::Int(_) => self.emit("    lea rdi, [rel format]".to_string()),
-                        Expression::Float(_) => self.emit("    lea rdi, [rel format]".to_string()),
-                        _ => self.emit("    lea rdi, [rel format]".to_string()),
+            Expression::Comparison(chain) => {
+                // `a < b < c` vaut `(a < b) && (b < c)` : chaque opérande est évalué une seule
+                // fois et reste sur la pile pour servir d'opérande gauche à la comparaison suivante
+                let end_label = self.new_label();
+                self.generate_expression(&chain.operands[0]);
+                self.emit("    push rax".to_string());
+                let last = chain.ops.len() - 1;
+                for (index, (op, operand)) in chain.ops.iter().zip(&chain.operands[1..]).enumerate() {
+                    self.generate_expression(operand);
+                    self.emit("    mov rbx, rax".to_string());
+                    self.emit("    pop rax".to_string());
+                    self.emit("    push rbx".to_string());
+                    if self.is_float_expression(&chain.operands[0]) {
+                        self.emit("    movq xmm0, rax".to_string());
+                        self.emit("    movq xmm1, rbx".to_string());
+                        self.generate_float_comparison(op);
+                    } else {
+                        self.emit("    cmp rax, rbx".to_string());
+                        self.emit(format!("    {} al", set_instruction(op)));
+                        self.emit("    movzx rax, al".to_string());
+                    }
+                    // Une comparaison fausse termine la chaîne sans évaluer les opérandes suivants
+                    if index < last {
+                        self.emit("    cmp rax, 0".to_string());
+                        self.emit(format!("    je {}", end_label));
+                    }
+                }
+                self.emit(format!("{}:", end_label));
+                // Retire le dernier opérande conservé sur la pile
+                self.emit("    add rsp, 8".to_string());
+            }
+            // Un littéral de tableau n'est généré que par la déclaration qu'il initialise
+            Expression::ArrayLiteral(_) => {
+                self.emit("    ; Array literal outside an array declaration".to_string())
+            }
+            // `-` appliqué à un littéral flottant donne directement la constante négative
+            Expression::Unary(unary_expr) if unary_expr.op == "-" && matches!(unary_expr.operand, Expression::Float(_)) => {
+                if let Expression::Float(val) = unary_expr.operand {
+                    self.generate_expression(&Expression::Float(-val));
+                }
+            }
+            Expression::Unary(unary_expr) => {
+                self.generate_expression(&unary_expr.operand);
+                match unary_expr.op.as_str() {
+                    // Un flottant est conservé en bits IEEE 754 dans rax : l'opposé ne diffère
+                    // que par le bit de signe, qu'on inverse comme le ferait `xorpd` avec un masque
+                    "-" if self.is_float_expression(&unary_expr.operand) => {
+                        self.emit("    btc rax, 63".to_string());
+                    }
+                    "-" => {
+                        self.emit("    neg rax".to_string());
+                        self.generate_overflow_check();
+                    }
+                    "!" => {
+                        self.emit("    cmp rax, 0".to_string());
+                        self.emit("    sete al".to_string());
+                        self.emit("    movzx rax, al".to_string());
+                    }
+                    // `+x` vaut simplement x
+                    "+" => {}
+                    _ => self.emit("    ; Unsupported unary operator".to_string()),
+                }
+            }
+            Expression::Index(index_expr) if self.is_string_expression(&index_expr.target) => {
+                // Octet d'une chaîne : la chaîne est évaluée avant l'index, puis l'octet est
+                // chargé et étendu à 64 bits
+                self.generate_expression(&index_expr.target);
+                self.emit("    push rax".to_string());
+                self.generate_expression(&index_expr.index);
+                self.emit("    mov rcx, rax".to_string());
+                self.emit("    pop rax".to_string());
+                if self.bounds_check {
+                    // La longueur n'est connue qu'à l'exécution : elle est mesurée par le runtime
+                    self.emit("    push rax".to_string());
+                    self.emit("    push rcx".to_string());
+                    self.emit("    mov rdi, rax".to_string());
+                    self.emit(self.target.c_call(self.runtime.string_length_function()));
+                    self.emit("    mov rdx, rax".to_string());
+                    self.emit("    pop rcx".to_string());
+                    self.emit("    pop rax".to_string());
+                    self.emit("    cmp rcx, rdx".to_string());
+                    self.emit("    jae bounds_error".to_string());
+                    self.require_abort_routine("bounds_error", "Index out of bounds");
+                }
+                self.emit("    movzx rax, byte [rax + rcx]".to_string());
+            }
+            Expression::Index(index_expr) => {
+                if let Expression::Ident(name) = &index_expr.target {
+                    self.generate_array_index(*name, &index_expr.index);
+                    self.generate_bounds_check(*name, "rax");
+                    self.generate_array_base(*name);
+                    self.emit("    mov rax, [rbx + rax * 8]".to_string());
+                } else {
+                    self.emit("    ; Unsupported index target".to_string());
+                }
+            }
+            Expression::FunctionCall(call) if call.name == "print" && call.arguments.len() == 1 => {
+                let arg = &call.arguments[0];
+                self.generate_expression(arg);
+                if self.is_float_expression(arg) {
+                    // Un flottant est passé dans xmm0 et `al` indique le nombre de registres
+                    // vectoriels utilisés ; printf exige alors une pile alignée sur 16 octets.
+                    // rbx est préservé par l'appel et garde l'ancienne valeur de rsp.
+                    self.emit("    movq xmm0, rax".to_string());
+                    self.emit("    lea rdi, [rel format_float]".to_string());
+                    self.emit("    mov rbx, rsp".to_string());
+                    self.emit("    and rsp, -16".to_string());
+                    self.emit("    mov rax, 1".to_string());
+                    self.emit(self.target.c_call("printf"));
+                    self.emit("    mov rsp, rbx".to_string());
+                    self.emit("    movsxd rax, eax".to_string());
+                    return;
+                }
+                if self.is_bool_expression(arg) {
+                    // Un booléen est affiché en toutes lettres
+                    self.emit("    cmp rax, 0".to_string());
+                    self.emit("    lea rsi, [rel text_false]".to_string());
+                    self.emit("    lea rax, [rel text_true]".to_string());
+                    self.emit("    cmovne rsi, rax".to_string());
+                    self.emit("    lea rdi, [rel format_str]".to_string());
+                } else {
+                    // Une chaîne est passée en argument de `%s`, jamais comme format : un `%`
+                    // qu'elle contient est affiché tel quel
+                    if self.is_string_expression(arg) {
+                        self.emit("    lea rdi, [rel format_str]".to_string());
+                    } else {
+                        self.emit("    lea rdi, [rel format]".to_string());
                     }
                     self.emit("    mov rsi, rax".to_string());
-                    self.emit("    xor rax, rax".to_string());
-                    self.emit("    call printf".to_string());
+                }
+                self.emit("    xor rax, rax".to_string());
+                self.emit(self.target.c_call("printf"));
+                // printf renvoie le nombre d'octets écrits dans eax, la valeur de `print`
+                self.emit("    movsxd rax, eax".to_string());
+            }
+            Expression::FunctionCall(call) if call.name == "readint" && call.arguments.is_empty() => {
+                // scanf écrit l'entier lu dans un emplacement de la pile, alignée sur 16 octets
+                // comme pour printf ; rbx garde l'ancienne valeur de rsp. L'emplacement est mis
+                // à 0 avant l'appel : sans nombre à lire, `readint()` vaut 0
+                self.emit("    mov rbx, rsp".to_string());
+                self.emit("    and rsp, -16".to_string());
+                self.emit("    sub rsp, 16".to_string());
+                self.emit("    mov qword [rsp], 0".to_string());
+                self.emit("    lea rdi, [rel format_read_int]".to_string());
+                self.emit("    mov rsi, rsp".to_string());
+                self.emit("    xor rax, rax".to_string());
+                self.emit(self.target.c_call("scanf"));
+                self.emit("    mov rax, [rsp]".to_string());
+                self.emit("    mov rsp, rbx".to_string());
+            }
+            Expression::FunctionCall(call) if call.name == "todo" && call.arguments.is_empty() => {
+                // `todo()` ne rend jamais la main : la routine d'arrêt termine le programme
+                self.emit("    jmp todo_error".to_string());
+                self.require_abort_routine("todo_error", "not yet implemented");
+            }
+            Expression::FunctionCall(call) if call.name == "len" && call.arguments.len() == 1 => {
+                // La longueur d'un littéral est connue à la compilation : pas de mesure à l'exécution
+                if let Some(ConstValue::Int(length)) = eval_const(expr) {
+                    self.emit(format!("    mov rax, {}", length));
+                } else {
+                    self.generate_expression(&call.arguments[0]);
+                    self.emit("    mov rdi, rax".to_string());
+                    self.emit(self.target.c_call(self.runtime.string_length_function()));
                 }
             }
             Expression::FunctionCall(call) => {
                 // Traitement générique des appels de fonction autres que print.
-                // Pour chaque argument, on génère son code et on le pousse sur la pile.
+                // Les arguments sont évalués strictement de gauche à droite, chacun poussé sur
+                // la pile avant d'évaluer le suivant : leurs effets de bord suivent l'ordre du source.
                 for arg in &call.arguments {
                     self.generate_expression(arg);
                     self.emit("    push rax".to_string());
                 }
                 // Appel de la fonction. On suppose que le label de la fonction est préfixé par "f_"
-                self.emit(format!("    call f_{}", call.name));
+                self.emit(format!("    call {}", function_symbol(&call.name)));
                 // Nettoyage de la pile (8 octets par argument)
                 if !call.arguments.is_empty() {
                     self.emit(format!("    add rsp, {}", 8 * call.arguments.len()));
                 }
             }
+        }
+    }
+
+    /// Génère une opération binaire entre deux flottants, dont les bits IEEE 754 sont dans `rax`
+    /// (opérande gauche) et `rbx` (opérande droit) : le calcul se fait dans les registres SSE
+    /// et le résultat revient dans `rax`.
+    ///
+    /// # Arguments
+    ///
+    /// * `op` - L'opérateur (`+`, `-`, `*`, `/` ou une comparaison).
+    fn generate_float_binary(&mut self, op: &str) {
+        self.emit("    movq xmm0, rax".to_string());
+        self.emit("    movq xmm1, rbx".to_string());
+        let instruction = match op {
+            "+" => "addsd",
+            "-" => "subsd",
+            "*" => "mulsd",
+            "/" => "divsd",
+            _ => {
+                self.generate_float_comparison(op);
+                return;
+            }
+        };
+        self.emit(format!("    {} xmm0, xmm1", instruction));
+        self.emit("    movq rax, xmm0".to_string());
+    }
+
+    /// Compare les flottants de `xmm0` (gauche) et `xmm1` (droite) et place 0 ou 1 dans `rax`.
+    ///
+    /// `ucomisd` positionne les indicateurs comme une comparaison non signée, et les lève tous
+    /// (ZF, PF et CF) si un opérande est NaN : toute comparaison avec NaN est alors fausse, sauf
+    /// `!=`. `<` et `<=` échangent leurs opérandes pour n'utiliser que `seta`/`setae`, faux
+    /// sur NaN.
+    ///
+    /// # Arguments
+    ///
+    /// * `op` - L'opérateur de comparaison.
+    fn generate_float_comparison(&mut self, op: &str) {
+        match op {
+            "==" | "!=" => {
+                self.emit("    ucomisd xmm0, xmm1".to_string());
+                if op == "==" {
+                    self.emit("    sete al".to_string());
+                    self.emit("    setnp cl".to_string());
+                    self.emit("    and al, cl".to_string());
+                } else {
+                    self.emit("    setne al".to_string());
+                    self.emit("    setp cl".to_string());
+                    self.emit("    or al, cl".to_string());
+                }
+            }
+            "<" | "<=" => {
+                self.emit("    ucomisd xmm1, xmm0".to_string());
+                self.emit(format!("    {} al", if op == "<" { "seta" } else { "setae" }));
+            }
+            ">" | ">=" => {
+                self.emit("    ucomisd xmm0, xmm1".to_string());
+                self.emit(format!("    {} al", if op == ">" { "seta" } else { "setae" }));
+            }
             _ => {
-                // Autres cas d'expressions…
+                self.emit("    ; Unsupported float operator".to_string());
+                return;
+            }
+        }
+        self.emit("    movzx rax, al".to_string());
+    }
+
+    /// Indique si une expression produit un flottant, d'après le contexte de types ou, à
+    /// défaut, les types déclarés.
+    ///
+    /// # Arguments
+    ///
+    /// * `expr` - L'expression dont on cherche le type.
+    fn is_float_expression(&self, expr: &Expression) -> bool {
+        if let Some(type_name) = self.type_context.get(expr) {
+            return type_name == "float";
+        }
+        match expr {
+            Expression::Float(_) => true,
+            Expression::Ident(name) => self.variable_type(*name) == Some("float"),
+            Expression::Index(index_expr) => match &index_expr.target {
+                Expression::Ident(name) => self.variable_type(*name) == Some("float"),
+                _ => false,
+            },
+            Expression::Unary(unary_expr) => {
+                matches!(unary_expr.op.as_str(), "-" | "+")
+                    && self.is_float_expression(&unary_expr.operand)
+            }
+            Expression::Binary(bin_expr) => {
+                matches!(bin_expr.op.as_str(), "+" | "-" | "*" | "/" | "**")
+                    && (self.is_float_expression(&bin_expr.left)
+                        || self.is_float_expression(&bin_expr.right))
+            }
+            Expression::FunctionCall(call) => {
+                self.function_return_types.get(&call.name).map(String::as_str) == Some("float")
+            }
+            _ => false,
+        }
+    }
+
+    /// Indique si une expression produit une chaîne, d'après le contexte de types ou, à
+    /// défaut, les types déclarés.
+    ///
+    /// # Arguments
+    ///
+    /// * `expr` - L'expression dont on cherche le type.
+    fn is_string_expression(&self, expr: &Expression) -> bool {
+        if let Some(type_name) = self.type_context.get(expr) {
+            return type_name == "string";
+        }
+        match expr {
+            Expression::Str(_) => true,
+            Expression::Ident(name) => {
+                self.variable_type(*name) == Some("string") && !self.is_array(*name)
+            }
+            // Un élément de tableau de chaînes est une chaîne, un octet de chaîne est un entier
+            Expression::Index(index_expr) => match &index_expr.target {
+                Expression::Ident(name) => {
+                    self.variable_type(*name) == Some("string") && self.is_array(*name)
+                }
+                _ => false,
+            },
+            Expression::FunctionCall(call) => {
+                self.function_return_types.get(&call.name).map(String::as_str) == Some("string")
+            }
+            _ => false,
+        }
+    }
+
+    /// Indique si une expression produit un booléen, d'après le contexte de types ou, à
+    /// défaut, les types déclarés et les opérateurs.
+    ///
+    /// # Arguments
+    ///
+    /// * `expr` - L'expression dont on cherche le type.
+    fn is_bool_expression(&self, expr: &Expression) -> bool {
+        if let Some(type_name) = self.type_context.get(expr) {
+            return type_name == "bool";
+        }
+        match expr {
+            Expression::Bool(_) | Expression::Comparison(_) => true,
+            Expression::Ident(name) => {
+                self.variable_type(*name) == Some("bool") && !self.is_array(*name)
             }
+            Expression::Index(index_expr) => match &index_expr.target {
+                Expression::Ident(name) => self.variable_type(*name) == Some("bool"),
+                _ => false,
+            },
+            Expression::Unary(unary_expr) => unary_expr.op == "!",
+            Expression::Binary(bin_expr) => matches!(
+                bin_expr.op.as_str(),
+                "==" | "!=" | "<" | "<=" | ">" | ">=" | "&&" | "||"
+            ),
+            Expression::FunctionCall(call) => {
+                self.function_return_types.get(&call.name).map(String::as_str) == Some("bool")
+            }
+            _ => false,
+        }
+    }
+
+    /// Indique si une variable est un tableau, la variable locale masquant la globale.
+    fn is_array(&self, name: Name) -> bool {
+        self.array_length(name).is_some()
+    }
+
+    /// Retourne le type déclaré d'une variable, la variable locale masquant la globale.
+    fn variable_type(&self, name: Name) -> Option<&str> {
+        if self.local_vars.contains_key(&name) {
+            self.local_var_types.get(&name).map(String::as_str)
+        } else {
+            self.global_var_types.get(&name).map(String::as_str)
         }
     }
 
@@ -0,0 +1,714 @@
+#[cfg(test)]
+mod codegen_tests {
+    use crate::codegen::codegen::CodeGenerator;
+    use crate::codegen::models::target::Target;
+    use crate::semantic::analyzer::SemanticAnalyzer;
+
+    // Helper function to run the analyzer and the code generator on source code
+    fn generate_source(source: &str) -> String {
+        let mut analyzer = SemanticAnalyzer::new(source.to_string());
+        analyzer.analyze();
+        assert!(analyzer.errors.is_empty(), "Unexpected semantic errors: {:?}", analyzer.errors);
+        let mut codegen = CodeGenerator::new();
+        codegen.type_context = std::mem::take(&mut analyzer.type_context);
+        codegen.generate(&analyzer.ast, &analyzer.symbol_table);
+        codegen.asm.join("\n")
+    }
+
+    #[test]
+    fn test_bool_equality() {
+        let asm = generate_source("let a: bool = true == false;\nlet b: bool = true != false;");
+        // Les opérandes booléens sont matérialisés en 0/1 avant la comparaison
+        assert!(asm.contains("    mov rax, 1\n    push rax\n    mov rax, 0"));
+        assert!(asm.contains("    cmp rax, rbx\n    sete al\n    movzx rax, al"));
+        assert!(asm.contains("    cmp rax, rbx\n    setne al\n    movzx rax, al"));
+    }
+
+    #[test]
+    fn test_const_array_size() {
+        let asm = generate_source(
+            "const N: int = 8;\nlet xs: int[N * 2];\nfunction f(): int {\n    let local: int[N];\n    local[1] = 3;\n    return local[1];\n}",
+        );
+        assert!(asm.contains("    g_xs resq 16"));
+        // 8 emplacements de 8 octets pour le tableau local
+        assert!(asm.contains("    sub rsp, 64"));
+        assert!(asm.contains("    lea rbx, [rbp - 64]\n    mov [rbx + rcx * 8], rax"));
+    }
+
+    #[test]
+    fn test_global_access_is_rip_relative() {
+        let asm = generate_source("let x: int = 1;\nx = x + 1;\nprint(x);");
+        assert!(asm.contains("    mov [rel g_x], rax"));
+        assert!(asm.contains("    mov rax, [rel g_x]"));
+        assert!(asm.contains("    call printf wrt ..plt"));
+        assert!(!asm.contains("[x]"));
+    }
+
+    #[test]
+    fn test_for_with_empty_clauses() {
+        let asm = generate_source("for (;;) {\n    break;\n};");
+        // Pas de test de condition : la seule sortie est le `break`
+        assert!(!asm.contains("cmp rax, 0"));
+        assert!(asm.contains("L0:\n    jmp L1\nL2:\n    jmp L0\nL1:"));
+    }
+
+    #[test]
+    fn test_unary_operators() {
+        let asm = generate_source("let a: int = -(1 + 2);\nlet b: bool = !(a == 3);");
+        assert!(asm.contains("    add rax, rbx\n    neg rax"));
+        assert!(asm.contains("    movzx rax, al\n    cmp rax, 0\n    sete al\n    movzx rax, al"));
+    }
+
+    #[test]
+    fn test_float_literals_are_bit_patterns() {
+        let asm = generate_source("let a: float = 1.5;\nlet b: float = 0.000000000000000000000000000000000000000000001;");
+        assert!(asm.contains("    mov rax, 0x3ff8000000000000 ; 1.5e0"));
+        assert!(asm.contains(&format!("    mov rax, 0x{:016x}", 1e-45_f64.to_bits())));
+    }
+
+    #[test]
+    fn test_evaluation_order_is_left_to_right() {
+        let asm = generate_source(
+            "function tick(n: int): int {\n    print(n);\n    return n;\n}\n\
+             function sub(a: int, b: int): int {\n    return a - b;\n}\n\
+             print(sub(tick(1), tick(2)));\nprint(tick(3) - tick(4));",
+        );
+        let first = asm.find("    mov rax, 1\n    push rax\n    call f_tick").unwrap();
+        let second = asm.find("    mov rax, 2\n    push rax\n    call f_tick").unwrap();
+        let third = asm.find("    mov rax, 3\n    push rax\n    call f_tick").unwrap();
+        let fourth = asm.find("    mov rax, 4\n    push rax\n    call f_tick").unwrap();
+        assert!(first < second);
+        assert!(third < fourth);
+        // Le premier paramètre est le plus éloigné de l'adresse de retour
+        assert!(asm.contains("    mov rax, [rbp + 24]\n    push rax\n    mov rax, [rbp + 16]"));
+    }
+
+    #[test]
+    fn test_bounds_check_flag() {
+        let source = "let xs: int[4];\nlet i: int = 2;\nxs[i] = 1;\nprint(xs[i]);";
+        let asm = generate_source(source);
+        assert!(!asm.contains("bounds_error"));
+
+        let mut analyzer = SemanticAnalyzer::new(source.to_string());
+        analyzer.analyze();
+        assert!(analyzer.errors.is_empty());
+        let mut codegen = CodeGenerator::new();
+        codegen.bounds_check = true;
+        codegen.generate(&analyzer.ast, &analyzer.symbol_table);
+        let asm = codegen.asm.join("\n");
+        // Une vérification pour l'écriture (index dans rcx), une pour la lecture (index dans rax)
+        assert!(asm.contains("    pop rcx\n    cmp rcx, 4\n    jae bounds_error"));
+        assert!(asm.contains("    cmp rax, 4\n    jae bounds_error"));
+        assert_eq!(asm.matches("bounds_error:").count(), 1);
+    }
+
+    #[test]
+    fn test_checked_arith_flag() {
+        let source = "let a: int = 3;\nlet b: int = a * a + a - -a;";
+        let asm = generate_source(source);
+        assert!(!asm.contains("jo overflow_error"));
+
+        let mut analyzer = SemanticAnalyzer::new(source.to_string());
+        analyzer.analyze();
+        assert!(analyzer.errors.is_empty());
+        let mut codegen = CodeGenerator::new();
+        codegen.checked_arith = true;
+        codegen.generate(&analyzer.ast, &analyzer.symbol_table);
+        let asm = codegen.asm.join("\n");
+        for op in ["imul rax, rbx", "add rax, rbx", "sub rax, rbx", "neg rax"] {
+            assert!(asm.contains(&format!("    {}\n    jo overflow_error", op)), "{}", op);
+        }
+        assert_eq!(asm.matches("overflow_error:").count(), 1);
+    }
+
+    #[test]
+    fn test_user_symbols_are_mangled() {
+        let asm = generate_source(
+            "let format: int = 1;\nlet rax: int = 2;\nfunction printf(exit: int): int {\n    return exit;\n}\nprint(printf(format + rax));",
+        );
+        assert!(asm.contains("    g_format resq 1"));
+        assert!(asm.contains("    g_rax resq 1"));
+        assert!(asm.contains("    mov rax, [rel g_format]"));
+        assert!(asm.contains("    call f_printf"));
+        // Le format de `print` reste celui généré par le compilateur
+        assert!(asm.contains("    format: db \"%d\", 10, 0"));
+        assert!(asm.contains("    lea rdi, [rel format]"));
+    }
+
+    #[test]
+    fn test_parameter_shadows_global() {
+        let asm = generate_source(
+            "let x: int = 10;\nfunction f(x: int): int {\n    x = x + 1;\n    return x;\n}\nprint(f(1));\nprint(x);",
+        );
+        let function = &asm[asm.find("f_f:").unwrap()..];
+        let function = &function[..function.find("    ret").unwrap()];
+        // Dans la fonction, `x` désigne uniquement le paramètre
+        assert!(function.contains("    mov rax, [rbp + 16]"));
+        assert!(function.contains("    mov [rbp + 16], rax"));
+        assert!(!function.contains("g_x"));
+        // Hors de la fonction, `x` reste la globale
+        assert!(asm.contains("    mov rax, [rel g_x]"));
+    }
+
+    #[test]
+    fn test_logical_operators_short_circuit() {
+        let asm = generate_source("let x: int = 1;\nlet b: bool = (x > 0) && (x < 5);\nlet c: bool = (x > 0) || (x < 5);");
+        // `&&` saute la partie droite si la gauche est fausse, `||` si elle est vraie
+        assert!(asm.contains("    cmp rax, 0\n    je L0"));
+        assert!(asm.contains("    cmp rax, 0\n    jne L1"));
+        // Le résultat est toujours normalisé en 0/1 avant d'être stocké
+        assert!(asm.contains("L0:\n    cmp rax, 0\n    setne al\n    movzx rax, al\n    mov [rel g_b], rax"));
+        assert!(asm.contains("L1:\n    cmp rax, 0\n    setne al\n    movzx rax, al\n    mov [rel g_c], rax"));
+    }
+
+    #[test]
+    fn test_target_directives() {
+        let source = "let xs: int[2];\nlet i: int = 1;\nprint(xs[i]);";
+        let linux = generate_source(source);
+        assert!(linux.contains("global _start\nextern printf\nextern exit\nextern scanf\nextern strlen\n\n_start:"));
+        assert!(linux.contains("    call printf wrt ..plt"));
+        assert!(linux.contains("    call exit wrt ..plt"));
+
+        let mut analyzer = SemanticAnalyzer::new(source.to_string());
+        analyzer.analyze();
+        assert!(analyzer.errors.is_empty());
+        let mut codegen = CodeGenerator::new();
+        codegen.target = Target::MacOs;
+        codegen.bounds_check = true;
+        codegen.generate(&analyzer.ast, &analyzer.symbol_table);
+        let macos = codegen.asm.join("\n");
+        assert!(macos.contains("global _main\nextern _printf\nextern _exit\nextern _scanf\nextern _strlen\n\n_main:"));
+        assert!(macos.contains("    call _printf"));
+        assert!(macos.contains("    call _exit"));
+        assert!(!macos.contains("wrt ..plt"));
+        assert!(!macos.contains("_start"));
+        // Les appels système macOS passent par la classe BSD (0x2000000)
+        assert!(macos.contains("    mov rax, 0x2000004"));
+    }
+    #[test]
+    fn test_return_in_main_program_exits() {
+        let asm = generate_source("let x: int = 3;\nif (x > 2) {\n    return 7;\n};\nreturn;");
+        // `f_main` rend la main au point d'entrée, qui transmet `rax` à `exit`
+        assert!(asm.contains("    mov rax, 7\n    mov rsp, rbp\n    pop rbp\n    ret"));
+        assert!(asm.contains("L1:\n    xor rax, rax\n    mov rsp, rbp\n    pop rbp\n    ret"));
+
+        // Un booléen est inversé : `true` donne le code de sortie 0, `false` le code 1
+        let asm = generate_source("function ok(): bool {\n    return true;\n}\nreturn ok();");
+        assert!(asm.contains("    call f_ok\n    xor rax, 1\n    mov rsp, rbp"));
+        // Le `return` d'une fonction renvoie le booléen tel quel
+        assert_eq!(asm.matches("xor rax, 1").count(), 1);
+    }
+    #[test]
+    fn test_entry_point_calls_main() {
+        let asm = generate_source("print(1);");
+        // `f_main` est une fonction ordinaire : appelée depuis le point d'entrée, avec prologue
+        // et épilogue, elle pourrait être appelée à nouveau sans casser la pile
+        assert!(asm.contains(
+            "_start:\n    xor rbp, rbp\n    and rsp, -16\n    call f_main\n    mov rdi, rax\n    call exit wrt ..plt"
+        ));
+        assert!(asm.contains("f_main:\n    push rbp\n    mov rbp, rsp\n"));
+        assert!(asm.contains("    xor rax, rax\n    mov rsp, rbp\n    pop rbp\n    ret"));
+        assert!(!asm.contains("jmp f_main"));
+    }
+    #[test]
+    fn test_more_than_six_parameters() {
+        let asm = generate_source(
+            "function sum8(a: int, b: int, c: int, d: int, e: int, f: int, g: int, h: int): int {\n    return a + b + c + d + e + f + g + h;\n}\nprint(sum8(1, 2, 3, 4, 5, 6, 7, 8));",
+        );
+        // Tous les arguments passent par la pile : le premier est le plus haut, le dernier en [rbp + 16]
+        assert!(asm.contains("    mov rax, [rbp + 72]\n    push rax\n    mov rax, [rbp + 64]"));
+        assert!(asm.contains("    mov rax, [rbp + 16]"));
+        assert!(asm.contains("    call f_sum8\n    add rsp, 64"));
+    }
+    #[test]
+    fn test_len_of_literal_is_folded() {
+        let asm = generate_source("print(len(\"hello\"));");
+        assert!(asm.contains("    mov rax, 5\n    lea rdi, [rel format]"));
+        assert!(!asm.contains("call strlen"));
+
+        // Une chaîne non littérale est mesurée à l'exécution
+        let asm = generate_source("let s: string = \"hey\";\nprint(len(s));");
+        assert!(asm.contains("    mov rax, [rel g_s]\n    mov rdi, rax\n    call strlen wrt ..plt"));
+    }
+    #[test]
+    fn test_float_negation() {
+        let asm = generate_source("let a: float = -2.5;\nlet b: float = -(a + 1.0);\nlet c: float = -a;");
+        // Un littéral négatif est émis directement comme constante
+        assert!(asm.contains(&format!("    mov rax, 0x{:016x} ; -2.5e0\n    mov [rel g_a], rax", (-2.5f64).to_bits())));
+        // L'opposé d'un flottant inverse le bit de signe au lieu d'utiliser `neg`
+        assert!(asm.contains("    addsd xmm0, xmm1\n    movq rax, xmm0\n    btc rax, 63\n    mov [rel g_b], rax"));
+        assert!(asm.contains("    mov rax, [rel g_a]\n    btc rax, 63\n    mov [rel g_c], rax"));
+        assert!(!asm.contains("neg rax"));
+        assert!(!asm.contains("add rax, rbx"));
+    }
+
+    #[test]
+    fn test_float_arithmetic_uses_sse() {
+        let asm = generate_source(
+            "let a: float = 1.5;\nlet b: float = 2.0;\n\
+             print(a + b);\nprint(a - b);\nprint(a * b);\nprint(a / b);",
+        );
+        // Les opérandes passent de rax/rbx aux registres SSE, le résultat revient dans rax
+        for instruction in ["addsd", "subsd", "mulsd", "divsd"] {
+            assert!(
+                asm.contains(&format!(
+                    "    xchg rax, rbx\n    movq xmm0, rax\n    movq xmm1, rbx\n    {} xmm0, xmm1\n    movq rax, xmm0",
+                    instruction
+                )),
+                "{}",
+                instruction
+            );
+        }
+        for instruction in ["add rax, rbx", "sub rax, rbx", "imul rax, rbx", "idiv rbx"] {
+            assert!(!asm.contains(instruction), "{}", instruction);
+        }
+
+        let asm = generate_source(
+            "let a: float = 1.5;\nlet b: float = 2.0;\n\
+             print(a < b);\nprint(a <= b);\nprint(a > b);\nprint(a >= b);\nprint(a == b);\nprint(a != b);\nprint(a < b < 3.0);",
+        );
+        // `<` et `<=` échangent les opérandes : seules `seta`/`setae` sont fausses sur NaN
+        assert!(asm.contains("    ucomisd xmm1, xmm0\n    seta al\n    movzx rax, al"));
+        assert!(asm.contains("    ucomisd xmm1, xmm0\n    setae al\n    movzx rax, al"));
+        assert!(asm.contains("    ucomisd xmm0, xmm1\n    seta al\n    movzx rax, al"));
+        assert!(asm.contains("    ucomisd xmm0, xmm1\n    setae al\n    movzx rax, al"));
+        assert!(asm.contains("    ucomisd xmm0, xmm1\n    sete al\n    setnp cl\n    and al, cl"));
+        assert!(asm.contains("    ucomisd xmm0, xmm1\n    setne al\n    setp cl\n    or al, cl"));
+        // Une chaîne de comparaisons flottantes compare aussi avec ucomisd
+        assert!(asm.contains("    push rbx\n    movq xmm0, rax\n    movq xmm1, rbx\n    ucomisd xmm1, xmm0"));
+        assert!(!asm.contains("cmp rax, rbx"));
+
+        // Les entiers gardent les instructions entières
+        let asm = generate_source("let i: int = 3;\nprint(i + 1);\nprint(i < 4);");
+        assert!(!asm.contains("xmm"));
+    }
+    #[test]
+    fn test_module_functions_are_mangled() {
+        let asm = generate_source(
+            "module math {\n    function double(x: int): int {\n        return x * 2;\n    }\n}\nprint(math::double(4));",
+        );
+        assert!(asm.contains("f_math.double:\n    push rbp"));
+        assert!(asm.contains("    call f_math.double"));
+        assert!(!asm.contains("::"));
+
+        // Une fonction globale au nom proche garde un label distinct
+        let asm = generate_source(
+            "module math {\n    function double(x: int): int {\n        return x * 2;\n    }\n}\n\
+             function math_double(x: int): int {\n    return x;\n}\nprint(math::double(4) + math_double(1));",
+        );
+        assert!(asm.contains("f_math.double:\n"));
+        assert!(asm.contains("f_math_double:\n"));
+    }
+
+    #[test]
+    fn test_switch_cases_have_their_own_locals() {
+        let asm = generate_source(
+            "function pick(n: int): int {\n    let result: int = 0;\n    switch (n) {\n        case 1 {\n            let tmp: int = 10;\n            result = tmp;\n        },\n        case 2 {\n            let tmp: int = 20;\n            result = tmp + 1;\n        },\n    };\n    return result;\n}\nprint(pick(2));",
+        );
+        // Chaque `tmp` a son propre emplacement, distinct de ceux de `result` et de la condition
+        assert!(asm.contains("    mov rax, 10\n    mov [rbp - 24], rax"));
+        assert!(asm.contains("    mov rax, 20\n    mov [rbp - 32], rax"));
+        assert!(asm.contains("    mov rax, [rbp - 32]\n    push rax\n    mov rax, 1"));
+        assert!(asm.contains("    sub rsp, 32"));
+    }
+
+    #[test]
+    fn test_comparison_chain_evaluates_operands_once() {
+        let asm = generate_source(
+            "function tick(n: int): int {\n    print(n);\n    return n;\n}\n\
+             let a: bool = 1 < tick(2) < tick(3);",
+        );
+        // Chaque appel n'apparaît qu'une fois ; la seconde comparaison réutilise `tick(2)`
+        assert_eq!(asm.matches("call f_tick").count(), 2);
+        assert!(asm.contains(
+            "    call f_tick\n    add rsp, 8\n    mov rbx, rax\n    pop rax\n    push rbx\n    cmp rax, rbx\n    setl al"
+        ));
+        // Une première comparaison fausse saute la seconde, puis l'opérande conservé est retiré
+        assert!(asm.contains("    cmp rax, 0\n    je L0\n    mov rax, 3"));
+        assert!(asm.contains("L0:\n    add rsp, 8"));
+    }
+
+    #[test]
+    fn test_print_string_uses_percent_s() {
+        let asm = generate_source("let s: string = \"50%d off\";\nprint(\"100%d done\");\nprint(s);\nprint(7);");
+        assert!(asm.contains("    format_str: db \"%s\", 10, 0"));
+        // La chaîne de l'utilisateur est l'argument de `%s`, pas le format de printf
+        assert!(asm.contains("    lea rax, [rel str_1]\n    lea rdi, [rel format_str]\n    mov rsi, rax"));
+        assert!(asm.contains("    mov rax, [rel g_s]\n    lea rdi, [rel format_str]\n    mov rsi, rax"));
+        assert!(asm.contains("    mov rax, 7\n    lea rdi, [rel format]\n    mov rsi, rax"));
+    }
+
+    #[test]
+    fn test_return_inside_loops() {
+        let asm = generate_source(
+            "function first(n: int): int {\n    while (true) {\n        for (let i: int = 0; i < n; i = i + 1;) {\n            return i;\n        };\n    };\n    return -1;\n}\nprint(first(3));",
+        );
+        // Le `return` de la boucle interne démonte le cadre avant de sauter en dehors des boucles
+        assert!(asm.contains("    mov rax, [rbp - 8]\n    mov rsp, rbp\n    pop rbp\n    ret"));
+        // Deux `return` et l'épilogue final
+        let function = asm.split("f_first:").nth(1).unwrap().split("f_main:").next().unwrap();
+        assert_eq!(function.matches("    mov rsp, rbp\n    pop rbp\n    ret").count(), 3);
+    }
+
+    #[test]
+    fn test_constant_if_keeps_only_taken_branch() {
+        let asm = generate_source(
+            "if (true) {\n    print(11);\n} else {\n    print(22);\n};\n\
+             const DEBUG: int = 0;\nif (DEBUG == 1) {\n    print(33);\n};",
+        );
+        assert!(asm.contains("    mov rax, 11"));
+        // La branche `else` et la branche désactivée par la constante ne sont pas générées
+        assert!(!asm.contains("    mov rax, 22"));
+        assert!(!asm.contains("    mov rax, 33"));
+        assert!(!asm.contains("    je L"));
+
+        // Une condition non constante garde ses deux branches
+        let asm = generate_source("let x: int = 1;\nif (x == 1) {\n    print(11);\n} else {\n    print(22);\n};");
+        assert!(asm.contains("    mov rax, 11"));
+        assert!(asm.contains("    mov rax, 22"));
+    }
+
+    #[test]
+    fn test_parameter_shadowing_a_constant_is_not_folded() {
+        let asm = generate_source(
+            "const DEBUG: bool = true;\nconst K: int = -1;\n\
+             function f(DEBUG: bool): int {\n    if (DEBUG) {\n        return 1;\n    };\n    return 0;\n}\n\
+             function g(K: int): int {\n    let xs: int[3] = [4, 5, 6];\n    switch (K) {\n        case K {\n            return xs[K];\n        },\n    };\n    return 0;\n}\n\
+             print(f(false));\nprint(g(0));",
+        );
+        // Le paramètre est lu à l'exécution : les deux branches du `if` sont générées
+        assert!(asm.contains("    mov rax, [rbp + 16]\n    cmp rax, 0\n    je L"));
+        assert!(asm.contains("    mov rax, 0\n    mov rsp, rbp"));
+        // Ni le cas ni l'index ne prennent la valeur -1 de la constante masquée
+        assert!(!asm.contains("    mov rax, -1"));
+        assert!(!asm.contains("    mov rax, 2\n"));
+        assert!(asm.contains("    mov rax, [rbp + 16]\n    mov rbx, rax"));
+    }
+
+    #[test]
+    fn test_string_indexing_loads_a_byte() {
+        let asm = generate_source("let s: string = \"hello\";\nprint(s[1]);\nlet words: string[2];\nprint(words[0]);");
+        assert!(asm.contains(
+            "    mov rax, [rel g_s]\n    push rax\n    mov rax, 1\n    mov rcx, rax\n    pop rax\n    movzx rax, byte [rax + rcx]"
+        ));
+        // L'octet est affiché comme un entier, un élément de tableau de chaînes comme une chaîne
+        assert!(asm.contains("    movzx rax, byte [rax + rcx]\n    lea rdi, [rel format]"));
+        assert!(asm.contains("    mov rax, [rbx + rax * 8]\n    lea rdi, [rel format_str]"));
+        assert!(!asm.contains("call strlen"));
+    }
+
+    #[test]
+    fn test_condition_call_is_generated_once() {
+        let asm = generate_source(
+            "let n: int = 3;\nfunction decrement(): int {\n    n = n - 1;\n    return n;\n}\nwhile (decrement() > 0) {\n};",
+        );
+        // La condition est générée une seule fois, en tête de boucle : un appel par itération
+        assert_eq!(asm.matches("call f_decrement").count(), 1);
+        let start = asm.find("L0:").expect("Missing loop label");
+        assert!(asm[start..].contains("L0:\n    call f_decrement"));
+    }
+
+    #[test]
+    fn test_global_constants_are_inlined() {
+        let asm = generate_source(
+            "const PI: float = 3.5;\nconst N: int = 4 * 2;\nconst DEBUG: bool = N > 4;\nfunction area(r: float): float {\n    return PI * r;\n}\nfunction twice(N: int): int {\n    return N + N;\n}\nprint(N);\nprint(DEBUG);",
+        );
+        // Les constantes sont des immédiats : aucune lecture de leur emplacement global
+        assert!(!asm.contains("mov rax, [rel g_PI]"));
+        assert!(!asm.contains("mov rax, [rel g_N]"));
+        assert!(!asm.contains("mov rax, [rel g_DEBUG]"));
+        let area = &asm[asm.find("f_area:").unwrap()..asm.find("f_twice:").unwrap()];
+        assert!(area.contains("    mov rax, 0x400c000000000000 ; 3.5e0"));
+        assert!(asm.contains("    mov rax, 8\n    lea rdi, [rel format]"));
+        assert!(asm.contains("    mov rax, 1\n    cmp rax, 0\n    lea rsi, [rel text_false]"));
+        // Un paramètre qui masque une constante est lu sur la pile
+        let twice = &asm[asm.find("f_twice:").unwrap()..asm.find("f_main:").unwrap()];
+        assert!(twice.contains("    mov rax, [rbp + 16]"));
+        assert!(!twice.contains("    mov rax, 8"));
+    }
+
+    #[test]
+    fn test_print_uses_the_argument_type() {
+        let asm = generate_source(
+            "let i: int = 7;\nlet f: float = 2.5;\nlet s: string = \"hi\";\nlet b: bool = i > 3;\nprint(i);\nprint(f);\nprint(s);\nprint(b);",
+        );
+        // Entier : `%d`
+        assert!(asm.contains("    mov rax, [rel g_i]\n    lea rdi, [rel format]\n    mov rsi, rax"));
+        // Flottant : `%f`, valeur dans xmm0 et pile alignée
+        assert!(asm.contains(
+            "    mov rax, [rel g_f]\n    movq xmm0, rax\n    lea rdi, [rel format_float]\n    mov rbx, rsp\n    and rsp, -16\n    mov rax, 1"
+        ));
+        // Chaîne : `%s`
+        assert!(asm.contains("    mov rax, [rel g_s]\n    lea rdi, [rel format_str]\n    mov rsi, rax"));
+        // Booléen : `true` ou `false`
+        assert!(asm.contains(
+            "    mov rax, [rel g_b]\n    cmp rax, 0\n    lea rsi, [rel text_false]\n    lea rax, [rel text_true]\n    cmovne rsi, rax\n    lea rdi, [rel format_str]"
+        ));
+
+        // Le type d'une expression quelconque est aussi reconnu
+        let asm = generate_source("let f: float = 2.5;\nprint(-f);\nprint(1 < 2);");
+        assert!(asm.contains("    movq xmm0, rax"));
+        assert!(asm.contains("    lea rsi, [rel text_false]"));
+    }
+
+    #[test]
+    fn test_print_queries_the_type_context() {
+        use crate::parser::models::expression::Expression;
+        use crate::parser::models::statement::Statement;
+
+        let mut analyzer = SemanticAnalyzer::new("let a: float = 1.5;\nprint(a * a);".to_string());
+        analyzer.analyze();
+        assert!(analyzer.errors.is_empty());
+        let Statement::ExpressionStatement(Expression::FunctionCall(call)) = &analyzer.ast.statements[1] else {
+            panic!("Expected a call");
+        };
+        assert_eq!(analyzer.type_context.get(&call.arguments[0]), Some("float"));
+
+        // Le générateur consulte le contexte : le produit est affiché comme un flottant
+        let mut codegen = CodeGenerator::new();
+        codegen.type_context = std::mem::take(&mut analyzer.type_context);
+        codegen.generate(&analyzer.ast, &analyzer.symbol_table);
+        let asm = codegen.asm.join("\n");
+        assert!(asm.contains("    movq xmm0, rax\n    lea rdi, [rel format_float]"));
+    }
+
+    #[test]
+    fn test_print_uses_the_return_type_from_the_symbol_table() {
+        use crate::lex::models::name::Name;
+        use crate::parser::parser::Parser;
+        use crate::semantic::models::semantic::{Symbol, SymbolTable, SymbolType};
+
+        // Seule la table des symboles connaît `half` : l'AST ne contient que l'appel
+        let ast = Parser::new("print(half(3));".to_string()).parse_file();
+        let mut symbols = SymbolTable::new();
+        symbols
+            .define(
+                Name::intern("half"),
+                Symbol {
+                    name: Name::intern("half"),
+                    symbol_type: SymbolType::Function {
+                        parameters: vec!["int".to_string()],
+                        return_type: "float".to_string(),
+                    },
+                },
+            )
+            .unwrap();
+        let mut codegen = CodeGenerator::new();
+        codegen.generate(&ast, &symbols);
+        let asm = codegen.asm.join("\n");
+        assert!(asm.contains("    call f_half\n    add rsp, 8\n    movq xmm0, rax"));
+
+        // Sans le symbole, l'appel est affiché comme un entier
+        let mut codegen = CodeGenerator::new();
+        codegen.generate(&ast, &SymbolTable::new());
+        let asm = codegen.asm.join("\n");
+        assert!(asm.contains("    call f_half\n    add rsp, 8\n    lea rdi, [rel format]"));
+    }
+
+    #[test]
+    fn test_bool_variable_conditions() {
+        let asm = generate_source(
+            "let running: bool = true;\nwhile (running) {\n    running = false;\n};\nif (running) {\n    print(1);\n};\nfor (; running;) {\n    break;\n};",
+        );
+        // La variable est chargée puis comparée à 0, pour chacune des trois conditions
+        assert_eq!(
+            asm.matches("    mov rax, [rel g_running]\n    cmp rax, 0\n    je L").count(),
+            3
+        );
+    }
+
+    #[test]
+    fn test_power_operator() {
+        // Entiers : boucle de multiplications, 0 pour un exposant négatif
+        let asm = generate_source("let a: int = 2 ** 10;");
+        assert!(asm.contains(
+            "    mov rax, 2\n    push rax\n    mov rax, 10\n    mov rbx, rax\n    pop rax\n    mov rcx, rbx\n    mov rbx, rax\n    mov rax, 1"
+        ));
+        assert!(asm.contains("    je L2\n    imul rax, rbx\n    dec rcx\n    jmp L0\nL1:\n    xor rax, rax\nL2:"));
+        assert!(!asm.contains("call pow"));
+        assert!(!asm.contains("extern pow"));
+
+        // Flottants : pow de la libm, déclarée une seule fois avec les autres fonctions externes
+        let source = "let b: float = 2.0 ** 0.5;\nlet c: float = b ** 2.0;";
+        let asm = generate_source(source);
+        assert!(asm.contains(
+            "    movq xmm0, rax\n    movq xmm1, rbx\n    mov rbx, rsp\n    and rsp, -16\n    call pow wrt ..plt\n    mov rsp, rbx\n    movq rax, xmm0"
+        ));
+        assert!(asm.contains("extern strlen\nextern pow\n\n_start:"));
+        assert_eq!(asm.matches("extern pow").count(), 1);
+
+        let mut analyzer = SemanticAnalyzer::new(source.to_string());
+        analyzer.analyze();
+        let mut codegen = CodeGenerator::new();
+        codegen.target = Target::MacOs;
+        codegen.type_context = std::mem::take(&mut analyzer.type_context);
+        codegen.generate(&analyzer.ast, &analyzer.symbol_table);
+        let macos = codegen.asm.join("\n");
+        assert!(macos.contains("extern _strlen\nextern _pow\n\n_main:"));
+        assert!(macos.contains("    call _pow"));
+    }
+
+    #[test]
+    fn test_print_result_is_sign_extended() {
+        let asm = generate_source("let n: int = print(\"hi\");\nprint(1.5);");
+        // La valeur de printf, sur 32 bits, devient la valeur de `print` dans rax
+        assert_eq!(asm.matches("    call printf wrt ..plt\n    movsxd rax, eax").count(), 1);
+        assert!(asm.contains("    call printf wrt ..plt\n    mov rsp, rbx\n    movsxd rax, eax"));
+    }
+
+    #[test]
+    fn test_void_function_falls_through_to_the_epilogue() {
+        let asm = generate_source("function greet(): void {\n    print(\"hi\");\n}\ngreet();");
+        let body = &asm[asm.find("f_greet:").expect("Missing function label")..];
+        // Sans return, le corps se termine directement par l'épilogue
+        assert!(body.contains("    movsxd rax, eax\n    mov rsp, rbp\n    pop rbp\n    ret"));
+        assert!(!body.contains("jmp"));
+    }
+
+    #[test]
+    fn test_string_literal_argument() {
+        let asm = generate_source("function greet(name: string): void {\n    print(name);\n}\ngreet(\"World\");");
+        // L'appelant pousse l'adresse du littéral ; l'appelé la relit au-dessus de l'adresse
+        // de retour et l'affiche comme une chaîne
+        assert!(asm.contains("    lea rax, [rel str_0]\n    push rax\n    call f_greet\n    add rsp, 8"));
+        assert!(asm.contains("    mov rax, [rbp + 16]\n    lea rdi, [rel format_str]\n    mov rsi, rax"));
+        assert!(asm.contains("str_0: db \"World\", 0"));
+    }
+
+    #[test]
+    fn test_nested_for_restores_the_outer_counter() {
+        let asm = generate_source(
+            "for (let i: int = 0; i < 2; i = i + 1;) {\n    for (let j: int = 0; j < 2; j = j + 1;) {\n        print(j);\n    };\n    print(i);\n};",
+        );
+        // Après la boucle interne, `i` désigne toujours le compteur de la boucle externe
+        let after_inner = &asm[asm.rfind("mov [rbp - 16], rax").unwrap()..];
+        assert!(after_inner.contains("    mov rax, [rbp - 8]\n    lea rdi, [rel format]"));
+        assert!(!asm.contains("[rel g_i]"));
+        assert!(!asm.contains("[rel g_j]"));
+    }
+
+    #[test]
+    fn test_for_counter_lives_in_the_frame() {
+        let asm = generate_source(
+            "function f(n: int): int {\n    for (let i: int = 0; i < n; i = i + 1;) {\n        if (i == 1) {\n            return f(n - 1) + i;\n        };\n    };\n    return 0;\n}\nprint(f(3));",
+        );
+        // Chaque appel a son propre compteur : l'appel récursif ne peut pas l'écraser
+        let function = asm.split("f_f:").nth(1).unwrap().split("f_main:").next().unwrap();
+        assert!(function.contains("    sub rsp, 16"));
+        assert!(function.contains("    mov rax, 0\n    mov [rbp - 8], rax"));
+        assert!(function.contains("    call f_f\n    add rsp, 8\n    push rax\n    mov rax, [rbp - 8]"));
+        assert!(!asm.contains("resq"));
+    }
+
+    #[test]
+    fn test_block_local_shadows_the_loop_counter() {
+        let asm = generate_source(
+            "for (let i: int = 0; i < 2; i = i + 1;) {\n    {\n        let i: int = 7;\n        i = i + 1;\n        print(i);\n    };\n    switch (i) {\n        case 0 {\n            let i: int = 9;\n            print(i);\n        },\n    };\n    print(i);\n};",
+        );
+        // Dans le bloc, `i` est la variable locale, qui a son propre emplacement
+        assert!(asm.contains("    mov rax, 7\n    mov [rbp - 16], rax\n    mov rax, [rbp - 16]"));
+        assert!(asm.contains("    add rax, rbx\n    mov [rbp - 16], rax\n    mov rax, [rbp - 16]\n    lea rdi, [rel format]"));
+        // Dans le cas du switch aussi
+        assert!(asm.contains("    mov rax, 9\n    mov [rbp - 32], rax\n    mov rax, [rbp - 32]\n    lea rdi, [rel format]"));
+        // Le switch lui-même et la suite du corps retrouvent le compteur
+        assert!(asm.contains("    mov rax, [rbp - 8]\n    mov [rbp - 24], rax"));
+        assert!(asm.contains("    mov rax, [rbp - 8]\n    lea rdi, [rel format]"));
+        // L'initialisation et l'incrément ne modifient que le compteur
+        assert_eq!(asm.matches("    mov [rbp - 8], rax").count(), 2);
+    }
+
+    #[test]
+    fn test_array_literal_initializer() {
+        let asm = generate_source(
+            "let xs: int[3] = [1, 2, 3];\nfunction f(): int {\n    let ys: int[3] = [4, 5, 6];\n    return ys[2];\n}\nprint(xs[0] + f());",
+        );
+        // Chaque élément est rangé dans son emplacement, dans l'ordre
+        assert!(asm.contains("    g_xs resq 3"));
+        assert!(asm.contains(
+            "    mov rax, 1\n    lea rbx, [rel g_xs]\n    mov [rbx + 0], rax\n    mov rax, 2\n    lea rbx, [rel g_xs]\n    mov [rbx + 8], rax\n    mov rax, 3\n    lea rbx, [rel g_xs]\n    mov [rbx + 16], rax"
+        ));
+        assert!(asm.contains("    mov rax, 6\n    lea rbx, [rbp - 24]\n    mov [rbx + 16], rax"));
+    }
+
+    #[test]
+    fn test_string_literals_are_null_terminated() {
+        let asm = generate_source("let s: string = \"hello\";\nprint(s);\nprint(len(s));");
+        // printf (`%s`) et strlen lisent la chaîne jusqu'à son octet nul
+        assert!(asm.contains("    str_0: db \"hello\", 0\n"));
+        assert!(asm.contains("extern strlen"));
+        assert!(asm.contains("    mov rdi, rax\n    call strlen wrt ..plt"));
+    }
+
+    #[test]
+    fn test_negative_switch_cases_are_immediates() {
+        let asm = generate_source(
+            "let x: int = 0;\nswitch (x) {\n    case -1 {\n        print(1);\n    },\n    case -2 {\n        print(2);\n    },\n};",
+        );
+        // Chaque cas est comparé à sa valeur calculée, sans `neg` à l'exécution
+        assert!(asm.contains("    mov rax, -1\n    mov rbx, rax\n    mov rax, [rbp - 8]\n    cmp rax, rbx"));
+        assert!(asm.contains("    mov rax, -2\n    mov rbx, rax\n    mov rax, [rbp - 8]\n    cmp rax, rbx"));
+        assert!(!asm.contains("neg rax"));
+    }
+
+    #[test]
+    fn test_readint_calls_scanf() {
+        let asm = generate_source("let n: int = readint();\nprint(n);");
+        assert!(asm.contains("    format_read_int: db \"%ld\", 0"));
+        assert!(asm.contains("extern scanf"));
+        // L'entier est lu dans un emplacement de la pile alignée, puis chargé dans rax
+        assert!(asm.contains(
+            "    mov rbx, rsp\n    and rsp, -16\n    sub rsp, 16\n    mov qword [rsp], 0\n    lea rdi, [rel format_read_int]\n    mov rsi, rsp\n    xor rax, rax\n    call scanf wrt ..plt\n    mov rax, [rsp]\n    mov rsp, rbx\n    mov [rel g_n], rax"
+        ));
+    }
+
+    #[test]
+    fn test_condition_guard_is_short_circuited() {
+        let asm = generate_source(
+            "let a: int = 0;\nlet b: int = 10;\nif (a != 0 && b / a > 1) {\n    print(1);\n};",
+        );
+        // La division n'est atteinte qu'après le saut qui l'évite lorsque `a != 0` est faux
+        let jump = asm.find("    cmp rax, 0\n    je L").expect("Missing short-circuit jump");
+        let division = asm.find("idiv rbx").expect("Missing division");
+        assert!(jump < division);
+    }
+
+    #[test]
+    fn test_switch_range_case_compares_both_bounds() {
+        let asm = generate_source(
+            "const TOP: int = 9;\nlet x: int = 0;\nswitch (x) {\n    case 1..TOP {\n        print(1);\n    },\n};",
+        );
+        // La condition est comparée aux deux bornes incluses, calculées à la compilation
+        assert!(asm.contains(
+            "    mov rax, [rbp - 8]\n    mov rbx, 1\n    cmp rax, rbx\n    jl L1\n    mov rbx, 9\n    cmp rax, rbx\n    jg L1"
+        ));
+    }
+
+    #[test]
+    fn test_switch_condition_is_evaluated_once() {
+        let asm = generate_source(
+            "function next(): int {\n    return 3;\n}\nswitch (next()) {\n    case 1 {\n        print(1);\n    },\n    case 2..5 {\n        print(2);\n    },\n    case 7 {\n        print(7);\n    },\n};",
+        );
+        // La valeur de la condition est rangée dans le cadre de pile, puis relue par chaque cas
+        assert_eq!(asm.matches("    call f_next").count(), 1);
+        assert!(asm.contains("    call f_next\n    mov [rbp - 8], rax"));
+        assert_eq!(asm.matches("    mov rax, [rbp - 8]\n").count(), 3);
+    }
+
+    #[test]
+    fn test_todo_jumps_to_abort_routine() {
+        let asm = generate_source("function f(): int {\n    todo();\n}\nfunction g(): int {\n    todo();\n}\nprint(f() + g());");
+        assert_eq!(asm.matches("    jmp todo_error").count(), 2);
+        // La routine n'est émise qu'une fois, quel que soit le nombre d'appels
+        assert_eq!(asm.matches("todo_error:").count(), 1);
+        assert!(asm.contains("    todo_error_msg: db \"not yet implemented\", 10"));
+    }
+
+    #[test]
+    fn test_negative_index_is_resolved_at_compile_time() {
+        let asm = generate_source("let xs: int[4];\nxs[-2] = 7;\nprint(xs[-1]);");
+        // L'index compte à partir de la fin : -1 et -2 deviennent 3 et 2, sans calcul à l'exécution
+        assert!(asm.contains("    mov rax, 2\n    push rax\n    mov rax, 7\n    pop rcx"));
+        assert!(asm.contains("    mov rax, 3\n    lea rbx, [rel g_xs]\n    mov rax, [rbx + rax * 8]"));
+        assert!(!asm.contains("neg rax"));
+    }
+}
\ No newline at end of file
@@ -1,2 +1,4 @@
+#[allow(clippy::module_inception)]
 pub mod codegen;
+pub mod codegen_test;
 pub mod models;
\ No newline at end of file
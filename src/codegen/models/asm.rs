@@ -1,4 +1,5 @@
 #[derive(Debug)]
+#[allow(clippy::upper_case_acronyms)]
 pub struct ASM {
     pub section_data: Vec<String>,
     pub section_bss: Vec<String>,
@@ -6,6 +7,12 @@ pub struct ASM {
     pub sections_code: Vec<SectionCode>,
 }
 
+impl Default for ASM {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl ASM {
     pub fn new() -> Self {
         ASM {
@@ -1 +1,3 @@
-pub mod asm;
\ No newline at end of file
+pub mod asm;
+pub mod runtime;
+pub mod target;
\ No newline at end of file
@@ -0,0 +1,42 @@
+/// Représentation en mémoire d'une chaîne du langage.
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub enum StringRepresentation {
+    /// Pointeur vers des octets terminés par un 0, comme en C : la chaîne est passée telle
+    /// quelle à `printf` et mesurée par `strlen`. Sa longueur n'est pas stockée, la mesurer
+    /// parcourt donc toute la chaîne.
+    #[default]
+    NullTerminated,
+}
+
+/// Conventions d'exécution (ABI) que le code généré suppose pour les valeurs du langage.
+///
+/// Tout code qui crée, mesure ou parcourt une chaîne passe par ces conventions, afin qu'une
+/// autre représentation (une longueur stockée avant les octets, par exemple) puisse être
+/// adoptée à un seul endroit. Une chaîne est toujours manipulée par l'adresse de son premier
+/// octet, dans `rax` comme dans un emplacement de 8 octets.
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub struct Runtime {
+    pub strings: StringRepresentation,
+}
+
+impl Runtime {
+    /// Directive de la section `.data` qui définit un littéral de chaîne sous le label donné.
+    ///
+    /// # Arguments
+    ///
+    /// * `label` - Le label du littéral (`str_N`).
+    /// * `text` - Le contenu du littéral.
+    pub fn string_literal(&self, label: &str, text: &str) -> String {
+        match self.strings {
+            StringRepresentation::NullTerminated => format!("    {}: db \"{}\", 0", label, text),
+        }
+    }
+
+    /// Fonction de la libc qui calcule la longueur d'une chaîne à l'exécution, l'adresse de la
+    /// chaîne dans `rdi` et sa longueur renvoyée dans `rax`.
+    pub fn string_length_function(&self) -> &'static str {
+        match self.strings {
+            StringRepresentation::NullTerminated => "strlen",
+        }
+    }
+}
@@ -0,0 +1,79 @@
+/// Plateforme visée par le code généré.
+///
+/// La cible choisit le format objet passé à NASM, le point d'entrée, la manière d'appeler
+/// les fonctions de la libc et le numéro des appels système utilisés par les routines d'arrêt.
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub enum Target {
+    /// Linux x86_64, ELF 64 bits, édition de liens dynamique avec la libc.
+    #[default]
+    LinuxElf64,
+    /// macOS x86_64, Mach-O 64 bits : les symboles C sont préfixés par `_`.
+    MacOs,
+}
+
+impl Target {
+    /// Noms acceptés par `--target`.
+    pub const NAMES: &'static [&'static str] = &["linux-elf64", "macos"];
+
+    /// Retourne la cible correspondant au nom donné sur la ligne de commande.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Le nom de la cible (`linux-elf64` ou `macos`).
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "linux-elf64" => Some(Target::LinuxElf64),
+            "macos" => Some(Target::MacOs),
+            _ => None,
+        }
+    }
+
+    /// Format objet passé à `nasm -f`.
+    pub fn nasm_format(&self) -> &'static str {
+        match self {
+            Target::LinuxElf64 => "elf64",
+            Target::MacOs => "macho64",
+        }
+    }
+
+    /// Symbole du point d'entrée du programme.
+    pub fn entry_symbol(&self) -> &'static str {
+        match self {
+            Target::LinuxElf64 => "_start",
+            Target::MacOs => "_main",
+        }
+    }
+
+    /// Nom sous lequel une fonction C (`printf`, `exit`, ...) est vue par l'assembleur.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Le nom de la fonction dans la libc.
+    pub fn c_symbol(&self, name: &str) -> String {
+        match self {
+            Target::LinuxElf64 => name.to_string(),
+            Target::MacOs => format!("_{}", name),
+        }
+    }
+
+    /// Instruction d'appel d'une fonction de la libc : via la PLT sous Linux (code PIE),
+    /// directement sous macOS où l'éditeur de liens résout les symboles externes.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Le nom de la fonction dans la libc.
+    pub fn c_call(&self, name: &str) -> String {
+        match self {
+            Target::LinuxElf64 => format!("    call {} wrt ..plt", name),
+            Target::MacOs => format!("    call {}", self.c_symbol(name)),
+        }
+    }
+
+    /// Numéro de l'appel système `write`.
+    pub fn write_syscall(&self) -> &'static str {
+        match self {
+            Target::LinuxElf64 => "1",
+            Target::MacOs => "0x2000004",
+        }
+    }
+}
@@ -0,0 +1,264 @@
+use std::env;
+use std::fs::{self, File};
+use std::io::{self, ErrorKind, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{self, Command, ExitStatus};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::codegen::codegen::CodeGenerator;
+use crate::codegen::models::target::Target;
+use crate::driver::models::compilation::CompilationResult;
+use crate::driver::models::options::{Options, STDIN_PATH};
+use crate::driver::models::timings::PassTimings;
+use crate::lex::lexer::Lexer;
+use crate::parser::parser::Parser;
+use crate::parser::serialize::ast_to_json;
+use crate::semantic::analyzer::SemanticAnalyzer;
+use crate::semantic::models::diagnostic::{render_diagnostic, Diagnostic};
+use crate::semantic::simplify::simplify_ast;
+
+/// 🔢 Distinguishes several compilations of the same input within one process.
+static TEMP_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// 🗂️ Builds unique paths for the intermediate `.asm` and `.o` files in the system temp
+/// directory. The name combines the input file stem, the process id and a counter, so that
+/// concurrent compilations (even of the same source) never clobber each other's files.
+pub fn temp_paths(input_path: &str) -> (PathBuf, PathBuf) {
+    let stem = Path::new(input_path)
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("output");
+    let unique = format!(
+        "{}-{}-{}",
+        stem,
+        process::id(),
+        TEMP_COUNTER.fetch_add(1, Ordering::Relaxed)
+    );
+    let dir = env::temp_dir();
+    (
+        dir.join(format!("{}.asm", unique)),
+        dir.join(format!("{}.o", unique)),
+    )
+}
+
+/// 🏷️ Path of the outputs without extension: the input path minus one trailing `.own`, or
+/// `a` when the source is read from stdin. An input without `.own` is kept as is.
+pub fn output_base(input_path: &str) -> &str {
+    if input_path == STDIN_PATH {
+        "a"
+    } else {
+        input_path.strip_suffix(".own").unwrap_or(input_path)
+    }
+}
+
+/// 🏷️ Path of an output written next to the source: `main.own` gives `main.<extension>`.
+/// Only one `.own` is replaced (`main.own.own` gives `main.own.<extension>`), and an input
+/// without it, such as `main`, gets the extension appended.
+pub fn output_path(input_path: &str, extension: &str) -> String {
+    format!("{}.{}", output_base(input_path), extension)
+}
+
+/// 📦 Path of the final executable: the `-o` path if one was given, `<base>.owne` otherwise.
+pub fn executable_path(options: &Options) -> String {
+    match &options.output_path {
+        Some(path) => path.clone(),
+        None => output_path(&options.input_path, "owne"),
+    }
+}
+
+/// 📖 Reads the source from the input file, or from stdin for `-`.
+fn read_source(input_path: &str) -> io::Result<String> {
+    if input_path == STDIN_PATH {
+        let mut source = String::new();
+        io::stdin().read_to_string(&mut source)?;
+        Ok(source)
+    } else {
+        fs::read_to_string(input_path)
+    }
+}
+
+/// 🛠️ Runs an external tool (`nasm`, `ld`, `cc`) and waits for it. A tool missing from the
+/// `PATH` is reported with a readable message instead of a panic.
+pub fn run_tool(command: &mut Command, tool: &str) -> Result<ExitStatus, String> {
+    command.status().map_err(|err| match err.kind() {
+        ErrorKind::NotFound => format!(
+            "error: '{}' not found in PATH; install it or use --emit-asm",
+            tool
+        ),
+        _ => format!("error: failed to run '{}': {}", tool, err),
+    })
+}
+
+/// 🚀 Runs the whole pipeline (analysis, code generation, assembly and linking) and
+/// returns the process exit code. With `--time-passes`, the duration of each phase is
+/// printed on stderr once the pipeline stops.
+pub fn run(options: &Options) -> i32 {
+    let mut timings = PassTimings::new(options.time_passes);
+    let status = run_passes(options, &mut timings);
+    timings.print();
+    status
+}
+
+/// 🧪 Compiles `source` in memory: lexing, parsing and semantic analysis, then, for a valid
+/// program, negation simplification and code generation. Nothing is printed or written to
+/// disk; each phase is recorded in `timings`.
+pub fn compile(source: &str, options: &Options, timings: &mut PassTimings) -> CompilationResult {
+    // Lexing and parsing
+    let tokens = timings.time("lex", || Lexer::new(source.to_string()).tokenize());
+    let mut parser = Parser::with_defines(tokens, &options.defines);
+    let ast = timings.time("parse", || parser.parse_file());
+
+    // The first syntax error is shown with its source line; the next ones often follow from it
+    let syntax_error_location = parser.error_locations.first().copied();
+
+    // Semantic Analysis
+    let mut analyzer = SemanticAnalyzer::from_ast(ast, parser.errors);
+    analyzer.warn_missing_default = options.warn_missing_default;
+    let diagnostics = timings.time("semantic", || analyzer.analyze());
+    let mut result = CompilationResult {
+        ast: std::mem::take(&mut analyzer.ast),
+        asm: None,
+        diagnostics,
+        syntax_error_location,
+    };
+    // The AST of a valid program is emitted as analyzed, before any rewriting
+    if result.has_errors() || options.emit_ast_json {
+        return result;
+    }
+    // The resolved types refer to the expressions of this AST, which is moved but never cloned
+    let mut type_context = analyzer.type_context;
+    let symbols = analyzer.symbol_table;
+
+    // Negations are simplified in place, keeping the resolved types up to date
+    timings.time("simplify", || simplify_ast(&mut result.ast, &mut type_context));
+
+    // Code Generation
+    let mut codegen = CodeGenerator::new();
+    codegen.bounds_check = options.bounds_check;
+    codegen.checked_arith = options.checked_arith;
+    codegen.target = options.target;
+    codegen.type_context = type_context;
+    result.asm = Some(timings.time("codegen", || {
+        codegen.generate(&result.ast, &symbols);
+        codegen.asm.join("\n")
+    }));
+    result
+}
+
+/// 📝 Compiles `source` with the default options and returns its assembly, or every
+/// diagnostic when it has errors. Nothing touches the filesystem, which suits tests and tools.
+pub fn compile_to_asm(source: &str) -> Result<String, Vec<Diagnostic>> {
+    compile(source, &Options::default(), &mut PassTimings::new(false)).into_asm()
+}
+
+/// 🧩 The pipeline itself, each phase being recorded in `timings`.
+fn run_passes(options: &Options, timings: &mut PassTimings) -> i32 {
+    let input_path = &options.input_path;
+    let source = read_source(input_path).expect("Failed to read source file");
+    let result = compile(&source, options, timings);
+
+    for warning in result.diagnostics.iter().filter(|d| !d.is_error()) {
+        eprintln!("{}", warning);
+    }
+    let errors: Vec<&String> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.is_error())
+        .map(|d| &d.message)
+        .collect();
+    if !errors.is_empty() {
+        if let Some((line, column)) = result.syntax_error_location {
+            eprint!("{}", render_diagnostic(&source, line, column, errors[0]));
+        }
+        println!("Semantic analysis failed: {:?}", errors);
+        return 1;
+    }
+
+    // Stop after analysis: the AST of the valid program goes next to the source file
+    if options.emit_ast_json {
+        let json_file = output_path(input_path, "ast.json");
+        fs::write(&json_file, ast_to_json(&result.ast)).expect("Failed to write AST");
+        println!("AST written to {}", json_file);
+        return 0;
+    }
+    println!("{:?}", result.ast);
+    let asm_code = result.into_asm().expect("A valid program always has its assembly");
+
+    // Stop after code generation: the assembly goes next to the source file
+    if options.emit_asm {
+        let asm_file = output_path(input_path, "asm");
+        fs::write(&asm_file, asm_code).expect("Failed to write assembly code");
+        println!("Assembly code written to {}", asm_file);
+        return 0;
+    }
+
+    // Write assembly code to a unique temporary file
+    let (asm_file, object_file) = temp_paths(output_base(input_path));
+    let mut file = File::create(&asm_file).expect("Failed to create assembly file");
+    file.write_all(asm_code.as_bytes()).expect("Failed to write assembly code");
+    println!("Assembly code written to {}", asm_file.display());
+
+    // Assemble with nasm (format elf64)
+    let nasm_status = timings.time("assemble", || {
+        run_tool(
+            Command::new("nasm")
+                .arg("-f")
+                .arg(options.target.nasm_format())
+                .arg(&asm_file)
+                .arg("-o")
+                .arg(&object_file),
+            "nasm",
+        )
+    });
+    let nasm_status = match nasm_status {
+        Ok(status) => status,
+        Err(message) => {
+            eprintln!("{}", message);
+            return 1;
+        }
+    };
+    if !nasm_status.success() {
+        eprintln!("nasm failed to assemble the code.");
+        return 1;
+    }
+    println!("Object file generated: {}", object_file.display());
+
+    // Link to produce a position-independent executable, linking with libc
+    let executable_file = executable_path(options);
+    let ld_status = timings.time("link", || match options.target {
+        Target::LinuxElf64 => run_tool(
+            Command::new("ld")
+                .arg(&object_file)
+                .arg("-pie")
+                .args(["-o", &executable_file, "-lc", "-lm", "--dynamic-linker", "/lib64/ld-linux-x86-64.so.2"]),
+            "ld",
+        ),
+        // The system C driver links libSystem and the startup code calling `_main`
+        Target::MacOs => run_tool(
+            Command::new("cc")
+                .args(["-arch", "x86_64"])
+                .arg(&object_file)
+                .args(["-o", &executable_file]),
+            "cc",
+        ),
+    });
+    let ld_status = match ld_status {
+        Ok(status) => status,
+        Err(message) => {
+            eprintln!("{}", message);
+            return 1;
+        }
+    };
+    if !ld_status.success() {
+        eprintln!("Linker failed to produce the executable.");
+        return 1;
+    }
+    println!("Executable generated: {}", executable_file);
+
+    // Clean up intermediate files unless asked to keep them
+    if !options.keep_temps {
+        fs::remove_file(&asm_file).expect("Failed to remove asm file");
+        fs::remove_file(&object_file).expect("Failed to remove object file");
+    }
+    0
+}
@@ -0,0 +1,256 @@
+#[cfg(test)]
+mod driver_tests {
+    use crate::codegen::models::target::Target;
+    use crate::driver::driver::{
+        compile, compile_to_asm, executable_path, output_base, output_path, run_tool, temp_paths,
+    };
+    use crate::driver::models::options::{help_text, version_text, Invocation, Options, FLAGS};
+    use crate::driver::models::timings::PassTimings;
+
+    fn args(values: &[&str]) -> Vec<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    // Helper function unwrapping the options of a compile invocation
+    fn compile_options(values: &[&str]) -> Options {
+        match Invocation::from_args(&args(values)) {
+            Ok(Invocation::Compile(options)) => options,
+            other => panic!("Expected a compile invocation, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_keep_temps_flag() {
+        let options = compile_options(&["--keep-temps", "prog.own"]);
+        assert_eq!(options.input_path, "prog.own");
+        assert!(options.keep_temps);
+
+        let options = compile_options(&["prog.own"]);
+        assert!(!options.keep_temps);
+
+        assert!(Invocation::from_args(&args(&["--keep-temps"])).is_err());
+
+        let options = compile_options(&["--bounds-check", "prog.own"]);
+        assert!(options.bounds_check);
+        assert!(!options.keep_temps);
+
+        let options = compile_options(&["prog.own", "--checked-arith"]);
+        assert!(options.checked_arith);
+        assert!(!options.bounds_check);
+
+        let options = compile_options(&["--emit-asm", "prog.own"]);
+        assert!(options.emit_asm);
+        assert!(!compile_options(&["prog.own"]).emit_asm);
+
+        let options = compile_options(&["--emit-ast-json", "prog.own"]);
+        assert!(options.emit_ast_json);
+        assert!(!options.emit_asm);
+    }
+
+    #[test]
+    fn test_target_flag() {
+        assert_eq!(compile_options(&["prog.own"]).target, Target::LinuxElf64);
+        assert_eq!(compile_options(&["--target", "macos", "prog.own"]).target, Target::MacOs);
+        assert_eq!(
+            compile_options(&["prog.own", "--target=linux-elf64"]).target,
+            Target::LinuxElf64
+        );
+        assert_eq!(
+            Invocation::from_args(&args(&["prog.own", "--target"])),
+            Err("Missing value for '--target'.".to_string())
+        );
+        assert_eq!(
+            Invocation::from_args(&args(&["--target", "windows", "prog.own"])),
+            Err("Unknown target 'windows', expected one of: linux-elf64, macos.".to_string())
+        );
+    }
+
+    #[test]
+    fn test_help_and_version_flags() {
+        assert_eq!(Invocation::from_args(&args(&["--help"])), Ok(Invocation::Help));
+        assert_eq!(Invocation::from_args(&args(&["prog.own", "-h"])), Ok(Invocation::Help));
+        assert_eq!(Invocation::from_args(&args(&["--version"])), Ok(Invocation::Version));
+
+        let help = help_text("own-lang");
+        assert!(help.starts_with("Usage: own-lang [options] <path_to_file>"));
+        for (flag, description) in FLAGS {
+            assert!(help.contains(flag), "--help does not list {}", flag);
+            assert!(help.contains(description));
+        }
+        assert_eq!(version_text(), format!("own-lang {}", env!("CARGO_PKG_VERSION")));
+    }
+
+    #[test]
+    fn test_argument_errors() {
+        assert_eq!(
+            Invocation::from_args(&args(&["--keep-temp", "prog.own"])),
+            Err("Unknown flag '--keep-temp'.".to_string())
+        );
+        assert_eq!(
+            Invocation::from_args(&args(&[])),
+            Err("No input file given.".to_string())
+        );
+        assert_eq!(
+            Invocation::from_args(&args(&["a.own", "b.own"])),
+            Err("Only one input file is supported, got 'a.own' and 'b.own'.".to_string())
+        );
+    }
+
+    #[test]
+    fn test_temp_paths_do_not_collide() {
+        let (first_asm, first_obj) = temp_paths("own_files/if/if_good.own");
+        let (second_asm, second_obj) = temp_paths("own_files/while/while_good.own");
+        let (again_asm, again_obj) = temp_paths("own_files/if/if_good.own");
+        assert_ne!(first_asm, second_asm);
+        assert_ne!(first_obj, second_obj);
+        // Compiling the same input twice must not reuse the same files either
+        assert_ne!(first_asm, again_asm);
+        assert_ne!(first_obj, again_obj);
+        assert!(first_asm.starts_with(std::env::temp_dir()));
+        assert!(first_asm
+            .file_name()
+            .unwrap()
+            .to_str()
+            .unwrap()
+            .starts_with("if_good-"));
+    }
+
+    #[test]
+    fn test_missing_tool_is_reported() {
+        let result = run_tool(&mut std::process::Command::new("own-lang-missing-nasm"), "nasm");
+        assert_eq!(
+            result,
+            Err("error: 'nasm' not found in PATH; install it or use --emit-asm".to_string())
+        );
+    }
+    #[test]
+    fn test_stdin_input() {
+        assert_eq!(compile_options(&["-"]).input_path, "-");
+        assert_eq!(compile_options(&["--keep-temps", "-"]).input_path, "-");
+        assert_eq!(output_base("-"), "a");
+        assert_eq!(output_base("own_files/example.own"), "own_files/example");
+    }
+    #[test]
+    fn test_pass_timings() {
+        let mut timings = PassTimings::new(true);
+        assert_eq!(timings.time("lex", || 1 + 1), 2);
+        timings.time("parse", || ());
+        let report = timings.report();
+        assert_eq!(report.len(), 2);
+        assert!(report[0].starts_with("time: lex "));
+        assert!(report[1].starts_with("time: parse "));
+        assert!(report.iter().all(|line| line.ends_with(" ms")));
+
+        let mut disabled = PassTimings::new(false);
+        assert_eq!(disabled.time("lex", || 3), 3);
+        assert!(disabled.report().is_empty());
+        assert!(compile_options(&["--time-passes", "prog.own"]).time_passes);
+    }
+    #[test]
+    fn test_warn_missing_default_flag() {
+        assert!(compile_options(&["--warn-missing-default", "prog.own"]).warn_missing_default);
+        assert!(!compile_options(&["prog.own"]).warn_missing_default);
+    }
+    #[test]
+    fn test_executable_path() {
+        assert_eq!(executable_path(&compile_options(&["prog.own"])), "prog.owne");
+        // Only one trailing `.own` is stripped, and an input without it keeps its name
+        assert_eq!(executable_path(&compile_options(&["prog"])), "prog.owne");
+        assert_eq!(executable_path(&compile_options(&["a.own.own"])), "a.own.owne");
+        assert_eq!(executable_path(&compile_options(&["-"])), "a.owne");
+
+        let options = compile_options(&["-o", "bin/prog", "prog.own"]);
+        assert_eq!(options.output_path, Some("bin/prog".to_string()));
+        assert_eq!(executable_path(&options), "bin/prog");
+        assert_eq!(executable_path(&compile_options(&["a.own.own", "-o", "out"])), "out");
+        assert_eq!(
+            Invocation::from_args(&args(&["prog.own", "-o"])),
+            Err("Missing value for '-o'.".to_string())
+        );
+    }
+    #[test]
+    fn test_output_path() {
+        assert_eq!(output_path("main.own", "asm"), "main.asm");
+        assert_eq!(output_path("main.own.own", "asm"), "main.own.asm");
+        assert_eq!(output_path("dir.own/main", "ast.json"), "dir.own/main.ast.json");
+        assert_eq!(output_path("main", "owne"), "main.owne");
+        assert_eq!(output_path("-", "asm"), "a.asm");
+        assert_eq!(output_base("main.own.own"), "main.own");
+    }
+    #[test]
+    fn test_define_flag() {
+        assert!(compile_options(&["prog.own"]).defines.is_empty());
+        assert_eq!(
+            compile_options(&["--define", "DEBUG", "prog.own", "--define=FAST"]).defines,
+            vec!["DEBUG".to_string(), "FAST".to_string()]
+        );
+        assert_eq!(
+            Invocation::from_args(&args(&["prog.own", "--define"])),
+            Err("Missing value for '--define'.".to_string())
+        );
+    }
+
+    #[test]
+    fn test_compile_returns_all_artifacts() {
+        let options = compile_options(&["prog.own"]);
+        let source = "let x: int = 1;\n{\n    let x: int = 2;\n    print(x);\n};";
+        let result = compile(source, &options, &mut PassTimings::new(false));
+        // One warning does not stop the compilation
+        assert!(!result.has_errors());
+        assert_eq!(result.diagnostics.len(), 1);
+        assert_eq!(
+            result.diagnostics[0].to_string(),
+            "warning: Variable 'x' shadows a variable from an outer scope."
+        );
+        assert_eq!(result.ast.statements.len(), 2);
+        let asm = result.asm.expect("Missing assembly");
+        assert!(asm.contains("f_main:"));
+        assert_eq!(result.syntax_error_location, None);
+
+        // An error leaves the assembly out, but keeps the AST and the error's position
+        let result = compile("let x: int = 1\nprint(x);", &options, &mut PassTimings::new(false));
+        assert!(result.has_errors());
+        assert!(result.asm.is_none());
+        assert_eq!(result.syntax_error_location, Some((2, 1)));
+
+        // `--emit-ast-json` only needs the analyzed AST
+        let options = compile_options(&["--emit-ast-json", "prog.own"]);
+        let result = compile(source, &options, &mut PassTimings::new(false));
+        assert!(result.asm.is_none());
+        assert_eq!(result.ast.statements.len(), 2);
+    }
+
+    #[test]
+    fn test_simplified_negations_keep_their_types() {
+        let options = compile_options(&["--time-passes", "prog.own"]);
+        let source = "let a: float = 1.5;\nlet b: float = 2.0;\nprint(!!(a < b));";
+        let mut timings = PassTimings::new(true);
+        let asm = compile(source, &options, &mut timings)
+            .into_asm()
+            .expect("Expected the assembly of a valid program");
+        // `!!(a < b)` becomes `a < b`, still compared as floats and printed as a bool
+        assert!(asm.contains("    ucomisd xmm1, xmm0\n    seta al"));
+        assert!(asm.contains("text_true"));
+        assert!(!asm.contains("    cmp rax, 0\n    sete al"));
+        assert!(timings.report().iter().any(|line| line.starts_with("time: simplify")));
+    }
+
+    #[test]
+    fn test_compile_to_asm() {
+        let asm = compile_to_asm("print(42);").expect("Expected the assembly of a valid program");
+        assert!(asm.contains("section .text"));
+        assert!(asm.contains("_start"));
+
+        // A program with errors gives back its diagnostics instead, warnings included
+        let diagnostics = compile_to_asm("let x: int = \"a\";\n{\n    let x: int = 2;\n};")
+            .expect_err("Expected the diagnostics of an invalid program");
+        let messages: Vec<String> = diagnostics.iter().map(|d| d.to_string()).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "error: Type mismatch in variable declaration 'x': expected 'int', found 'string'.".to_string(),
+                "warning: Variable 'x' shadows a variable from an outer scope.".to_string(),
+            ]
+        );
+    }
+}
\ No newline at end of file
@@ -0,0 +1,4 @@
+#[allow(clippy::module_inception)]
+pub mod driver;
+pub mod driver_test;
+pub mod models;
\ No newline at end of file
@@ -0,0 +1,33 @@
+use crate::parser::models::ast::AST;
+use crate::semantic::models::diagnostic::Diagnostic;
+
+/// 📦 Everything produced by compiling one source in memory, for callers that embed the
+/// compiler instead of running the whole command-line pipeline.
+#[derive(Debug)]
+pub struct CompilationResult {
+    /// The parsed program. Once code has been generated, its negations are simplified.
+    pub ast: AST,
+    /// The NASM assembly, or `None` when an error stopped the compilation or when only the
+    /// AST was requested (`--emit-ast-json`).
+    pub asm: Option<String>,
+    /// Syntax and semantic errors first, then warnings.
+    pub diagnostics: Vec<Diagnostic>,
+    /// Line and column of the first syntax error, to show it with its source line.
+    pub syntax_error_location: Option<(usize, usize)>,
+}
+
+impl CompilationResult {
+    /// ❌ Tells whether any diagnostic is an error.
+    pub fn has_errors(&self) -> bool {
+        self.diagnostics.iter().any(|diagnostic| diagnostic.is_error())
+    }
+
+    /// 🧾 Keeps only the assembly of a successful compilation, or every diagnostic when an
+    /// error stopped it. Warnings of a successful compilation are dropped.
+    pub fn into_asm(self) -> Result<String, Vec<Diagnostic>> {
+        match self.asm {
+            Some(asm) if !self.has_errors() => Ok(asm),
+            _ => Err(self.diagnostics),
+        }
+    }
+}
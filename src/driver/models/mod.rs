@@ -0,0 +1,3 @@
+pub mod compilation;
+pub mod options;
+pub mod timings;
\ No newline at end of file
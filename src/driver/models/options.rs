@@ -0,0 +1,160 @@
+use crate::codegen::models::target::Target;
+
+/// 📥 Input path meaning "read the source from stdin".
+pub const STDIN_PATH: &str = "-";
+
+/// ⚙️ Command-line options controlling a single compiler invocation.
+/// The default options are those of a bare `own-lang <file>`, with an empty input path.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct Options {
+    pub input_path: String,
+    /// Path of the executable given with `-o`; `None` derives it from the input path.
+    pub output_path: Option<String>,
+    pub keep_temps: bool,
+    pub bounds_check: bool,
+    pub checked_arith: bool,
+    pub emit_asm: bool,
+    pub emit_ast_json: bool,
+    pub time_passes: bool,
+    pub warn_missing_default: bool,
+    pub target: Target,
+    /// Flags given with `--define`, which enable the matching `#if` blocks.
+    pub defines: Vec<String>,
+}
+
+/// 🧭 What the compiler was asked to do on the command line.
+#[derive(Debug, PartialEq, Clone)]
+pub enum Invocation {
+    Compile(Options),
+    Help,
+    Version,
+}
+
+/// 📋 Every flag understood by the compiler, with the description shown by `--help`.
+pub const FLAGS: &[(&str, &str)] = &[
+    ("-o <path>", "Write the executable to <path> instead of next to the source file"),
+    ("--keep-temps", "Keep the intermediate .asm and .o files in the temp directory"),
+    ("--bounds-check", "Abort with an error on out-of-range array indexes at runtime"),
+    ("--checked-arith", "Abort with an error on integer overflow in + - * and negation"),
+    ("--emit-asm", "Write the assembly next to the source file and stop, without nasm or ld"),
+    ("--emit-ast-json", "Write the AST as JSON next to the source file and stop"),
+    ("--time-passes", "Print how long each compilation phase took"),
+    ("--warn-missing-default", "Warn about switch statements without a default case"),
+    ("--define <name>", "Enable the #if <name> blocks of the source; may be repeated"),
+    ("--target <name>", "Target platform: linux-elf64 (default) or macos"),
+    ("--help", "Print this help message and exit"),
+    ("--version", "Print the compiler version and exit"),
+];
+
+impl Invocation {
+    /// 🔧 Parses the raw command-line arguments (program name excluded).
+    /// Unknown flags, a missing input path or several input paths are reported as errors.
+    pub fn from_args(args: &[String]) -> Result<Self, String> {
+        let mut input_path: Option<String> = None;
+        let mut output_path: Option<String> = None;
+        let mut keep_temps = false;
+        let mut bounds_check = false;
+        let mut checked_arith = false;
+        let mut emit_asm = false;
+        let mut emit_ast_json = false;
+        let mut time_passes = false;
+        let mut warn_missing_default = false;
+        let mut target = Target::default();
+        let mut defines = Vec::new();
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--help" | "-h" => return Ok(Invocation::Help),
+                "--version" | "-V" => return Ok(Invocation::Version),
+                "--keep-temps" => keep_temps = true,
+                "--bounds-check" => bounds_check = true,
+                "--checked-arith" => checked_arith = true,
+                "--emit-asm" => emit_asm = true,
+                "--emit-ast-json" => emit_ast_json = true,
+                "--time-passes" => time_passes = true,
+                "--warn-missing-default" => warn_missing_default = true,
+                "--target" => {
+                    let name = args
+                        .next()
+                        .ok_or_else(|| "Missing value for '--target'.".to_string())?;
+                    target = parse_target(name)?;
+                }
+                flag if flag.starts_with("--target=") => {
+                    target = parse_target(&flag["--target=".len()..])?;
+                }
+                "--define" => {
+                    let name = args
+                        .next()
+                        .ok_or_else(|| "Missing value for '--define'.".to_string())?;
+                    defines.push(name.to_string());
+                }
+                flag if flag.starts_with("--define=") => {
+                    defines.push(flag["--define=".len()..].to_string());
+                }
+                "-o" => {
+                    let path = args
+                        .next()
+                        .ok_or_else(|| "Missing value for '-o'.".to_string())?;
+                    output_path = Some(path.to_string());
+                }
+                flag if flag.starts_with('-') && flag != STDIN_PATH => {
+                    return Err(format!("Unknown flag '{}'.", flag));
+                }
+                path => {
+                    if let Some(first) = &input_path {
+                        return Err(format!(
+                            "Only one input file is supported, got '{}' and '{}'.",
+                            first, path
+                        ));
+                    }
+                    input_path = Some(path.to_string());
+                }
+            }
+        }
+        let input_path = input_path.ok_or_else(|| "No input file given.".to_string())?;
+        Ok(Invocation::Compile(Options {
+            input_path,
+            output_path,
+            keep_temps,
+            bounds_check,
+            checked_arith,
+            emit_asm,
+            emit_ast_json,
+            time_passes,
+            warn_missing_default,
+            target,
+            defines,
+        }))
+    }
+}
+
+/// 🎯 Resolves the value given to `--target`.
+fn parse_target(name: &str) -> Result<Target, String> {
+    Target::from_name(name).ok_or_else(|| {
+        format!(
+            "Unknown target '{}', expected one of: {}.",
+            name,
+            Target::NAMES.join(", ")
+        )
+    })
+}
+
+/// 📝 Short usage line, printed on its own after an argument error.
+pub fn usage(program: &str) -> String {
+    format!("Usage: {} [options] <path_to_file>", program)
+}
+
+/// 📖 Full `--help` text listing every known flag.
+pub fn help_text(program: &str) -> String {
+    let mut text = format!("{}\n\nOptions:\n", usage(program));
+    let width = FLAGS.iter().map(|(flag, _)| flag.len()).max().unwrap_or(0);
+    for (flag, description) in FLAGS {
+        text.push_str(&format!("  {:width$}  {}\n", flag, description, width = width));
+    }
+    text
+}
+
+/// 🏷️ Version line printed by `--version`.
+pub fn version_text() -> String {
+    format!("own-lang {}", env!("CARGO_PKG_VERSION"))
+}
@@ -0,0 +1,47 @@
+use std::time::{Duration, Instant};
+
+/// ⏱️ Records how long each compiler phase takes, for `--time-passes`.
+/// When disabled, phases still run but nothing is measured or printed.
+#[derive(Debug, Default)]
+pub struct PassTimings {
+    enabled: bool,
+    passes: Vec<(&'static str, Duration)>,
+}
+
+impl PassTimings {
+    /// 🔧 Creates a recorder, measuring only when `enabled` is set.
+    pub fn new(enabled: bool) -> Self {
+        PassTimings {
+            enabled,
+            passes: Vec::new(),
+        }
+    }
+
+    /// ▶️ Runs `pass` and records its duration under `name`.
+    pub fn time<T>(&mut self, name: &'static str, pass: impl FnOnce() -> T) -> T {
+        if !self.enabled {
+            return pass();
+        }
+        let start = Instant::now();
+        let result = pass();
+        self.passes.push((name, start.elapsed()));
+        result
+    }
+
+    /// 📋 One line per recorded phase, in execution order, e.g. `time: parse      0.042 ms`.
+    pub fn report(&self) -> Vec<String> {
+        self.passes
+            .iter()
+            .map(|(name, duration)| {
+                format!("time: {:<9} {:>9.3} ms", name, duration.as_secs_f64() * 1000.0)
+            })
+            .collect()
+    }
+
+    /// 🖨️ Prints the report on stderr, so it never mixes with the compiler's regular output.
+    pub fn print(&self) {
+        for line in self.report() {
+            eprintln!("{}", line);
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,81 @@
+use crate::lex::models::token::Token;
+use crate::lex::models::token_type::TokenType;
+
+/// 🚨 A malformed directive: the message and the line and column of its `#if`.
+pub type DirectiveError = (String, (usize, usize));
+
+/// 🚩 One `#if NAME {` block still open while filtering.
+struct OpenConditional {
+    name: String,
+    /// Line and column of the `#if`, to report a block left open.
+    location: (usize, usize),
+    /// Brace depth just outside the block: its closing `}` brings the depth back here.
+    depth: usize,
+    included: bool,
+}
+
+/// 🔀 Resolves `#if NAME { ... }` blocks before parsing.
+///
+/// The tokens of a block are kept, without its `#if NAME {` and `}`, when `NAME` is one of
+/// `defines`, and dropped otherwise, so the statements of an included block belong to the
+/// enclosing scope. Blocks may be nested. Malformed directives are returned as error messages
+/// (without final punctuation, like the parser's) with the line and column of their `#if`;
+/// the other tokens are still filtered.
+pub fn filter_conditionals(
+    tokens: Vec<Token>,
+    defines: &[String],
+) -> (Vec<Token>, Vec<DirectiveError>) {
+    let mut filtered = Vec::with_capacity(tokens.len());
+    let mut errors = Vec::new();
+    let mut open: Vec<OpenConditional> = Vec::new();
+    let mut depth = 0;
+    let mut tokens = tokens.into_iter().peekable();
+
+    while let Some(token) = tokens.next() {
+        let skipping = open.iter().any(|block| !block.included);
+        let location = (token.line, token.column);
+        match token.token_type {
+            TokenType::Directive => {
+                let name = match tokens.next_if(|next| next.token_type == TokenType::Identifier) {
+                    Some(name) => name.value,
+                    None => {
+                        errors.push((format!("Expected a flag name after '{}'", token.value), location));
+                        continue;
+                    }
+                };
+                if tokens.next_if(|next| next.token_type == TokenType::LeftBracket).is_none() {
+                    errors.push((format!("Expected '{{' after '#if {}'", name), location));
+                    continue;
+                }
+                let included = defines.contains(&name);
+                open.push(OpenConditional { name, location, depth, included });
+                depth += 1;
+            }
+            TokenType::LeftBracket => {
+                depth += 1;
+                if !skipping {
+                    filtered.push(token);
+                }
+            }
+            TokenType::RightBracket => {
+                depth = depth.saturating_sub(1);
+                if open.last().is_some_and(|block| block.depth == depth) {
+                    open.pop();
+                } else if !skipping {
+                    filtered.push(token);
+                }
+            }
+            TokenType::EOF => filtered.push(token),
+            _ => {
+                if !skipping {
+                    filtered.push(token);
+                }
+            }
+        }
+    }
+
+    for block in open {
+        errors.push((format!("Missing '}}' to close '#if {}'", block.name), block.location));
+    }
+    (filtered, errors)
+}
@@ -0,0 +1,66 @@
+#[cfg(test)]
+mod conditional_tests {
+    use crate::lex::conditional::filter_conditionals;
+    use crate::lex::lexer::Lexer;
+    use crate::lex::models::token_type::TokenType;
+
+    // Helper function filtering the tokens of the source, returning the kept values (EOF
+    // excluded) and the errors
+    fn filter(source: &str, defines: &[&str]) -> (Vec<String>, Vec<String>) {
+        let defines: Vec<String> = defines.iter().map(|name| name.to_string()).collect();
+        let tokens = Lexer::new(source.to_string()).tokenize();
+        let (tokens, errors) = filter_conditionals(tokens, &defines);
+        assert_eq!(tokens.last().map(|token| &token.token_type), Some(&TokenType::EOF));
+        let values = tokens[..tokens.len() - 1].iter().map(|token| token.value.clone()).collect();
+        (values, errors.into_iter().map(|(message, _)| message).collect())
+    }
+
+    #[test]
+    fn test_directive_token() {
+        let tokens = Lexer::new("#if DEBUG #else".to_string()).tokenize();
+        assert_eq!(tokens[0].token_type, TokenType::Directive);
+        assert_eq!(tokens[0].value, "#if");
+        assert_eq!(tokens[1].token_type, TokenType::Identifier);
+        assert_eq!(tokens[2].token_type, TokenType::Illegal);
+        assert_eq!(tokens[2].value, "#else");
+    }
+
+    #[test]
+    fn test_block_kept_only_when_defined() {
+        let source = "print(1);\n#if DEBUG {\n    print(2);\n}\nprint(3);";
+        let (without, errors) = filter(source, &[]);
+        assert!(errors.is_empty());
+        assert_eq!(without, vec!["print", "(", "1", ")", ";", "print", "(", "3", ")", ";"]);
+
+        // The block's own braces disappear, its statements join the enclosing list
+        let (with, errors) = filter(source, &["DEBUG"]);
+        assert!(errors.is_empty());
+        assert_eq!(with.join(" "), "print ( 1 ) ; print ( 2 ) ; print ( 3 ) ;");
+    }
+
+    #[test]
+    fn test_nested_blocks_and_inner_braces() {
+        let source = "#if A { if (x) { print(1); }; #if B { print(2); } } print(3);";
+        assert_eq!(filter(source, &["A"]).0.join(" "), "if ( x ) { print ( 1 ) ; } ; print ( 3 ) ;");
+        assert_eq!(
+            filter(source, &["A", "B"]).0.join(" "),
+            "if ( x ) { print ( 1 ) ; } ; print ( 2 ) ; print ( 3 ) ;"
+        );
+        // An excluded outer block hides the inner one, even when its flag is defined
+        assert_eq!(filter(source, &["B"]).0.join(" "), "print ( 3 ) ;");
+    }
+
+    #[test]
+    fn test_malformed_directives() {
+        assert_eq!(filter("#if { print(1); }", &[]).1, vec!["Expected a flag name after '#if'"]);
+        assert_eq!(filter("#if DEBUG print(1);", &[]).1, vec!["Expected '{' after '#if DEBUG'"]);
+        assert_eq!(filter("#if DEBUG { print(1);", &[]).1, vec!["Missing '}' to close '#if DEBUG'"]);
+    }
+
+    #[test]
+    fn test_errors_point_at_the_directive() {
+        let tokens = Lexer::new("print(1);\n  #if DEBUG {\nprint(2);".to_string()).tokenize();
+        let (_, errors) = filter_conditionals(tokens, &[]);
+        assert_eq!(errors, vec![("Missing '}' to close '#if DEBUG'".to_string(), (2, 3))]);
+    }
+}
\ No newline at end of file
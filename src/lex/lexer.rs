@@ -8,21 +8,40 @@ pub struct Lexer {
     pub position: usize,
     pub read_position: usize,
     pub ch: char,
+    /// Line of the current character, starting at 1.
+    pub line: usize,
+    /// Byte offset where the current line starts.
+    pub line_start: usize,
 }
 
 impl Lexer {
+    /// Creates a lexer over `input`.
+    /// 🔁 `\r\n` and lone `\r` line endings are normalized to `\n` up front, so
+    /// comment termination and line handling behave the same on every platform.
     pub fn new(input: String) -> Lexer {
+        let input = if input.contains('\r') {
+            input.replace("\r\n", "\n").replace('\r', "\n")
+        } else {
+            input
+        };
         let mut l = Lexer {
             input,
             position: 0,
             read_position: 0,
             ch: '\0',
+            line: 1,
+            line_start: 0,
         };
         l.read_char();
         l
     }
 
     fn read_char(&mut self) {
+        // Leaving a newline moves to the start of the next line
+        if self.ch == '\n' {
+            self.line += 1;
+            self.line_start = self.read_position;
+        }
         if self.read_position >= self.input.len() {
             self.ch = '\0';
         } else {
@@ -30,68 +49,64 @@ impl Lexer {
         }
         self.position = self.read_position;
         self.read_position += 1;
+    }
 
-        if self.ch == '/' {
-            let next = if self.read_position < self.input.len() {
-                self.input.as_bytes()[self.read_position] as char
-            } else {
-                '\0'
-            };
-            if next == '/' {
-                self.read_position += 1;
-                self.position = self.read_position;
-                while self.read_position < self.input.len()
-                    && self.input.as_bytes()[self.read_position] as char != '\n'
-                {
-                    self.read_position += 1;
-                }
-                if self.read_position < self.input.len() {
-                    self.ch = self.input.as_bytes()[self.read_position] as char;
-                } else {
-                    self.ch = '\0';
-                }
-                self.read_position += 1;
-                self.position = self.read_position;
-            }
+    /// 👀 Returns the character after the current one without consuming it.
+    fn peek_char(&self) -> char {
+        if self.read_position >= self.input.len() {
+            '\0'
+        } else {
+            self.input.as_bytes()[self.read_position] as char
         }
     }
 
+    /// ⏭️ Skips whitespace and `//` comments. A comment ends at the next newline or at the
+    /// end of the input, so a comment on the last line needs no trailing newline.
     fn skip_whitespace(&mut self) {
-        while self.ch.is_whitespace() {
-            self.read_char();
+        loop {
+            if self.ch.is_whitespace() {
+                self.read_char();
+            } else if self.ch == '/' && self.peek_char() == '/' {
+                while self.ch != '\n' && self.ch != '\0' {
+                    self.read_char();
+                }
+            } else {
+                break;
+            }
         }
     }
 
     fn get_token_type(word: &str) -> TokenType {
         match word {
             "let" | "if" | "else" | "return" | "function" | "switch" | "case" | "default"
-            | "while" | "for" => TokenType::Keyword,
+            | "while" | "for" | "const" | "break" | "continue" | "module" => TokenType::Keyword,
             "int" | "float" | "bool" | "string" | "void" => TokenType::Type,
             "true" | "false" => TokenType::Bool,
             ";" => TokenType::Semicolon,
             ":" => TokenType::Colon,
+            "::" => TokenType::DoubleColon,
+            ".." => TokenType::Range,
             "," => TokenType::Comma,
             "=" => TokenType::Equals,
-            "+" | "-" | "*" | "/" | "==" | "<=" | ">=" | ">" | "<" | "%" | "!=" => {
+            "+" | "-" | "*" | "**" | "/" | "==" | "<=" | ">=" | ">" | "<" | "%" | "!=" | "!" | "&&"
+            | "||" => {
                 TokenType::Operator
             }
             "(" => TokenType::LeftParen,
             ")" => TokenType::RightParen,
             "{" => TokenType::LeftBracket,
             "}" => TokenType::RightBracket,
-            _ if word.parse::<i64>().is_ok() => TokenType::Int,
-            _ if word.parse::<f64>().is_ok() => TokenType::Float,
+            "[" => TokenType::LeftSquareBracket,
+            "]" => TokenType::RightSquareBracket,
+            // Numbers go through `read_number`, so words such as `inf` or `NaN`
+            // (which `parse::<f64>()` accepts) stay identifiers
             _ => TokenType::Identifier,
         }
     }
 
     fn read_operator(&mut self) -> String {
         let c1 = self.ch;
-        let c2 = if self.read_position < self.input.len() {
-            self.input.as_bytes()[self.read_position] as char
-        } else {
-            '\0'
-        };
+        let c2 = self.peek_char();
         match (c1, c2) {
             ('!', '=') => {
                 self.read_char();
@@ -113,6 +128,31 @@ impl Lexer {
                 self.read_char();
                 ">=".to_string()
             }
+            (':', ':') => {
+                self.read_char();
+                self.read_char();
+                "::".to_string()
+            }
+            ('.', '.') => {
+                self.read_char();
+                self.read_char();
+                "..".to_string()
+            }
+            ('*', '*') => {
+                self.read_char();
+                self.read_char();
+                "**".to_string()
+            }
+            ('&', '&') => {
+                self.read_char();
+                self.read_char();
+                "&&".to_string()
+            }
+            ('|', '|') => {
+                self.read_char();
+                self.read_char();
+                "||".to_string()
+            }
             _ => {
                 self.read_char();
                 c1.to_string()
@@ -120,60 +160,108 @@ impl Lexer {
         }
     }
 
+    /// 📜 Lexes the whole input into a token list ending with exactly one `EOF` token.
+    pub fn tokenize(&mut self) -> Vec<Token> {
+        let mut tokens = Vec::new();
+        loop {
+            let token = self.next_token();
+            let is_eof = token.token_type == TokenType::EOF;
+            tokens.push(token);
+            if is_eof {
+                return tokens;
+            }
+        }
+    }
+
+    /// 📍 Reads the next token, recording the line and column where it starts.
     pub fn next_token(&mut self) -> Token {
         self.skip_whitespace();
+        let line = self.line;
+        let column = self.position - self.line_start + 1;
+        let mut token = self.read_token();
+        token.line = line;
+        token.column = column;
+        token
+    }
 
+    fn read_token(&mut self) -> Token {
         if self.ch == '\0' {
-            return Token {
-                token_type: TokenType::EOF,
-                value: "".to_string(),
-            };
+            return Token::new(TokenType::EOF, "".to_string());
         }
 
-        if self.ch.is_alphabetic() {
+        if is_identifier_start(self.ch) {
             let word = self.read_identifier();
             let token_type = Self::get_token_type(&word);
-            return Token {
-                token_type,
-                value: word,
-            };
+            return Token::new(token_type, word);
         }
 
-        if self.ch.is_numeric() {
+        if self.ch.is_ascii_digit() {
+            let position = self.position;
             let number = self.read_number();
+            // A number glued to letters (`1x`, `2_000`) is neither a number nor an identifier
+            if is_identifier_char(self.ch) {
+                while is_identifier_char(self.ch) || self.ch == '.' {
+                    self.read_char();
+                }
+                let value = self.input[position..self.position].to_string();
+                return Token::new(TokenType::Illegal, value);
+            }
             let token_type = if number.contains('.') {
                 TokenType::Float
             } else {
                 TokenType::Int
             };
-            return Token {
-                token_type,
-                value: number,
+            return Token::new(token_type, number);
+        }
+
+        // `#if` and other directives are read as a single token, handled before parsing
+        if self.ch == '#' && is_identifier_start(self.peek_char()) {
+            self.read_char();
+            let word = format!("#{}", self.read_identifier());
+            let token_type = if word == "#if" {
+                TokenType::Directive
+            } else {
+                TokenType::Illegal
             };
+            return Token::new(token_type, word);
         }
 
         if self.ch == '"' || self.ch == '\'' {
             let string_value = self.read_string();
-            return Token {
-                token_type: TokenType::String,
-                value: string_value,
-            };
+            return Token::new(TokenType::String, string_value);
+        }
+
+        // Identifiers are ASCII-only: any other character is rejected as a whole
+        if !self.ch.is_ascii() {
+            let position = self.position;
+            while !self.ch.is_ascii() {
+                self.read_char();
+            }
+            let value = self.input[position..self.position].to_string();
+            return Token::new(TokenType::Illegal, value);
         }
 
         let op_str = self.read_operator();
         let token_type = Self::get_token_type(&op_str);
 
-        Token {
-            token_type,
-            value: op_str,
-        }
+        Token::new(token_type, op_str)
     }
 }
 
+/// 🔤 Identifiers start with an ASCII letter or `_`.
+fn is_identifier_start(ch: char) -> bool {
+    ch.is_ascii_alphabetic() || ch == '_'
+}
+
+/// 🔤 After the first character, identifiers may also contain ASCII digits.
+fn is_identifier_char(ch: char) -> bool {
+    ch.is_ascii_alphanumeric() || ch == '_'
+}
+
 impl TokenReader for Lexer {
     fn read_identifier(&mut self) -> String {
         let position = self.position;
-        while self.ch.is_alphanumeric() {
+        while is_identifier_char(self.ch) {
             self.read_char();
         }
         self.input[position..self.position].to_string()
@@ -183,7 +271,10 @@ impl TokenReader for Lexer {
         let position = self.position;
         let mut has_dot = false;
 
-        while self.ch.is_numeric() || (self.ch == '.' && !has_dot) {
+        // The dot of `1..5` starts a range: it does not make `1.` a float
+        while self.ch.is_ascii_digit()
+            || (self.ch == '.' && !has_dot && self.peek_char() != '.')
+        {
             if self.ch == '.' {
                 has_dot = true;
             }
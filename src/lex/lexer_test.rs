@@ -0,0 +1,148 @@
+#[cfg(test)]
+mod lexer_tests {
+    use crate::lex::lexer::Lexer;
+    use crate::lex::models::name::Name;
+    use crate::lex::models::token::Token;
+    use crate::lex::models::token_type::TokenType;
+
+    // Helper function collecting every token of the source, EOF excluded
+    fn tokenize(source: &str) -> Vec<Token> {
+        let mut tokens = Lexer::new(source.to_string()).tokenize();
+        tokens.pop();
+        tokens
+    }
+
+    // Helper function keeping only the token values
+    fn values(tokens: &[Token]) -> Vec<String> {
+        tokens.iter().map(|token| token.value.clone()).collect()
+    }
+
+    #[test]
+    fn test_crlf_and_tabs() {
+        let lf = tokenize("let x: int = 1; // comment\nprint(x);\n");
+        let crlf = tokenize("let x: int = 1; // comment\r\n\tprint(x);\r\n");
+        let cr = tokenize("let x: int = 1; // comment\rprint(x);\r");
+        // The tab shifts the columns of the second line, nothing else differs
+        let kinds = |tokens: &[Token]| -> Vec<(TokenType, String)> {
+            tokens.iter().map(|token| (token.token_type.clone(), token.value.clone())).collect()
+        };
+        assert_eq!(kinds(&crlf), kinds(&lf));
+        assert_eq!(cr, lf);
+        assert_eq!(
+            values(&crlf),
+            vec!["let", "x", ":", "int", "=", "1", ";", "print", "(", "x", ")", ";"]
+        );
+        assert!(crlf.iter().all(|token| !token.value.contains('\r')));
+    }
+
+    #[test]
+    fn test_comment_at_end_of_file() {
+        let tokens = tokenize("let x: int = 5; // comment");
+        assert_eq!(values(&tokens), vec!["let", "x", ":", "int", "=", "5", ";"]);
+
+        // A comment alone, or an empty comment, at the very end
+        assert!(tokenize("// only a comment").is_empty());
+        assert_eq!(values(&tokenize("print(x);//")), vec!["print", "(", "x", ")", ";"]);
+
+        // Division is not mistaken for a comment
+        assert_eq!(values(&tokenize("a / b; // c")), vec!["a", "/", "b", ";"]);
+    }
+    #[test]
+    fn test_tokenize_ends_with_one_eof() {
+        for source in ["let", "", "// comment", "print(1);\n"] {
+            let tokens = Lexer::new(source.to_string()).tokenize();
+            let eof_count = tokens.iter().filter(|t| t.token_type == TokenType::EOF).count();
+            assert_eq!(eof_count, 1, "Expected one EOF for {:?}", source);
+            assert_eq!(tokens.last().unwrap().token_type, TokenType::EOF);
+        }
+        let tokens = Lexer::new("let".to_string()).tokenize();
+        assert_eq!(values(&tokens), vec!["let", ""]);
+    }
+    #[test]
+    fn test_double_colon() {
+        let tokens = tokenize("math::sqrt(x: int)");
+        assert_eq!(values(&tokens), vec!["math", "::", "sqrt", "(", "x", ":", "int", ")"]);
+        assert_eq!(tokens[1].token_type, TokenType::DoubleColon);
+        assert_eq!(tokens[4].token_type, TokenType::Identifier);
+        assert_eq!(tokens[5].token_type, TokenType::Colon);
+    }
+
+    #[test]
+    fn test_underscore_identifiers() {
+        let tokens = tokenize("_x x_y camelCase2 __");
+        assert!(tokens.iter().all(|token| token.token_type == TokenType::Identifier));
+        assert_eq!(values(&tokens), vec!["_x", "x_y", "camelCase2", "__"]);
+    }
+
+    #[test]
+    fn test_identifiers_are_interned() {
+        let tokens = tokenize("total = total + other;");
+        let names: Vec<Option<Name>> = tokens.iter().map(|token| token.name).collect();
+        // Both occurrences of `total` share one name, distinct from `other`
+        assert_eq!(names[0], names[2]);
+        assert_ne!(names[0], names[4]);
+        assert_eq!(names[0], Some(Name::intern("total")));
+        assert_eq!(names[0].unwrap().as_str(), "total");
+        // Operators and punctuation carry no name
+        assert_eq!(names[1], None);
+        assert_eq!(names[5], None);
+    }
+
+    #[test]
+    fn test_invalid_identifiers_are_rejected() {
+        // A number glued to letters is a single illegal token, not `1` followed by `x`
+        let tokens = tokenize("1x 2_000 3.5e 4");
+        assert_eq!(values(&tokens), vec!["1x", "2_000", "3.5e", "4"]);
+        assert_eq!(tokens[0].token_type, TokenType::Illegal);
+        assert_eq!(tokens[1].token_type, TokenType::Illegal);
+        assert_eq!(tokens[2].token_type, TokenType::Illegal);
+        assert_eq!(tokens[3].token_type, TokenType::Int);
+
+        // Identifiers are ASCII-only
+        let tokens = tokenize("let café: int = 1;");
+        assert_eq!(values(&tokens)[1..4], ["caf", "é", ":"]);
+        assert_eq!(tokens[2].token_type, TokenType::Illegal);
+    }
+
+    #[test]
+    fn test_power_operator() {
+        let tokens = tokenize("2 ** 3 * 4");
+        assert_eq!(values(&tokens), vec!["2", "**", "3", "*", "4"]);
+        assert_eq!(tokens[1].token_type, TokenType::Operator);
+    }
+
+    #[test]
+    fn test_token_positions() {
+        let tokens = Lexer::new("let x: int = 1;\n\n  print(x); // done\nlen(\"a b\")".to_string()).tokenize();
+        let positions: Vec<(&str, usize, usize)> = tokens
+            .iter()
+            .map(|token| (token.value.as_str(), token.line, token.column))
+            .collect();
+        assert_eq!(&positions[..3], &[("let", 1, 1), ("x", 1, 5), (":", 1, 6)]);
+        assert_eq!(&positions[7..9], &[("print", 3, 3), ("(", 3, 8)]);
+        assert_eq!(&positions[12..], &[("len", 4, 1), ("(", 4, 4), ("a b", 4, 5), (")", 4, 10), ("", 4, 11)]);
+    }
+
+    #[test]
+    fn test_comments_inside_expressions() {
+        // A comment may stand wherever whitespace can, even glued to an operator
+        let tokens = tokenize("let x: int = 1 + // note\n 2 *// tight\n3;");
+        assert_eq!(values(&tokens), vec!["let", "x", ":", "int", "=", "1", "+", "2", "*", "3", ";"]);
+        // The token after the comment keeps its real position
+        assert_eq!((tokens[7].line, tokens[7].column), (2, 2));
+
+        // A third slash belongs to the comment, and `//` inside a string is text
+        assert_eq!(values(&tokenize("8 /// triple\n;")), vec!["8", ";"]);
+        assert_eq!(values(&tokenize("\"a // b\";")), vec!["a // b", ";"]);
+    }
+
+    #[test]
+    fn test_range() {
+        // `1..5` is a range between two integers, not the float `1.` followed by `.5`
+        let tokens = tokenize("case 1..5 case -2 .. 2.5");
+        assert_eq!(values(&tokens), vec!["case", "1", "..", "5", "case", "-", "2", "..", "2.5"]);
+        assert_eq!(tokens[1].token_type, TokenType::Int);
+        assert_eq!(tokens[2].token_type, TokenType::Range);
+        assert_eq!(tokens[8].token_type, TokenType::Float);
+    }
+}
\ No newline at end of file
@@ -1,2 +1,5 @@
+pub mod conditional;
+pub mod conditional_test;
 pub mod lexer;
+pub mod lexer_test;
 pub mod models;
\ No newline at end of file
@@ -1,3 +1,4 @@
 pub mod token_type;
 pub mod token;
-pub mod token_reader;
\ No newline at end of file
+pub mod token_reader;
+pub mod name;
\ No newline at end of file
@@ -0,0 +1,86 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::ops::Deref;
+use std::sync::{OnceLock, PoisonError, RwLock};
+
+/// 🏷️ An interned identifier. Equal names share the same `u32` id, so comparing or hashing a
+/// name never reads its characters.
+///
+/// The lexer interns each identifier when it creates the token; the text of every name then
+/// lives until the process exits and is shared by all the compilations it runs.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Name(u32);
+
+/// 📚 The interned names, indexed by id, and the id of each text.
+#[derive(Default)]
+struct Interner {
+    ids: HashMap<&'static str, Name>,
+    names: Vec<&'static str>,
+}
+
+fn interner() -> &'static RwLock<Interner> {
+    static INTERNER: OnceLock<RwLock<Interner>> = OnceLock::new();
+    INTERNER.get_or_init(Default::default)
+}
+
+impl Name {
+    /// 🔖 Returns the name of `text`, registering it on its first occurrence.
+    pub fn intern(text: &str) -> Name {
+        if let Some(&name) = interner()
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .ids
+            .get(text)
+        {
+            return name;
+        }
+        let mut interner = interner().write().unwrap_or_else(PoisonError::into_inner);
+        // Another thread may have registered it between the two locks
+        if let Some(&name) = interner.ids.get(text) {
+            return name;
+        }
+        let text: &'static str = Box::leak(text.into());
+        let name = Name(interner.names.len() as u32);
+        interner.names.push(text);
+        interner.ids.insert(text, name);
+        name
+    }
+
+    /// 📖 The text of the name.
+    pub fn as_str(self) -> &'static str {
+        interner().read().unwrap_or_else(PoisonError::into_inner).names[self.0 as usize]
+    }
+}
+
+impl Deref for Name {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl PartialEq<&str> for Name {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl PartialEq<String> for Name {
+    fn eq(&self, other: &String) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl fmt::Display for Name {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// 🔍 Shown as its text, so that a debug-printed AST reads `Ident("x")`.
+impl fmt::Debug for Name {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
@@ -1,7 +1,35 @@
+use super::name::Name;
 use super::token_type::TokenType;
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct Token {
     pub token_type: TokenType,
     pub value: String,
-}
\ No newline at end of file
+    /// 📍 Line of the token's first character, starting at 1 (0 for a synthesized token).
+    pub line: usize,
+    /// 📍 Column of the token's first character in bytes, starting at 1.
+    pub column: usize,
+    /// 🏷️ The interned name of an identifier token, `None` for any other token.
+    pub name: Option<Name>,
+}
+
+impl Token {
+    /// 🧱 Creates a token without a source position; the lexer sets it afterwards.
+    /// An identifier is interned here, once for the whole compilation.
+    pub fn new(token_type: TokenType, value: String) -> Self {
+        let name = (token_type == TokenType::Identifier).then(|| Name::intern(&value));
+        Token {
+            token_type,
+            value,
+            line: 0,
+            column: 0,
+            name,
+        }
+    }
+
+    /// 🏷️ The name the token stands for: interned by the lexer for an identifier, or interned
+    /// now for a reserved word used as a name, which the analyzer then reports.
+    pub fn interned_name(&self) -> Name {
+        self.name.unwrap_or_else(|| Name::intern(&self.value))
+    }
+}
@@ -11,10 +11,19 @@ pub enum TokenType {
     Equals,
     Semicolon,
     Colon,
+    DoubleColon,
+    /// The `..` between the bounds of a range, as in `case 1..5`.
+    Range,
     Comma,
     LeftParen, 
     RightParen,
     LeftBracket,
     RightBracket,
+    LeftSquareBracket,
+    RightSquareBracket,
+    /// A preprocessor directive such as `#if`.
+    Directive,
+    Illegal,
+    #[allow(clippy::upper_case_acronyms)]
     EOF,
 }
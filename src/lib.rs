@@ -0,0 +1,5 @@
+pub mod codegen;
+pub mod driver;
+pub mod lex;
+pub mod parser;
+pub mod semantic;
@@ -1,21 +1,117 @@
 use super::models::expression::FunctionCall;
 use super::parser::Parser;
+use crate::lex::models::name::Name;
 use crate::lex::models::token_type::TokenType;
-use crate::parser::models::expression::{BinaryExpression, Expression};
+use crate::parser::models::expression::{
+    BinaryExpression, ComparisonChain, Expression, IndexExpression, UnaryExpression,
+};
+
+/// 🧱 Deepest nesting accepted inside one expression, counting each operator applied to a
+/// sub-expression (so each term of a flat `a + b + c` chain), parenthesized or bracketed
+/// sub-expression and call argument. Parsing and the later passes over the tree are recursive,
+/// so a deeper input is rejected instead of overflowing the stack.
+pub const MAX_EXPRESSION_DEPTH: usize = 128;
 
 /// ✨ Parses a full expression by starting with the highest-level function
 /// and returning the resulting `Expression`.
 pub fn parse_expression(parser: &mut Parser) -> Option<Expression> {
-    parse_equality(parser)
+    let depth = parser.expression_depth;
+    let expr = parse_subexpression(parser);
+    parser.expression_depth = depth;
+    expr
+}
+
+/// 🧩 Parses an expression nested in another one, such as a parenthesized sub-expression,
+/// an index or a call argument, one level deeper than where it starts.
+fn parse_subexpression(parser: &mut Parser) -> Option<Expression> {
+    parse_nested(parser, parse_or)
+}
+
+/// 🪜 Runs `parse` one nesting level deeper, or reports `Expression nesting too deep`
+/// once `MAX_EXPRESSION_DEPTH` is reached.
+///
+/// `expression_depth` is left at the depth reached by the parsed expression, so that the
+/// operator applied to it next counts one level above it.
+fn parse_nested(
+    parser: &mut Parser,
+    parse: fn(&mut Parser) -> Option<Expression>,
+) -> Option<Expression> {
+    if parser.expression_depth >= MAX_EXPRESSION_DEPTH {
+        parser.error("Expression nesting too deep");
+        return None;
+    }
+    parser.expression_depth += 1;
+    parse(parser)
+}
+
+/// 📏 Parses the right operand of a binary operator from `start`, the depth at which its
+/// left operand began, then records the depth of the new node: one level above the deeper
+/// of its two operands.
+fn parse_right_operand(
+    parser: &mut Parser,
+    start: usize,
+    parse: fn(&mut Parser) -> Option<Expression>,
+) -> Option<Expression> {
+    let left_depth = parser.expression_depth;
+    parser.expression_depth = start;
+    let right = parse(parser)?;
+    deepen(parser, left_depth.max(parser.expression_depth) + 1)?;
+    Some(right)
+}
+
+/// 📐 Sets `expression_depth` to the depth reached by a node whose children are already
+/// parsed, or reports `Expression nesting too deep` past `MAX_EXPRESSION_DEPTH`.
+fn deepen(parser: &mut Parser, depth: usize) -> Option<()> {
+    if depth > MAX_EXPRESSION_DEPTH {
+        parser.error("Expression nesting too deep");
+        return None;
+    }
+    parser.expression_depth = depth;
+    Some(())
+}
+
+/// 🔀 Parses logical OR (`||`), the loosest binary operator.
+pub fn parse_or(parser: &mut Parser) -> Option<Expression> {
+    let start = parser.expression_depth;
+    let mut expr = parse_and(parser)?;
+    while parser.check_operator(&["||"]) {
+        let op_token = parser.advance();
+        let op = op_token.value;
+        let right = parse_right_operand(parser, start, parse_and)?;
+        expr = Expression::Binary(Box::new(BinaryExpression {
+            left: expr,
+            op,
+            right,
+        }));
+    }
+    Some(expr)
+}
+
+/// 🔗 Parses logical AND (`&&`), which binds tighter than `||`.
+pub fn parse_and(parser: &mut Parser) -> Option<Expression> {
+    let start = parser.expression_depth;
+    let mut expr = parse_equality(parser)?;
+    while parser.check_operator(&["&&"]) {
+        let op_token = parser.advance();
+        let op = op_token.value;
+        let right = parse_right_operand(parser, start, parse_equality)?;
+        expr = Expression::Binary(Box::new(BinaryExpression {
+            left: expr,
+            op,
+            right,
+        }));
+    }
+    Some(expr)
 }
 
 /// ⚖️ Parses equality operators (`==`, `!=`).
 pub fn parse_equality(parser: &mut Parser) -> Option<Expression> {
+    let start = parser.expression_depth;
     let mut expr = parse_comparison(parser)?;
     while parser.check_operator(&["==", "!="]) {
         let op_token = parser.advance();
         let op = op_token.value;
-        let right = parse_comparison(parser)?;
+        let right = parse_right_operand(parser, start, parse_comparison)?;
         expr = Expression::Binary(Box::new(BinaryExpression {
             left: expr,
             op,
@@ -26,28 +122,46 @@ pub fn parse_equality(parser: &mut Parser) -> Option<Expression> {
 }
 
 /// 🔍 Parses comparison operators (`<`, `<=`, `>`, `>=`).
+/// A chain such as `a < b < c` reads as `(a < b) && (b < c)` and becomes a single
+/// `ComparisonChain`, so that `b` is evaluated only once.
 pub fn parse_comparison(parser: &mut Parser) -> Option<Expression> {
-    let mut expr = parse_term(parser)?;
+    let start = parser.expression_depth;
+    let mut operands = vec![parse_term(parser)?];
+    let mut ops = Vec::new();
+    let mut depth = parser.expression_depth;
     while parser.check_operator(&["<", "<=", ">", ">="]) {
         let op_token = parser.advance();
-        let op = op_token.value;
-        let right = parse_term(parser)?;
-        expr = Expression::Binary(Box::new(BinaryExpression {
-            left: expr,
+        ops.push(op_token.value);
+        parser.expression_depth = start;
+        operands.push(parse_term(parser)?);
+        depth = depth.max(parser.expression_depth);
+    }
+    if !ops.is_empty() {
+        // The whole chain is a single node
+        deepen(parser, depth + 1)?;
+    }
+    if ops.len() > 1 {
+        return Some(Expression::Comparison(Box::new(ComparisonChain { operands, ops })));
+    }
+    let right = operands.pop()?;
+    match ops.pop() {
+        Some(op) => Some(Expression::Binary(Box::new(BinaryExpression {
+            left: operands.pop()?,
             op,
             right,
-        }));
+        }))),
+        None => Some(right),
     }
-    Some(expr)
 }
 
 /// ➕ Parses addition and subtraction operators (`+`, `-`).
 pub fn parse_term(parser: &mut Parser) -> Option<Expression> {
+    let start = parser.expression_depth;
     let mut expr = parse_factor(parser)?;
     while parser.check_operator(&["+", "-"]) {
         let op_token = parser.advance();
         let op = op_token.value;
-        let right = parse_factor(parser)?;
+        let right = parse_right_operand(parser, start, parse_factor)?;
         expr = Expression::Binary(Box::new(BinaryExpression {
             left: expr,
             op,
@@ -59,11 +173,12 @@ pub fn parse_term(parser: &mut Parser) -> Option<Expression> {
 
 /// ✖️ Parses multiplication, division, and modulo operators (`*`, `/`, `%`).
 pub fn parse_factor(parser: &mut Parser) -> Option<Expression> {
-    let mut expr = parse_unary(parser)?;
+    let start = parser.expression_depth;
+    let mut expr = parse_power(parser)?;
     while parser.check_operator(&["*", "/", "%"]) {
         let op_token = parser.advance();
         let op = op_token.value;
-        let right = parse_unary(parser)?;
+        let right = parse_right_operand(parser, start, parse_power)?;
         expr = Expression::Binary(Box::new(BinaryExpression {
             left: expr,
             op,
@@ -73,26 +188,62 @@ pub fn parse_factor(parser: &mut Parser) -> Option<Expression> {
     Some(expr)
 }
 
-/// 🚀 Parses unary operators like `-` and `!`.
+/// 🔺 Parses exponentiation (`**`), which binds tighter than `*` and is right-associative:
+/// `2 ** 3 ** 2` is `2 ** (3 ** 2)`. Its operands are unary expressions, so `-2 ** 2` is
+/// `(-2) ** 2`.
+pub fn parse_power(parser: &mut Parser) -> Option<Expression> {
+    let start = parser.expression_depth;
+    let base = parse_unary(parser)?;
+    if !parser.check_operator(&["**"]) {
+        return Some(base);
+    }
+    let op = parser.advance().value;
+    // Right-associative: the exponent is itself a power, nested one level deeper
+    let base_depth = parser.expression_depth;
+    parser.expression_depth = start;
+    let exponent = parse_nested(parser, parse_power)?;
+    parser.expression_depth = parser.expression_depth.max(base_depth + 1);
+    Some(Expression::Binary(Box::new(BinaryExpression {
+        left: base,
+        op,
+        right: exponent,
+    })))
+}
+
+/// 🚀 Parses prefix unary operators: `-`, `+` and `!`.
+/// They bind tighter than any binary operator, so `-a * b` is `(-a) * b`, but looser than
+/// postfix operators and calls, so `-a[i]` is `-(a[i])` and `-f(x)` is `-(f(x))`.
 pub fn parse_unary(parser: &mut Parser) -> Option<Expression> {
-    if parser.check_operator(&["-", "!"]) {
+    if parser.check_operator(&["-", "+", "!"]) {
         let op_token = parser.advance();
         let op = op_token.value;
-        let right = parse_unary(parser)?;
-        return Some(Expression::Binary(Box::new(BinaryExpression {
-            left: Expression::Int(0),
-            op,
-            right,
-        })));
+        let operand = parse_nested(parser, parse_unary)?;
+        return Some(Expression::Unary(Box::new(UnaryExpression { op, operand })));
+    }
+    parse_postfix(parser)
+}
+
+/// 📌 Parses postfix operators applied to a primary, such as indexing (`xs[i]`).
+pub fn parse_postfix(parser: &mut Parser) -> Option<Expression> {
+    let start = parser.expression_depth;
+    let mut expr = parse_primary(parser)?;
+    while parser.check(TokenType::LeftSquareBracket) {
+        parser.advance();
+        let index = parse_right_operand(parser, start, parse_subexpression)?;
+        parser.consume(TokenType::RightSquareBracket, "Expected ']' after index")?;
+        expr = Expression::Index(Box::new(IndexExpression {
+            target: expr,
+            index,
+        }));
     }
-    parse_primary(parser)
+    Some(expr)
 }
 
 /// 🏷️ Parses primary elements: parentheses, literals, and identifiers.
 pub fn parse_primary(parser: &mut Parser) -> Option<Expression> {
     if parser.check(TokenType::LeftParen) {
         parser.advance();
-        let expr = parse_expression(parser)?;
+        let expr = parse_subexpression(parser)?;
         parser.consume(TokenType::RightParen, "Expected ')'")?;
         return Some(expr);
     }
@@ -102,47 +253,111 @@ pub fn parse_primary(parser: &mut Parser) -> Option<Expression> {
             if let Ok(val) = token.value.parse::<i64>() {
                 Some(Expression::Int(val))
             } else {
-                eprintln!("Cannot parse int from '{}'", token.value);
+                parser.error(&format!("Cannot parse int from '{}'", token.value));
                 None
             }
         }
         TokenType::Float => {
             if let Ok(val) = token.value.parse::<f64>() {
+                // A non-zero literal too small for a 64-bit float would silently become 0
+                if val == 0.0 && token.value.bytes().any(|b| matches!(b, b'1'..=b'9')) {
+                    parser.error(&format!(
+                        "Float literal '{}' is too small for a 64-bit float",
+                        token.value
+                    ));
+                    return None;
+                }
                 Some(Expression::Float(val))
             } else {
-                eprintln!("Cannot parse float from '{}'", token.value);
+                parser.error(&format!("Cannot parse float from '{}'", token.value));
                 None
             }
         }
         TokenType::Identifier => {
-            let ident_name = token.value;
+            let mut name = token.interned_name();
+            // `module::function` : only functions live in modules, so a call must follow
+            if parser.check(TokenType::DoubleColon) {
+                parser.advance();
+                let member = parser.consume(TokenType::Identifier, "Expected a function name after '::'")?;
+                name = Name::intern(&format!("{}::{}", name, member.value));
+                if !parser.check(TokenType::LeftParen) {
+                    parser.error(&format!("Expected '(' after qualified name '{}'", name));
+                    return None;
+                }
+            }
             if parser.check(TokenType::LeftParen) {
                 parser.advance();
+                let start = parser.expression_depth;
+                let mut depth = start;
                 let mut args = Vec::new();
                 while !parser.check(TokenType::RightParen) && !parser.is_at_end() {
-                    let arg_expr = parse_expression(parser)?;
+                    if parser.check(TokenType::Comma) {
+                        parser.error("Expected an argument before ','");
+                        return None;
+                    }
+                    parser.expression_depth = start;
+                    let arg_expr = parse_subexpression(parser)?;
+                    depth = depth.max(parser.expression_depth);
                     args.push(arg_expr);
+                    // Arguments are separated by exactly one comma; a single trailing comma is allowed
                     if parser.check(TokenType::Comma) {
                         parser.advance();
+                    } else if !parser.check(TokenType::RightParen) {
+                        parser.error("Expected ',' between arguments");
+                        return None;
                     }
                 }
                 parser.consume(TokenType::RightParen, "Expected ')' after function call")?;
+                parser.expression_depth = depth;
                 Some(Expression::FunctionCall(Box::new(FunctionCall {
-                    name: ident_name,
+                    name,
                     arguments: args,
                 })))
             } else {
                 // Juste un ident
-                Some(Expression::Ident(ident_name))
+                Some(Expression::Ident(name))
+            }
+        }
+        TokenType::LeftSquareBracket => {
+            // `[a, b, c]` : an array literal, only accepted as an array initializer by the analyzer
+            let start = parser.expression_depth;
+            let mut depth = start;
+            let mut elements = Vec::new();
+            while !parser.check(TokenType::RightSquareBracket) && !parser.is_at_end() {
+                if parser.check(TokenType::Comma) {
+                    parser.error("Expected an element before ','");
+                    return None;
+                }
+                parser.expression_depth = start;
+                elements.push(parse_subexpression(parser)?);
+                depth = depth.max(parser.expression_depth);
+                if parser.check(TokenType::Comma) {
+                    parser.advance();
+                } else if !parser.check(TokenType::RightSquareBracket) {
+                    parser.error("Expected ',' between array elements");
+                    return None;
+                }
             }
+            parser.consume(TokenType::RightSquareBracket, "Expected ']' after array elements")?;
+            parser.expression_depth = depth;
+            Some(Expression::ArrayLiteral(elements))
         }
         TokenType::Bool => {
             let b = token.value == "true";
             Some(Expression::Bool(b))
         }
-        TokenType::String => Some(Expression::Str(token.value)),
+        TokenType::String => {
+            // Adjacent literals are merged, as in C: `"foo" "bar"` is `"foobar"`
+            let mut value = token.value;
+            while parser.check(TokenType::String) {
+                value.push_str(&parser.advance().value);
+            }
+            Some(Expression::Str(value))
+        }
+        // A type name in value position (e.g. `void`) is rejected by the analyzer
+        TokenType::Type => Some(Expression::Ident(token.interned_name())),
         _ => {
-            eprintln!("Unexpected token in parse_primary: {:?}", token);
+            parser.error(&format!("Unexpected token '{}' in expression", token.value));
             None
         }
     }
@@ -1,4 +1,9 @@
+#[allow(clippy::module_inception)]
 pub mod parser;
+pub mod parser_test;
 pub mod statement_parser;
 pub mod expression_parser;
-pub mod models;
\ No newline at end of file
+pub mod models;
+pub mod visit;
+pub mod serialize;
+pub mod serialize_test;
\ No newline at end of file
@@ -1,6 +1,7 @@
 use crate::parser::models::statement::Statement;
 
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Debug, PartialEq, Clone, Default)]
+#[allow(clippy::upper_case_acronyms)]
 pub struct AST {
     pub statements: Vec<Statement>,
 }
\ No newline at end of file
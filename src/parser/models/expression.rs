@@ -1,14 +1,20 @@
 use std::fmt;
 
+use crate::lex::models::name::Name;
+
 #[derive(Debug, PartialEq, Clone)]
 pub enum Expression {
-    Ident(String),
+    Ident(Name),
     Int(i64),
     Float(f64),
     Str(String),
     Bool(bool),
     Binary(Box<BinaryExpression>),
+    Unary(Box<UnaryExpression>),
     FunctionCall(Box<FunctionCall>),
+    Index(Box<IndexExpression>),
+    Comparison(Box<ComparisonChain>),
+    ArrayLiteral(Vec<Expression>),
 }
 impl fmt::Display for Expression {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -19,7 +25,14 @@ impl fmt::Display for Expression {
             Expression::Str(s) => write!(f, "\"{}\"", s),
             Expression::Bool(b) => write!(f, "{}", b),
             Expression::Binary(b) => write!(f, "{}", b),
+            Expression::Unary(u) => write!(f, "{}", u),
             Expression::FunctionCall(fc) => write!(f, "{}", fc),
+            Expression::Index(ie) => write!(f, "{}", ie),
+            Expression::Comparison(chain) => write!(f, "{}", chain),
+            Expression::ArrayLiteral(elements) => {
+                let elements: Vec<String> = elements.iter().map(|e| e.to_string()).collect();
+                write!(f, "[{}]", elements.join(", "))
+            }
         }
     }
 }
@@ -27,7 +40,7 @@ impl fmt::Display for Expression {
 impl AsRef<str> for Expression {
     fn as_ref(&self) -> &str {
         match self {
-            Expression::Ident(s) => s,
+            Expression::Ident(name) => name.as_str(),
             Expression::Str(s) => s,
             _ => panic!("Cannot convert this expression to &str"),
         }
@@ -40,6 +53,18 @@ impl fmt::Display for BinaryExpression {
     }
 }
 
+impl fmt::Display for UnaryExpression {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let operand = self.operand.to_string();
+        // `-(-3)` rather than `--3`, which would read as a single operator
+        if operand.starts_with(['-', '+']) {
+            write!(f, "{}({})", self.op, operand)
+        } else {
+            write!(f, "{}{}", self.op, operand)
+        }
+    }
+}
+
 impl fmt::Display for FunctionCall {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let args: Vec<String> = self.arguments.iter().map(|arg| arg.to_string()).collect();
@@ -47,6 +72,22 @@ impl fmt::Display for FunctionCall {
     }
 }
 
+impl fmt::Display for IndexExpression {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}[{}]", self.target, self.index)
+    }
+}
+
+impl fmt::Display for ComparisonChain {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({}", self.operands[0])?;
+        for (op, operand) in self.ops.iter().zip(&self.operands[1..]) {
+            write!(f, " {} {}", op, operand)?;
+        }
+        write!(f, ")")
+    }
+}
+
 #[derive(Debug, PartialEq, Clone)]
 pub struct BinaryExpression {
     pub left: Expression,
@@ -54,8 +95,30 @@ pub struct BinaryExpression {
     pub right: Expression,
 }
 
+#[derive(Debug, PartialEq, Clone)]
+pub struct UnaryExpression {
+    pub op: String,
+    pub operand: Expression,
+}
+
 #[derive(Debug, PartialEq, Clone)]
 pub struct FunctionCall {
-    pub name: String,
+    pub name: Name,
     pub arguments: Vec<Expression>,
 }
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct IndexExpression {
+    pub target: Expression,
+    pub index: Expression,
+}
+
+/// A chained comparison such as `a < b < c`, meaning `(a < b) && (b < c)`.
+/// Each inner operand is evaluated once and the chain stops at the first false comparison.
+#[derive(Debug, PartialEq, Clone)]
+pub struct ComparisonChain {
+    /// The compared operands, at least three of them.
+    pub operands: Vec<Expression>,
+    /// The operator between each pair of neighbouring operands.
+    pub ops: Vec<String>,
+}
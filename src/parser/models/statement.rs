@@ -1,34 +1,54 @@
+use crate::lex::models::name::Name;
 use crate::parser::models::expression::Expression;
 
 #[derive(Debug, PartialEq, Clone)]
+#[allow(clippy::enum_variant_names)] // `ExpressionStatement` names an expression used as a statement
 pub enum Statement {
     VarDeclaration(VarDeclaration),
+    /// `let a: int = 1, b: float = 2.0;` : several declarators, each with its own type.
+    VarDeclarations(Vec<VarDeclaration>),
     VarAffection(VarAffection),
-    Return(Option<Expression>),
+    Return(Return),
     If(IfStatement),
     Switch(SwitchStatement),
     While(WhileStatement),
     For(ForStatement),
     FunctionDeclaration(FunctionDeclaration),
+    Module(ModuleDeclaration),
     ExpressionStatement(Expression),
+    Break(Option<String>),
+    Continue(Option<String>),
+    Block(Vec<Statement>),
 }
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct VarDeclaration {
-    pub name: String,
+    pub name: Name,
     pub type_name: String,
+    pub array_size: Option<Expression>,
+    pub is_const: bool,
     pub init: Option<Expression>,
 }
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct VarAffection {
-    pub name: String,
+    pub name: Name,
+    pub index: Option<Expression>,
     pub value: Expression,
 }
 
+/// A `module name { ... }` block grouping functions under a namespace.
+/// The functions keep their qualified name (`math::sqrt`), which is how they are called.
+#[derive(Debug, PartialEq, Clone)]
+pub struct ModuleDeclaration {
+    pub name: String,
+    pub functions: Vec<FunctionDeclaration>,
+}
+
+/// A `return` statement; `value` is `None` for a bare `return;`.
 #[derive(Debug, PartialEq, Clone)]
 pub struct Return {
-    pub value: Expression,
+    pub value: Option<Expression>,
 }
 
 #[derive(Debug, PartialEq, Clone)]
@@ -48,27 +68,31 @@ pub struct SwitchStatement {
 #[derive(Debug, PartialEq, Clone)]
 pub struct SwitchCase {
     pub value: Expression,
+    /// Upper bound of a range case (`case 1..5`), inclusive; `value` is then its lower bound.
+    pub range_end: Option<Expression>,
     pub body: Vec<Statement>,
 }
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct WhileStatement{
+    pub label: Option<String>,
     pub condition: Expression,
     pub body: Vec<Statement>,
 }
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct ForStatement {
-    pub init: Box<Statement>,
-    pub cond: Box<Statement>,
-    pub incr: Box<Statement>,
+    pub label: Option<String>,
+    pub init: Option<Box<Statement>>,
+    pub cond: Option<Box<Statement>>,
+    pub incr: Vec<Statement>,
     pub body: Vec<Statement>,
 }
 
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct FunctionDeclaration{
-    pub name: String,
+    pub name: Name,
     pub parameters: Vec<Parameter>,
     pub return_type: String,
     pub body: Vec<Statement>,
@@ -76,6 +100,6 @@ pub struct FunctionDeclaration{
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct Parameter {
-    pub name: String,
+    pub name: Name,
     pub type_name: String,
 }
@@ -1,29 +1,66 @@
-use crate::lex::lexer::{Lexer};
+use crate::lex::conditional::filter_conditionals;
+#[cfg(test)]
+use crate::lex::lexer::Lexer;
 use crate::lex::models::{token::Token, token_type::TokenType};
 use crate::parser::models::ast::AST;
 use super::statement_parser;
 
+/// 🛑 Returned by `peek` once the position runs past the last token.
+static EOF_TOKEN: Token = Token {
+    token_type: TokenType::EOF,
+    value: String::new(),
+    line: 0,
+    column: 0,
+    name: None,
+};
+
 /// 🚀 The `Parser` structure holds the token stream and the current reading position.
 /// It provides methods to navigate and check the token stream.
 pub struct Parser {
     pub tokens: Vec<Token>,
     pub position: usize,
+    pub errors: Vec<String>,
+    /// Line and column of each error in `errors`, in the same order.
+    pub error_locations: Vec<(usize, usize)>,
+    /// Nesting depth reached in the expression being parsed, counting nested sub-expressions
+    /// and operators, checked against `MAX_EXPRESSION_DEPTH`.
+    pub expression_depth: usize,
 }
 
 impl Parser {
-    /// 🔧 Creates a new `Parser` from a given vector of `Token`.
+    /// 🔧 Creates a new `Parser` over the tokens of `input`.
+    /// Shortcut for the tests: the driver lexes separately to time each phase.
+    #[cfg(test)]
     pub fn new(input: String) -> Self {
-        let mut lexer= Lexer::new(input);
-        let mut tokens = Vec::new();
+        Parser::from_tokens(Lexer::new(input).tokenize())
+    }
 
-        loop {
-            let token = lexer.next_token();
-            if token.token_type == TokenType::EOF {
-                break;
-            }
-            tokens.push(token);
+    /// 🧱 Creates a `Parser` from an already lexed token stream.
+    /// A final `EOF` token is appended when the stream does not end with one.
+    /// No flag is defined, so every `#if` block is left out.
+    #[cfg(test)]
+    pub fn from_tokens(tokens: Vec<Token>) -> Self {
+        Parser::with_defines(tokens, &[])
+    }
+
+    /// 🚩 Creates a `Parser` keeping the `#if NAME { ... }` blocks whose flag is in `defines`.
+    /// Malformed directives are recorded as parser errors.
+    pub fn with_defines(tokens: Vec<Token>, defines: &[String]) -> Self {
+        let (mut tokens, errors) = filter_conditionals(tokens, defines);
+        if tokens.last().map(|token| &token.token_type) != Some(&TokenType::EOF) {
+            tokens.push(EOF_TOKEN.clone());
+        }
+        let mut parser = Parser {
+            tokens,
+            position: 0,
+            errors: Vec::new(),
+            error_locations: Vec::new(),
+            expression_depth: 0,
+        };
+        for (message, location) in errors {
+            parser.error_at(&message, location);
         }
-        Parser { tokens, position: 0 }
+        parser
     }
 
     /// 🏁 Parses an entire file, producing an `AST` composed of multiple `Statement`s.
@@ -34,7 +71,10 @@ impl Parser {
             match statement_parser::parse_statement(self) {
                 Some(stmt) => statements.push(stmt),
                 None => {
-                    eprintln!("Error: could not parse statement. Stopping.");
+                    // Parsing stops at the first malformed statement
+                    if self.errors.is_empty() {
+                        self.error("Could not parse statement");
+                    }
                     break;
                 }
             }
@@ -45,19 +85,21 @@ impl Parser {
 
     /// ❓ Checks if we have reached the end of the tokens or encountered `EOF`.
     pub fn is_at_end(&self) -> bool {
-        self.position >= self.tokens.len()
-            || self.tokens[self.position].token_type == TokenType::EOF
+        self.peek().token_type == TokenType::EOF
     }
 
-    /// 👀 Retrieves the current token without consuming it.
+    /// 👀 Retrieves the current token without consuming it, or `EOF` past the end.
     pub fn peek(&self) -> &Token {
-        &self.tokens[self.position]
+        self.tokens.get(self.position).unwrap_or(&EOF_TOKEN)
     }
 
     /// ⏩ Consumes (advances past) the current token and returns it.
+    /// Past the end of the stream, `EOF` is returned and the position stays put.
     pub fn advance(&mut self) -> Token {
-        let token = self.tokens[self.position].clone();
-        self.position += 1;
+        let token = self.peek().clone();
+        if self.position < self.tokens.len() {
+            self.position += 1;
+        }
         token
     }
 
@@ -87,7 +129,7 @@ impl Parser {
         if self.check(ttype.clone()) {
             Some(self.advance())
         } else {
-            eprintln!("Parser error: {}", err_msg);
+            self.error(err_msg);
             None
         }
     }
@@ -106,8 +148,24 @@ impl Parser {
         if self.is_keyword(keyword) {
             Some(self.advance())
         } else {
-            eprintln!("Parser error: Expected keyword '{}'", keyword);
+            self.error(&format!("Expected keyword '{}'", keyword));
             None
         }
     }
+
+    /// 🚨 Records a syntax error at the current token; the analyzer reports it alongside
+    /// semantic errors.
+    pub fn error(&mut self, message: &str) {
+        let token = self.peek();
+        let location = (token.line, token.column);
+        self.error_at(message, location);
+    }
+
+    /// 🚨 Records a syntax error at the given line and column.
+    pub fn error_at(&mut self, message: &str, location: (usize, usize)) {
+        // A message ending with a question keeps its own punctuation
+        let end = if message.ends_with('?') { "" } else { "." };
+        self.errors.push(format!("Parser error: {}{}", message, end));
+        self.error_locations.push(location);
+    }
 }
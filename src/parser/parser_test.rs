@@ -0,0 +1,592 @@
+#[cfg(test)]
+mod parser_tests {
+    use crate::lex::models::token::Token;
+    use crate::lex::models::token_type::TokenType;
+    use crate::parser::models::ast::AST;
+    use crate::parser::models::expression::Expression;
+    use crate::parser::models::statement::Statement;
+    use crate::parser::models::statement::{FunctionDeclaration, Return};
+    use crate::parser::parser::Parser;
+    use crate::parser::visit::{walk_block, Visitor};
+
+    // Helper function to parse source code into an AST
+    fn parse_source(source: &str) -> AST {
+        let mut parser = Parser::new(source.to_string());
+        parser.parse_file()
+    }
+
+    #[test]
+    fn test_for_with_empty_clauses() {
+        let ast = parse_source("for (;;) {\n    break;\n};");
+        assert_eq!(ast.statements.len(), 1);
+        match &ast.statements[0] {
+            Statement::For(for_stmt) => {
+                assert!(for_stmt.init.is_none());
+                assert!(for_stmt.cond.is_none());
+                assert!(for_stmt.incr.is_empty());
+                assert_eq!(for_stmt.body, vec![Statement::Break(None)]);
+            }
+            other => panic!("Expected a for statement, got {:?}", other),
+        }
+
+        // Filled clauses keep working alongside empty ones
+        let ast = parse_source("for (let i: int = 0; ; i = i + 1;) { break; };");
+        match &ast.statements[0] {
+            Statement::For(for_stmt) => {
+                assert!(for_stmt.init.is_some());
+                assert!(for_stmt.cond.is_none());
+                assert_eq!(for_stmt.incr.len(), 1);
+            }
+            other => panic!("Expected a for statement, got {:?}", other),
+        }
+    }
+
+    // Helper function extracting the initializer of the first top-level declaration
+    fn first_init(ast: &AST) -> &Expression {
+        match &ast.statements[0] {
+            Statement::VarDeclaration(decl) => decl.init.as_ref().expect("Missing initializer"),
+            other => panic!("Expected a variable declaration, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_unary_with_nested_parentheses() {
+        let ast = parse_source("let a: bool = !(1 == 2);");
+        assert_eq!(first_init(&ast).to_string(), "!(1 == 2)");
+
+        let ast = parse_source("let b: int = -(-(3));");
+        assert_eq!(first_init(&ast).to_string(), "-(-3)");
+        let ast = parse_source("let e: int = -+3;");
+        assert_eq!(first_init(&ast).to_string(), "-(+3)");
+
+        let ast = parse_source("let c: int = -((1 + 2) * 3);");
+        assert_eq!(first_init(&ast).to_string(), "-((1 + 2) * 3)");
+
+        // Unary binds tighter than binary operators
+        let ast = parse_source("let d: int = -1 * 2;");
+        assert_eq!(first_init(&ast).to_string(), "(-1 * 2)");
+    }
+
+    // A visitor only interested in function declarations, relying on the default walk elsewhere
+    struct FunctionCounter {
+        count: usize,
+    }
+
+    impl Visitor for FunctionCounter {
+        fn visit_function_declaration(&mut self, func_decl: &FunctionDeclaration) {
+            self.count += 1;
+            walk_block(self, &func_decl.body);
+        }
+    }
+
+    #[test]
+    fn test_visitor_counts_function_declarations() {
+        let ast = parse_source(
+            "function a(): void {\n    print(1);\n}\n\
+             let x: int = 1;\n\
+             if (x == 1) {\n    x = 2;\n};\n\
+             function b(n: int): int {\n    return n;\n}",
+        );
+        let mut counter = FunctionCounter { count: 0 };
+        counter.visit_ast(&ast);
+        assert_eq!(counter.count, 2);
+    }
+
+    #[test]
+    fn test_return_statements_share_one_node() {
+        let ast = parse_source(
+            "function f(): void {\n    return;\n}\nfunction g(): int {\n    return 1 + 2;\n}",
+        );
+        let returns: Vec<&Statement> = ast
+            .statements
+            .iter()
+            .map(|stmt| match stmt {
+                Statement::FunctionDeclaration(func_decl) => &func_decl.body[0],
+                other => panic!("Expected a function declaration, got {:?}", other),
+            })
+            .collect();
+        assert_eq!(returns[0], &Statement::Return(Return { value: None }));
+        match returns[1] {
+            Statement::Return(Return { value: Some(expr) }) => {
+                assert_eq!(expr.to_string(), "(1 + 2)")
+            }
+            other => panic!("Expected a return with a value, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_logical_operator_precedence() {
+        let ast = parse_source("let a: bool = x || y && z == 1;");
+        assert_eq!(first_init(&ast).to_string(), "(x || (y && (z == 1)))");
+
+        let ast = parse_source("let b: bool = !x && y || z;");
+        assert_eq!(first_init(&ast).to_string(), "((!x && y) || z)");
+    }
+    #[test]
+    fn test_empty_and_comment_only_sources() {
+        for source in ["", "   \n\t\n", "// nothing to see here\n// still nothing"] {
+            let mut parser = Parser::new(source.to_string());
+            let ast = parser.parse_file();
+            assert!(ast.statements.is_empty(), "Unexpected statements for {:?}", source);
+            assert!(parser.errors.is_empty(), "Unexpected errors for {:?}: {:?}", source, parser.errors);
+        }
+    }
+    #[test]
+    fn test_token_stream_without_eof() {
+        let token = |token_type: TokenType, value: &str| Token::new(token_type, value.to_string());
+        let tokens = vec![
+            token(TokenType::Identifier, "print"),
+            token(TokenType::LeftParen, "("),
+            token(TokenType::Int, "1"),
+            token(TokenType::RightParen, ")"),
+            token(TokenType::Semicolon, ";"),
+        ];
+        let mut parser = Parser::from_tokens(tokens.clone());
+        assert_eq!(parser.tokens.last().unwrap().token_type, TokenType::EOF);
+        let ast = parser.parse_file();
+        assert_eq!(ast.statements.len(), 1);
+        assert!(parser.errors.is_empty(), "Unexpected errors: {:?}", parser.errors);
+
+        // A stream cut in the middle of a statement reports an error instead of panicking
+        let mut parser = Parser::from_tokens(tokens[..3].to_vec());
+        parser.parse_file();
+        assert!(!parser.errors.is_empty());
+
+        let mut parser = Parser::from_tokens(Vec::new());
+        assert!(parser.parse_file().statements.is_empty());
+        assert_eq!(parser.advance().token_type, TokenType::EOF);
+        assert_eq!(parser.peek().token_type, TokenType::EOF);
+    }
+    #[test]
+    fn test_module_and_qualified_call() {
+        let ast = parse_source(
+            "module math {\n    function double(x: int): int {\n        return x * 2;\n    }\n}\nprint(math::double(4));",
+        );
+        match &ast.statements[0] {
+            Statement::Module(module) => {
+                assert_eq!(module.name, "math");
+                assert_eq!(module.functions.len(), 1);
+                assert_eq!(module.functions[0].name, "math::double");
+            }
+            other => panic!("Expected a module, got {:?}", other),
+        }
+        match &ast.statements[1] {
+            Statement::ExpressionStatement(expr) => {
+                assert_eq!(expr.to_string(), "print(math::double(4))");
+            }
+            other => panic!("Expected an expression statement, got {:?}", other),
+        }
+
+        // Inside a module, an unqualified call to one of its functions is qualified; other
+        // calls are left to the global scope
+        let ast = parse_source(
+            "module math {\n    function double(x: int): int {\n        return x * 2;\n    }\n\
+             function quad(x: int): int {\n        return double(double(x)) + abs(x);\n    }\n}",
+        );
+        match &ast.statements[0] {
+            Statement::Module(module) => match &module.functions[1].body[0] {
+                Statement::Return(return_stmt) => assert_eq!(
+                    return_stmt.value.as_ref().map(|value| value.to_string()),
+                    Some("(math::double(math::double(x)) + abs(x))".to_string())
+                ),
+                other => panic!("Expected a return, got {:?}", other),
+            },
+            other => panic!("Expected a module, got {:?}", other),
+        }
+
+        let mut parser = Parser::new("let y: int = math::pi;".to_string());
+        parser.parse_file();
+        assert_eq!(
+            parser.errors,
+            vec!["Parser error: Expected '(' after qualified name 'math::pi'.".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_comparison_chain() {
+        let ast = parse_source("let a: bool = 1 < f(x) <= 3 + 1;");
+        match first_init(&ast) {
+            Expression::Comparison(chain) => {
+                // `f(x)` appears only once, so it is evaluated only once
+                assert_eq!(chain.operands.len(), 3);
+                assert_eq!(chain.operands[1].to_string(), "f(x)");
+                assert_eq!(chain.ops, vec!["<".to_string(), "<=".to_string()]);
+            }
+            other => panic!("Expected a comparison chain, got {:?}", other),
+        }
+        assert_eq!(first_init(&ast).to_string(), "(1 < f(x) <= (3 + 1))");
+
+        // A single comparison stays a binary expression
+        let ast = parse_source("let b: bool = a < b == c > d;");
+        assert_eq!(first_init(&ast).to_string(), "((a < b) == (c > d))");
+    }
+
+    #[test]
+    fn test_labeled_loops() {
+        let ast = parse_source("outer: while (true) {\n    for (;;) {\n        break outer;\n        continue;\n    };\n};");
+        match &ast.statements[0] {
+            Statement::While(while_stmt) => {
+                assert_eq!(while_stmt.label.as_deref(), Some("outer"));
+                match &while_stmt.body[0] {
+                    Statement::For(for_stmt) => {
+                        assert_eq!(for_stmt.label, None);
+                        assert_eq!(
+                            for_stmt.body,
+                            vec![Statement::Break(Some("outer".to_string())), Statement::Continue(None)]
+                        );
+                    }
+                    other => panic!("Expected a for statement, got {:?}", other),
+                }
+            }
+            other => panic!("Expected a while statement, got {:?}", other),
+        }
+
+        let mut parser = Parser::new("outer: print(1);".to_string());
+        parser.parse_file();
+        assert_eq!(
+            parser.errors[0],
+            "Parser error: Expected 'for' or 'while' after label 'outer'."
+        );
+    }
+
+    #[test]
+    fn test_block_statement() {
+        let ast = parse_source("let x: int = 1;\n{\n    let x: int = 2;\n    print(x);\n};\nprint(x);");
+        match &ast.statements[1] {
+            Statement::Block(body) => {
+                assert_eq!(body.len(), 2);
+                assert!(matches!(&body[0], Statement::VarDeclaration(var_decl) if var_decl.name == "x"));
+            }
+            other => panic!("Expected a block, got {:?}", other),
+        }
+        assert!(matches!(ast.statements[2], Statement::ExpressionStatement(_)));
+
+        let mut parser = Parser::new("{ print(1); }".to_string());
+        parser.parse_file();
+        assert_eq!(parser.errors[0], "Parser error: Expected ';' after block.");
+    }
+
+    #[test]
+    fn test_for_increment_with_commas() {
+        let ast = parse_source("for (let i: int = 0; i < j; i = i + 1, j = j - 1, tick();) { };");
+        match &ast.statements[0] {
+            Statement::For(for_stmt) => {
+                assert_eq!(for_stmt.incr.len(), 3);
+                assert!(matches!(&for_stmt.incr[0], Statement::VarAffection(a) if a.name == "i"));
+                assert!(matches!(&for_stmt.incr[1], Statement::VarAffection(a) if a.name == "j"));
+                assert!(matches!(&for_stmt.incr[2], Statement::ExpressionStatement(_)));
+            }
+            other => panic!("Expected a for statement, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_assignment_in_condition() {
+        for source in ["if (x = 5) {\n    print(x);\n};", "while (done = true) {\n};"] {
+            let mut parser = Parser::new(source.to_string());
+            parser.parse_file();
+            assert_eq!(
+                parser.errors[0],
+                "Parser error: Unexpected '=' in condition; did you mean '=='?",
+                "{}",
+                source
+            );
+        }
+        // `==` is still a comparison
+        let ast = parse_source("if (x == 5) {\n    print(x);\n};");
+        assert!(matches!(ast.statements[0], Statement::If(_)));
+    }
+
+    #[test]
+    fn test_unary_plus() {
+        let ast = parse_source("let a: int = +5;");
+        match first_init(&ast) {
+            Expression::Unary(unary_expr) => {
+                assert_eq!(unary_expr.op, "+");
+                assert_eq!(unary_expr.operand, Expression::Int(5));
+            }
+            other => panic!("Expected a unary expression, got {:?}", other),
+        }
+
+        let ast = parse_source("let b: float = +f * 2.0;");
+        assert_eq!(first_init(&ast).to_string(), "(+f * 2)");
+    }
+
+    #[test]
+    fn test_unary_binds_looser_than_postfix() {
+        // The index applies to `a`, then the minus to the element
+        let ast = parse_source("let a: int = -a[i];");
+        match first_init(&ast) {
+            Expression::Unary(unary_expr) => {
+                assert_eq!(unary_expr.op, "-");
+                assert!(matches!(unary_expr.operand, Expression::Index(_)));
+            }
+            other => panic!("Expected a unary expression, got {:?}", other),
+        }
+        assert_eq!(first_init(&ast).to_string(), "-a[i]");
+
+        // The call is negated before the multiplication
+        let ast = parse_source("let b: int = -f(x) * 2;");
+        match first_init(&ast) {
+            Expression::Binary(bin_expr) => {
+                assert_eq!(bin_expr.op, "*");
+                match &bin_expr.left {
+                    Expression::Unary(unary_expr) => {
+                        assert!(matches!(unary_expr.operand, Expression::FunctionCall(_)))
+                    }
+                    other => panic!("Expected a unary expression, got {:?}", other),
+                }
+            }
+            other => panic!("Expected a binary expression, got {:?}", other),
+        }
+
+        // Prefix operators stack on top of postfix ones
+        let ast = parse_source("let c: bool = !flags[i + 1];");
+        match first_init(&ast) {
+            Expression::Unary(unary_expr) => {
+                assert!(matches!(unary_expr.operand, Expression::Index(_)))
+            }
+            other => panic!("Expected a unary expression, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_power_operator() {
+        let ast = parse_source("let a: int = 2 ** 10;");
+        match first_init(&ast) {
+            Expression::Binary(bin_expr) => {
+                assert_eq!(bin_expr.op, "**");
+                assert_eq!(bin_expr.left, Expression::Int(2));
+                assert_eq!(bin_expr.right, Expression::Int(10));
+            }
+            other => panic!("Expected a binary expression, got {:?}", other),
+        }
+
+        // Right-associative, tighter than `*`, looser than unary minus
+        let ast = parse_source("let b: int = 2 ** 3 ** 2;");
+        assert_eq!(first_init(&ast).to_string(), "(2 ** (3 ** 2))");
+        let ast = parse_source("let c: int = 3 * 2 ** 2;");
+        assert_eq!(first_init(&ast).to_string(), "(3 * (2 ** 2))");
+        let ast = parse_source("let d: int = -2 ** 2;");
+        assert_eq!(first_init(&ast).to_string(), "(-2 ** 2)");
+        let ast = parse_source("let e: float = 2.0 ** 0.5;");
+        assert_eq!(first_init(&ast).to_string(), "(2 ** 0.5)");
+    }
+
+    #[test]
+    fn test_multiple_declarations() {
+        let ast = parse_source("let a: int = 1, b: float, c: string[2];");
+        match &ast.statements[0] {
+            Statement::VarDeclarations(declarations) => {
+                let names: Vec<&str> = declarations.iter().map(|d| d.name.as_str()).collect();
+                assert_eq!(names, vec!["a", "b", "c"]);
+                let types: Vec<&str> = declarations.iter().map(|d| d.type_name.as_str()).collect();
+                assert_eq!(types, vec!["int", "float", "string"]);
+                assert_eq!(declarations[0].init, Some(Expression::Int(1)));
+                assert!(declarations[1].init.is_none());
+                assert!(declarations[2].array_size.is_some());
+            }
+            other => panic!("Expected several declarations, got {:?}", other),
+        }
+
+        // `const` applies to every declarator, and a single one stays a plain declaration
+        let ast = parse_source("const N: int = 2, M: int = N * 3;\nlet x: int = 0;");
+        match &ast.statements[0] {
+            Statement::VarDeclarations(declarations) => {
+                assert!(declarations.iter().all(|d| d.is_const))
+            }
+            other => panic!("Expected several declarations, got {:?}", other),
+        }
+        assert!(matches!(ast.statements[1], Statement::VarDeclaration(_)));
+
+        let mut parser = Parser::new("for (let i: int = 0, j: int = 0; i < 3; i = i + 1;) { };".to_string());
+        parser.parse_file();
+        assert_eq!(
+            parser.errors[0],
+            "Parser error: Only one variable can be declared in a for clause."
+        );
+    }
+
+    #[test]
+    fn test_expression_nesting_limit() {
+        // 10,000 nested parentheses give an error instead of overflowing the stack
+        let source = format!("let x: int = {}1{};", "(".repeat(10_000), ")".repeat(10_000));
+        let mut parser = Parser::new(source);
+        let ast = parser.parse_file();
+        assert!(ast.statements.is_empty());
+        assert_eq!(parser.errors, vec!["Parser error: Expression nesting too deep.".to_string()]);
+
+        // Chained prefix and `**` operators count as nesting too
+        for source in [
+            format!("let b: bool = {}true;", "!".repeat(10_000)),
+            format!("let x: int = 2{};", " ** 1".repeat(10_000)),
+        ] {
+            let mut parser = Parser::new(source);
+            parser.parse_file();
+            assert_eq!(parser.errors, vec!["Parser error: Expression nesting too deep.".to_string()]);
+        }
+
+        // So does each term of a flat chain of binary operators, whose tree is as deep as it
+        // is long, including when the chains are nested in one another's first operand
+        for source in [
+            format!("let x: int = 1{};", " + 1".repeat(2_000)),
+            format!("let b: bool = true{};", " && true".repeat(2_000)),
+            format!("let x: int = {}1{};", "(".repeat(100), " + 1)".repeat(100)),
+            format!("let x: int = {}1{};", "f(".repeat(100), " + 1 + 1)".repeat(100)),
+        ] {
+            let mut parser = Parser::new(source);
+            parser.parse_file();
+            assert_eq!(parser.errors, vec!["Parser error: Expression nesting too deep.".to_string()]);
+        }
+
+        // Below the limit, a deep expression parses normally
+        let ast = parse_source(&format!("let x: int = {}1{};", "(".repeat(100), ")".repeat(100)));
+        match &ast.statements[0] {
+            Statement::VarDeclaration(decl) => assert_eq!(decl.init, Some(Expression::Int(1))),
+            other => panic!("Expected a variable declaration, got {:?}", other),
+        }
+        let mut parser = Parser::new(format!("let x: int = 1{};\nlet y: int = 1{};", " + 1".repeat(100), " - 1".repeat(100)));
+        parser.parse_file();
+        assert!(parser.errors.is_empty(), "Unexpected errors: {:?}", parser.errors);
+    }
+
+    #[test]
+    fn test_error_locations() {
+        let mut parser = Parser::new("let x: int = 1;\nprint(x)\nlet y: int = 2;".to_string());
+        parser.parse_file();
+        assert_eq!(parser.errors, vec!["Parser error: Expected ';' after expression statement.".to_string()]);
+        // The error points at the token where the `;` was expected
+        assert_eq!(parser.error_locations, vec![(3, 1)]);
+    }
+
+    #[test]
+    fn test_array_literal_initializer() {
+        let ast = parse_source("let xs: int[3] = [1, 2, 3];");
+        match &ast.statements[0] {
+            Statement::VarDeclaration(decl) => {
+                assert_eq!(decl.array_size, Some(Expression::Int(3)));
+                assert_eq!(
+                    decl.init,
+                    Some(Expression::ArrayLiteral(vec![
+                        Expression::Int(1),
+                        Expression::Int(2),
+                        Expression::Int(3)
+                    ]))
+                );
+            }
+            other => panic!("Expected a variable declaration, got {:?}", other),
+        }
+
+        // Elements are separated by exactly one comma
+        let mut parser = Parser::new("let xs: int[2] = [1 2];".to_string());
+        parser.parse_file();
+        assert_eq!(parser.errors, vec!["Parser error: Expected ',' between array elements.".to_string()]);
+    }
+
+    #[test]
+    fn test_commas_in_calls_and_signatures() {
+        // Parameters and arguments may span several lines, with a single trailing comma
+        let ast = parse_source(
+            "function add(\n    a: int,\n    b: int,\n): int {\n    return a + b;\n}\nprint(add(\n    1,\n    2,\n));",
+        );
+        match &ast.statements[0] {
+            Statement::FunctionDeclaration(func_decl) => {
+                let names: Vec<&str> = func_decl.parameters.iter().map(|p| p.name.as_str()).collect();
+                assert_eq!(names, vec!["a", "b"]);
+            }
+            other => panic!("Expected a function declaration, got {:?}", other),
+        }
+        match &ast.statements[1] {
+            Statement::ExpressionStatement(Expression::FunctionCall(print)) => {
+                assert_eq!(print.arguments[0].to_string(), "add(1, 2)");
+            }
+            other => panic!("Expected a call, got {:?}", other),
+        }
+        assert_eq!(parse_source("print(1,);").statements.len(), 1);
+
+        // An empty argument or parameter, between commas or before the first one, is an error
+        for (source, expected) in [
+            ("print(add(1,, 2));", "Parser error: Expected an argument before ','."),
+            ("print(add(, 2));", "Parser error: Expected an argument before ','."),
+            ("print(add(1, 2,,));", "Parser error: Expected an argument before ','."),
+            (
+                "function add(a: int,, b: int): int {\n    return a + b;\n}",
+                "Parser error: Expected a parameter before ','.",
+            ),
+            (
+                "function f(,): int {\n    return 1;\n}",
+                "Parser error: Expected a parameter before ','.",
+            ),
+        ] {
+            let mut parser = Parser::new(source.to_string());
+            parser.parse_file();
+            assert_eq!(parser.errors.first().map(String::as_str), Some(expected), "{}", source);
+        }
+    }
+
+    #[test]
+    fn test_comment_inside_binary_expression() {
+        let ast = parse_source("let x: int = 1 + // note\n    2 * // more\n    3;\nprint(x);");
+        assert_eq!(ast.statements.len(), 2);
+        match &ast.statements[0] {
+            Statement::VarDeclaration(decl) => {
+                assert_eq!(decl.init.as_ref().map(|init| init.to_string()), Some("(1 + (2 * 3))".to_string()));
+            }
+            other => panic!("Expected a variable declaration, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_adjacent_string_literals_are_merged() {
+        let ast = parse_source("let s: string = \"a\" \"b\";\nprint(\"Hello, \"\n    \"World\"\n    \"!\");");
+        match &ast.statements[0] {
+            Statement::VarDeclaration(decl) => assert_eq!(decl.init, Some(Expression::Str("ab".to_string()))),
+            other => panic!("Expected a variable declaration, got {:?}", other),
+        }
+        match &ast.statements[1] {
+            Statement::ExpressionStatement(Expression::FunctionCall(call)) => {
+                assert_eq!(call.arguments, vec![Expression::Str("Hello, World!".to_string())]);
+            }
+            other => panic!("Expected a call, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_switch_range_case() {
+        let ast = parse_source("switch (n) {\n    case -5..-1 {\n        print(1);\n    },\n    case 3 {\n        print(2);\n    },\n    case 6..TOP {\n        print(3);\n    },\n};");
+        match &ast.statements[0] {
+            Statement::Switch(switch_stmt) => {
+                let cases: Vec<(String, Option<String>)> = switch_stmt
+                    .cases
+                    .iter()
+                    .map(|case| (case.value.to_string(), case.range_end.as_ref().map(|end| end.to_string())))
+                    .collect();
+                assert_eq!(
+                    cases,
+                    vec![
+                        ("-5".to_string(), Some("-1".to_string())),
+                        ("3".to_string(), None),
+                        ("6".to_string(), Some("TOP".to_string())),
+                    ]
+                );
+            }
+            other => panic!("Expected a switch statement, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_float_literal_underflow() {
+        // 1e-400 written out: below the smallest 64-bit float, it would round to 0
+        let literal = format!("0.{}1", "0".repeat(399));
+        let mut parser = Parser::new(format!("let tiny: float = {};", literal));
+        parser.parse_file();
+        let expected = format!("Parser error: Float literal '{}' is too small for a 64-bit float.", literal);
+        assert_eq!(parser.errors.first(), Some(&expected));
+
+        // Zero itself and the smallest subnormal float are fine
+        let source = format!("let zero: float = 0.000;\nlet small: float = 0.{}5;", "0".repeat(323));
+        let mut parser = Parser::new(source);
+        parser.parse_file();
+        assert!(parser.errors.is_empty(), "Unexpected errors: {:?}", parser.errors);
+    }
+}
\ No newline at end of file
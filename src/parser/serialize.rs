@@ -0,0 +1,200 @@
+use crate::parser::models::ast::AST;
+use crate::parser::models::expression::Expression;
+use crate::parser::models::statement::{
+    FunctionDeclaration, Statement, SwitchCase, VarDeclaration,
+};
+
+/// 🗂️ Serializes the AST to compact JSON, for external tools (`--emit-ast-json`).
+///
+/// Every statement and expression is an object whose `kind` field names the node,
+/// followed by its fields in declaration order. Absent optional fields are `null`.
+pub fn ast_to_json(ast: &AST) -> String {
+    object(&[("statements", statements(&ast.statements))])
+}
+
+fn statements(statements: &[Statement]) -> String {
+    array(statements.iter().map(statement))
+}
+
+/// 📜 Serializes one statement.
+fn statement(stmt: &Statement) -> String {
+    match stmt {
+        Statement::VarDeclaration(var_decl) => var_declaration(var_decl),
+        Statement::VarDeclarations(declarations) => object(&[
+            ("kind", string("VarDeclarations")),
+            ("declarations", array(declarations.iter().map(var_declaration))),
+        ]),
+        Statement::VarAffection(var_affection) => object(&[
+            ("kind", string("VarAffection")),
+            ("name", string(&var_affection.name)),
+            ("index", optional(var_affection.index.as_ref(), expression)),
+            ("value", expression(&var_affection.value)),
+        ]),
+        Statement::Return(return_stmt) => object(&[
+            ("kind", string("Return")),
+            ("value", optional(return_stmt.value.as_ref(), expression)),
+        ]),
+        Statement::If(if_stmt) => object(&[
+            ("kind", string("If")),
+            ("condition", expression(&if_stmt.condition)),
+            ("then_branch", statements(&if_stmt.then_branch)),
+            ("else_branch", optional(if_stmt.else_branch.as_ref(), |body| statements(body))),
+        ]),
+        Statement::Switch(switch_stmt) => object(&[
+            ("kind", string("Switch")),
+            ("condition", expression(&switch_stmt.condition)),
+            ("cases", array(switch_stmt.cases.iter().map(switch_case))),
+            ("default", optional(switch_stmt.default.as_ref(), |body| statements(body))),
+        ]),
+        Statement::While(while_stmt) => object(&[
+            ("kind", string("While")),
+            ("label", optional(while_stmt.label.as_ref(), |label| string(label))),
+            ("condition", expression(&while_stmt.condition)),
+            ("body", statements(&while_stmt.body)),
+        ]),
+        Statement::For(for_stmt) => object(&[
+            ("kind", string("For")),
+            ("label", optional(for_stmt.label.as_ref(), |label| string(label))),
+            ("init", optional(for_stmt.init.as_deref(), statement)),
+            ("cond", optional(for_stmt.cond.as_deref(), statement)),
+            ("incr", statements(&for_stmt.incr)),
+            ("body", statements(&for_stmt.body)),
+        ]),
+        Statement::FunctionDeclaration(func_decl) => function_declaration(func_decl),
+        Statement::Module(module) => object(&[
+            ("kind", string("Module")),
+            ("name", string(&module.name)),
+            ("functions", array(module.functions.iter().map(function_declaration))),
+        ]),
+        Statement::ExpressionStatement(expr) => object(&[
+            ("kind", string("ExpressionStatement")),
+            ("expression", expression(expr)),
+        ]),
+        Statement::Break(label) => object(&[
+            ("kind", string("Break")),
+            ("label", optional(label.as_ref(), |label| string(label))),
+        ]),
+        Statement::Continue(label) => object(&[
+            ("kind", string("Continue")),
+            ("label", optional(label.as_ref(), |label| string(label))),
+        ]),
+        Statement::Block(body) => object(&[("kind", string("Block")), ("body", statements(body))]),
+    }
+}
+
+fn var_declaration(var_decl: &VarDeclaration) -> String {
+    object(&[
+        ("kind", string("VarDeclaration")),
+        ("name", string(&var_decl.name)),
+        ("type", string(&var_decl.type_name)),
+        ("array_size", optional(var_decl.array_size.as_ref(), expression)),
+        ("is_const", var_decl.is_const.to_string()),
+        ("init", optional(var_decl.init.as_ref(), expression)),
+    ])
+}
+
+fn function_declaration(func_decl: &FunctionDeclaration) -> String {
+    object(&[
+        ("kind", string("FunctionDeclaration")),
+        ("name", string(&func_decl.name)),
+        (
+            "parameters",
+            array(func_decl.parameters.iter().map(|param| {
+                object(&[("name", string(&param.name)), ("type", string(&param.type_name))])
+            })),
+        ),
+        ("return_type", string(&func_decl.return_type)),
+        ("body", statements(&func_decl.body)),
+    ])
+}
+
+fn switch_case(case: &SwitchCase) -> String {
+    object(&[
+        ("value", expression(&case.value)),
+        ("range_end", optional(case.range_end.as_ref(), expression)),
+        ("body", statements(&case.body)),
+    ])
+}
+
+/// 🧮 Serializes one expression.
+fn expression(expr: &Expression) -> String {
+    match expr {
+        Expression::Ident(name) => object(&[("kind", string("Ident")), ("name", string(name))]),
+        Expression::Int(value) => object(&[("kind", string("Int")), ("value", value.to_string())]),
+        // JSON has no infinity or NaN: such a literal is written as `null`
+        Expression::Float(value) => object(&[
+            ("kind", string("Float")),
+            ("value", if value.is_finite() { format!("{:?}", value) } else { "null".to_string() }),
+        ]),
+        Expression::Str(value) => object(&[("kind", string("Str")), ("value", string(value))]),
+        Expression::Bool(value) => object(&[("kind", string("Bool")), ("value", value.to_string())]),
+        Expression::Binary(bin_expr) => object(&[
+            ("kind", string("Binary")),
+            ("op", string(&bin_expr.op)),
+            ("left", expression(&bin_expr.left)),
+            ("right", expression(&bin_expr.right)),
+        ]),
+        Expression::Unary(unary_expr) => object(&[
+            ("kind", string("Unary")),
+            ("op", string(&unary_expr.op)),
+            ("operand", expression(&unary_expr.operand)),
+        ]),
+        Expression::FunctionCall(call) => object(&[
+            ("kind", string("FunctionCall")),
+            ("name", string(&call.name)),
+            ("arguments", array(call.arguments.iter().map(expression))),
+        ]),
+        Expression::Index(index_expr) => object(&[
+            ("kind", string("Index")),
+            ("target", expression(&index_expr.target)),
+            ("index", expression(&index_expr.index)),
+        ]),
+        Expression::Comparison(chain) => object(&[
+            ("kind", string("Comparison")),
+            ("operands", array(chain.operands.iter().map(expression))),
+            ("ops", array(chain.ops.iter().map(|op| string(op)))),
+        ]),
+        Expression::ArrayLiteral(elements) => object(&[
+            ("kind", string("ArrayLiteral")),
+            ("elements", array(elements.iter().map(expression))),
+        ]),
+    }
+}
+
+/// 🧱 Writes an object from already serialized field values.
+fn object(fields: &[(&str, String)]) -> String {
+    let fields: Vec<String> = fields
+        .iter()
+        .map(|(name, value)| format!("{}:{}", string(name), value))
+        .collect();
+    format!("{{{}}}", fields.join(","))
+}
+
+/// 🧱 Writes an array from already serialized items.
+fn array(items: impl Iterator<Item = String>) -> String {
+    format!("[{}]", items.collect::<Vec<_>>().join(","))
+}
+
+/// 🧱 Writes an optional value, or `null` when it is absent.
+fn optional<T: ?Sized>(value: Option<&T>, serialize: impl Fn(&T) -> String) -> String {
+    value.map_or_else(|| "null".to_string(), serialize)
+}
+
+/// 🔤 Writes a JSON string literal, escaping quotes, backslashes and control characters.
+fn string(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len() + 2);
+    escaped.push('"');
+    for ch in value.chars() {
+        match ch {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            ch if (ch as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", ch as u32)),
+            ch => escaped.push(ch),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
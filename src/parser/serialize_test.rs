@@ -0,0 +1,50 @@
+#[cfg(test)]
+mod serialize_tests {
+    use crate::parser::parser::Parser;
+    use crate::parser::serialize::ast_to_json;
+
+    // Helper function parsing source code and serializing its AST
+    fn json_of(source: &str) -> String {
+        let mut parser = Parser::new(source.to_string());
+        let ast = parser.parse_file();
+        assert!(parser.errors.is_empty(), "Unexpected parser errors: {:?}", parser.errors);
+        ast_to_json(&ast)
+    }
+
+    #[test]
+    fn test_small_program() {
+        let json = json_of("let x: int = 1 + 2;\nif (x > 2) {\n    print(\"big\");\n};");
+        assert_eq!(
+            json,
+            concat!(
+                r#"{"statements":["#,
+                r#"{"kind":"VarDeclaration","name":"x","type":"int","array_size":null,"is_const":false,"#,
+                r#""init":{"kind":"Binary","op":"+","left":{"kind":"Int","value":1},"right":{"kind":"Int","value":2}}},"#,
+                r#"{"kind":"If","condition":{"kind":"Binary","op":">","left":{"kind":"Ident","name":"x"},"right":{"kind":"Int","value":2}},"#,
+                r#""then_branch":[{"kind":"ExpressionStatement","expression":{"kind":"FunctionCall","name":"print","arguments":[{"kind":"Str","value":"big"}]}}],"#,
+                r#""else_branch":null}"#,
+                r#"]}"#
+            )
+        );
+    }
+
+    #[test]
+    fn test_strings_and_floats_are_escaped() {
+        let json = json_of("let s: string = 'say \"hi\"\\';\nlet f: float = 2.0;");
+        assert!(json.contains(r#""value":"say \"hi\"\\""#));
+        assert!(json.contains(r#"{"kind":"Float","value":2.0}"#));
+    }
+
+    #[test]
+    fn test_functions_and_loops() {
+        let json = json_of(
+            "function id(n: int): int {\n    return n;\n}\nouter: for (;;) {\n    break outer;\n};",
+        );
+        assert!(json.contains(
+            r#"{"kind":"FunctionDeclaration","name":"id","parameters":[{"name":"n","type":"int"}],"return_type":"int","body":[{"kind":"Return","value":{"kind":"Ident","name":"n"}}]}"#
+        ));
+        assert!(json.contains(
+            r#"{"kind":"For","label":"outer","init":null,"cond":null,"incr":[],"body":[{"kind":"Break","label":"outer"}]}"#
+        ));
+    }
+}
\ No newline at end of file
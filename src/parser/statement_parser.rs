@@ -1,72 +1,126 @@
 use super::parser::Parser;
+use crate::lex::models::name::Name;
 use crate::lex::models::token_type::TokenType;
 use crate::parser::expression_parser::parse_expression;
+use crate::parser::models::expression::Expression;
 use crate::parser::models::statement::{
-    ForStatement, FunctionDeclaration, IfStatement, Parameter, SwitchCase, SwitchStatement,
-    VarAffection, WhileStatement,
+    ForStatement, FunctionDeclaration, IfStatement, ModuleDeclaration, Parameter, Return,
+    SwitchCase, SwitchStatement, VarAffection, WhileStatement,
 };
 use crate::parser::models::statement::{Statement, VarDeclaration};
 
 /// Parses a single statement (variable declaration, return, etc.).
 pub fn parse_statement(parser: &mut Parser) -> Option<Statement> {
-    if parser.is_keyword("let") {
-
-        parse_var_decl(parser).map(Statement::VarDeclaration)
+    if parser.is_keyword("let") || parser.is_keyword("const") {
+        let mut declarations = parse_var_decl(parser)?;
+        if declarations.len() == 1 {
+            declarations.pop().map(Statement::VarDeclaration)
+        } else {
+            Some(Statement::VarDeclarations(declarations))
+        }
     } else if parser.is_keyword("return") {
 
         parse_return_stmt(parser)
+    } else if is_loop_label(parser) {
+        parse_labeled_loop(parser)
+
     } else if is_var_affection(parser) {
         parse_var_affection(parser).map(Statement::VarAffection)
 
     } else if parser.is_keyword("if") {
         let if_stmt = parse_if_stmt(parser)?;
         parser.consume(TokenType::Semicolon, "Expected ';' after if statement")?;
-        return Some(Statement::If(if_stmt));
+        Some(Statement::If(if_stmt))
 
     } else if parser.is_keyword("switch") {
         let switch_stmt = parse_switch_stmt(parser)?;
         parser.consume(TokenType::Semicolon, "Expected ';' after switch statement")?;
-        return Some(Statement::Switch(switch_stmt));
+        Some(Statement::Switch(switch_stmt))
 
     } else if parser.is_keyword("while") {
         let while_stmt = parse_while_stmt(parser)?;
         parser.consume(TokenType::Semicolon, "Expected ';' after while statement")?;
-        return Some(Statement::While(while_stmt));
+        Some(Statement::While(while_stmt))
 
     } else if parser.is_keyword("for") {
         let for_stmt = parse_for_stmt(parser)?;
         parser.consume(TokenType::Semicolon, "Expected ';' after for statement")?;
-        return Some(Statement::For(for_stmt));
+        Some(Statement::For(for_stmt))
+
+    } else if parser.is_keyword("break") {
+        parser.advance();
+        let label = parse_jump_label(parser);
+        parser.consume(TokenType::Semicolon, "Expected ';' after break")?;
+        Some(Statement::Break(label))
+
+    } else if parser.is_keyword("continue") {
+        parser.advance();
+        let label = parse_jump_label(parser);
+        parser.consume(TokenType::Semicolon, "Expected ';' after continue")?;
+        Some(Statement::Continue(label))
+
+    } else if parser.check(TokenType::LeftBracket) {
+        parser.advance();
+        let body = parse_block_like(parser)?;
+        parser.consume(TokenType::RightBracket, "Expected '}' after block")?;
+        parser.consume(TokenType::Semicolon, "Expected ';' after block")?;
+        Some(Statement::Block(body))
 
     } else if parser.is_keyword("function") {
         parser_function_decl(parser).map(Statement::FunctionDeclaration)
 
+    } else if parser.is_keyword("module") {
+        parse_module_decl(parser).map(Statement::Module)
+
     } else if parser.check(TokenType::Identifier) {
         if let Some(expr) = parse_expression(parser) {
             parser.consume(
                 TokenType::Semicolon,
                 "Expected ';' after expression statement",
             )?;
-            return Some(Statement::ExpressionStatement(expr));
+            Some(Statement::ExpressionStatement(expr))
 
         } else {
-            eprintln!("Could not parse expression statement");
-            return None;
+            None
         }
 
     } else {
-        eprintln!("Parser warning: unexpected token: {:?}", parser.peek());
+        let message = format!("Unexpected token '{}'", parser.peek().value);
+        parser.error(&message);
         parser.advance();
         None
     }
 }
 
-/// Parses a variable declaration of the form `let x: type = expr;`.
-fn parse_var_decl(parser: &mut Parser) -> Option<VarDeclaration> {
-    parser.consume_keyword("let")?;
+/// Parses a variable declaration: `let` or `const` followed by one or more comma-separated
+/// declarators such as `x: int = 1` or `xs: int[size]`, e.g. `let a: int = 1, b: float;`.
+/// Each declarator has its own type; `const` applies to all of them.
+fn parse_var_decl(parser: &mut Parser) -> Option<Vec<VarDeclaration>> {
+    let is_const = parser.is_keyword("const");
+    parser.consume_keyword(if is_const { "const" } else { "let" })?;
+
+    let mut declarations = vec![parse_declarator(parser, is_const)?];
+    while parser.check(TokenType::Comma) {
+        parser.advance();
+        declarations.push(parse_declarator(parser, is_const)?);
+    }
+
+    parser.consume(
+        TokenType::Semicolon,
+        "Expected ';' at the end of variable declaration",
+    )?;
+    Some(declarations)
+}
 
-    let name_token = parser.consume(TokenType::Identifier, "Expected identifier after 'let'")?;
-    let name = name_token.value;
+/// Parses one declarator: `name: type`, an optional `[size]` and an optional `= value`.
+fn parse_declarator(parser: &mut Parser, is_const: bool) -> Option<VarDeclaration> {
+    // Reserved words (`true`, type names) are accepted here so the analyzer can report them
+    let name_token = if parser.check(TokenType::Bool) || parser.check(TokenType::Type) {
+        parser.advance()
+    } else {
+        parser.consume(TokenType::Identifier, "Expected identifier after 'let'")?
+    };
+    let name = name_token.interned_name();
 
     parser.consume(TokenType::Colon, "Expected ':' after identifier")?;
 
@@ -76,20 +130,24 @@ fn parse_var_decl(parser: &mut Parser) -> Option<VarDeclaration> {
     )?;
     let type_name = type_token.value;
 
+    let mut array_size = None;
+    if parser.check(TokenType::LeftSquareBracket) {
+        parser.advance();
+        array_size = Some(parse_expression(parser)?);
+        parser.consume(TokenType::RightSquareBracket, "Expected ']' after array size")?;
+    }
+
     let mut init = None;
     if parser.check(TokenType::Equals) {
         parser.advance();
-        init = parse_expression(parser);
+        init = Some(parse_expression(parser)?);
     }
 
-    parser.consume(
-        TokenType::Semicolon,
-        "Expected ';' at the end of variable declaration",
-    )?;
-
     Some(VarDeclaration {
         name,
         type_name,
+        array_size,
+        is_const,
         init,
     })
 }
@@ -106,27 +164,40 @@ fn parse_return_stmt(parser: &mut Parser) -> Option<Statement> {
 
     parser.consume(TokenType::Semicolon, "Expected ';' after return")?;
 
-    Some(Statement::Return(expr))
+    Some(Statement::Return(Return { value: expr }))
 }
 
 fn parse_var_affection(parser: &mut Parser) -> Option<VarAffection> {
+    let var_affection = parse_affection_body(parser)?;
+    parser.consume(
+        TokenType::Semicolon,
+        "Expected ';' at the end of variable affection",
+    )?;
+    Some(var_affection)
+}
+
+/// Parses an affection such as `xs[i] = value`, without the trailing `;`.
+fn parse_affection_body(parser: &mut Parser) -> Option<VarAffection> {
     let name_token = parser.consume(
         TokenType::Identifier,
         "Expected identifier for variable affection",
     )?;
-    let name = name_token.value;
+    let name = name_token.interned_name();
+
+    let mut index = None;
+    if parser.check(TokenType::LeftSquareBracket) {
+        parser.advance();
+        index = Some(parse_expression(parser)?);
+        parser.consume(TokenType::RightSquareBracket, "Expected ']' after index")?;
+    }
 
     parser.consume(TokenType::Equals, "Expected '=' in variable affection")?;
 
     let value_expr = parse_expression(parser)?;
 
-    parser.consume(
-        TokenType::Semicolon,
-        "Expected ';' at the end of variable affection",
-    )?;
-
     Some(VarAffection {
         name,
+        index,
         value: value_expr,
     })
 }
@@ -134,7 +205,7 @@ fn parse_var_affection(parser: &mut Parser) -> Option<VarAffection> {
 pub fn parse_if_stmt(parser: &mut Parser) -> Option<IfStatement> {
     parser.consume_keyword("if")?;
     parser.consume(TokenType::LeftParen, "Expected '(' after 'if'")?;
-    let condition = parse_expression(parser)?;
+    let condition = parse_condition(parser)?;
 
     parser.consume(TokenType::RightParen, "Expected ')' after condition")?;
     parser.consume(TokenType::LeftBracket, "Expected '{' after if condition")?;
@@ -173,13 +244,19 @@ fn parse_switch_stmt(parser: &mut Parser) -> Option<SwitchStatement> {
         if parser.is_keyword("case") {
             parser.advance();
             let value = parse_expression(parser)?;
+            let range_end = if parser.check(TokenType::Range) {
+                parser.advance();
+                Some(parse_expression(parser)?)
+            } else {
+                None
+            };
             parser.consume(TokenType::LeftBracket, "Expected '{' after case expression")?;
             let body = parse_block_like(parser)?;
             parser.consume(
                 TokenType::RightBracket,
                 "Expected '}' at the end of case block",
             )?;
-            cases.push(SwitchCase { value, body });
+            cases.push(SwitchCase { value, range_end, body });
 
             if parser.check(TokenType::Comma) {
                 parser.advance();
@@ -198,10 +275,8 @@ fn parse_switch_stmt(parser: &mut Parser) -> Option<SwitchStatement> {
                 parser.advance();
             }
         } else {
-            eprintln!(
-                "Error while parsing switch statement: unexpected token: {:?}",
-                parser.peek()
-            );
+            let message = format!("Unexpected token '{}' in switch statement", parser.peek().value);
+            parser.error(&message);
             parser.advance();
             break;
         }
@@ -215,10 +290,21 @@ fn parse_switch_stmt(parser: &mut Parser) -> Option<SwitchStatement> {
     })
 }
 
+/// Parses the condition of an `if` or a `while`.
+/// ⚠️ A `=` right after it is almost always a mistyped `==`, and is reported as such.
+fn parse_condition(parser: &mut Parser) -> Option<Expression> {
+    let condition = parse_expression(parser)?;
+    if parser.check(TokenType::Equals) {
+        parser.error("Unexpected '=' in condition; did you mean '=='?");
+        return None;
+    }
+    Some(condition)
+}
+
 fn parse_while_stmt(parser: &mut Parser) -> Option<WhileStatement> {
     parser.consume_keyword("while")?;
     parser.consume(TokenType::LeftParen, "Expected '(' after 'while'")?;
-    let condition = parse_expression(parser)?;
+    let condition = parse_condition(parser)?;
     parser.consume(TokenType::RightParen, "Expected ')' after while condition")?;
     parser.consume(TokenType::LeftBracket, "Expected '{' after while(...)")?;
 
@@ -226,16 +312,25 @@ fn parse_while_stmt(parser: &mut Parser) -> Option<WhileStatement> {
 
     parser.consume(TokenType::RightBracket, "Expected '}' after while block")?;
 
-    Some(WhileStatement { condition, body })
+    Some(WhileStatement {
+        label: None,
+        condition,
+        body,
+    })
 }
 
 pub fn parse_for_stmt(parser: &mut Parser) -> Option<ForStatement> {
     parser.consume_keyword("for")?;
     parser.consume(TokenType::LeftParen, "Expected '(' after 'for'")?;
 
-    let init_stmt = parse_statement(parser)?;
-    let cond_stmt = parse_statement(parser)?;
-    let incr_stmt = parse_statement(parser)?;
+    // Each clause may be left empty: `for (;;)` loops forever
+    let init_stmt = parse_for_clause(parser)?;
+    let cond_stmt = parse_for_clause(parser)?;
+    let incr_stmts = if parser.check(TokenType::RightParen) {
+        Vec::new()
+    } else {
+        parse_for_increment(parser)?
+    };
 
     parser.consume(TokenType::RightParen, "Expected ')' after for(...)")?;
     parser.consume(TokenType::LeftBracket, "Expected '{' after for(...)")?;
@@ -243,32 +338,242 @@ pub fn parse_for_stmt(parser: &mut Parser) -> Option<ForStatement> {
     parser.consume(TokenType::RightBracket, "Expected '}' after for block")?;
 
     Some(ForStatement {
-        init: Box::new(init_stmt),
-        cond: Box::new(cond_stmt),
-        incr: Box::new(incr_stmt),
+        label: None,
+        init: init_stmt.map(Box::new),
+        cond: cond_stmt.map(Box::new),
+        incr: incr_stmts,
         body: body_statements,
     })
 }
 
+/// Parses a loop preceded by a label, such as `outer: for (...) { ... };`.
+fn parse_labeled_loop(parser: &mut Parser) -> Option<Statement> {
+    let label = parser.advance().value;
+    parser.consume(TokenType::Colon, "Expected ':' after loop label")?;
+    if parser.is_keyword("for") {
+        let mut for_stmt = parse_for_stmt(parser)?;
+        parser.consume(TokenType::Semicolon, "Expected ';' after for statement")?;
+        for_stmt.label = Some(label);
+        Some(Statement::For(for_stmt))
+    } else if parser.is_keyword("while") {
+        let mut while_stmt = parse_while_stmt(parser)?;
+        parser.consume(TokenType::Semicolon, "Expected ';' after while statement")?;
+        while_stmt.label = Some(label);
+        Some(Statement::While(while_stmt))
+    } else {
+        parser.error(&format!("Expected 'for' or 'while' after label '{}'", label));
+        None
+    }
+}
+
+/// Parses the optional loop label after `break` or `continue`.
+fn parse_jump_label(parser: &mut Parser) -> Option<String> {
+    if parser.check(TokenType::Identifier) {
+        Some(parser.advance().value)
+    } else {
+        None
+    }
+}
+
+/// Parses one clause of a `for` header. An empty clause is a lone `;` and yields `None`.
+fn parse_for_clause(parser: &mut Parser) -> Option<Option<Statement>> {
+    if parser.check(TokenType::Semicolon) {
+        parser.advance();
+        return Some(None);
+    }
+    let clause = parse_statement(parser)?;
+    if let Statement::VarDeclarations(_) = clause {
+        parser.error("Only one variable can be declared in a for clause");
+        return None;
+    }
+    Some(Some(clause))
+}
+
+/// Parses the increment clause of a `for`: one or more comma-separated affections or
+/// expressions, ended by `;`, such as `i = i + 1, j = j - 1;`.
+fn parse_for_increment(parser: &mut Parser) -> Option<Vec<Statement>> {
+    let mut statements = Vec::new();
+    loop {
+        if is_var_affection(parser) {
+            statements.push(Statement::VarAffection(parse_affection_body(parser)?));
+        } else {
+            statements.push(Statement::ExpressionStatement(parse_expression(parser)?));
+        }
+        if !parser.check(TokenType::Comma) {
+            break;
+        }
+        parser.advance();
+    }
+    parser.consume(TokenType::Semicolon, "Expected ';' after for increment")?;
+    Some(statements)
+}
+
+/// Parses a module of the form `module name { function ... }`.
+/// Each function name is qualified with the module name (`name::function`), and so is every
+/// unqualified call from inside the module to one of its functions: the module's own
+/// functions are looked up before the global ones.
+fn parse_module_decl(parser: &mut Parser) -> Option<ModuleDeclaration> {
+    parser.consume_keyword("module")?;
+    let name = parser.consume(TokenType::Identifier, "Expected module name")?.value;
+    parser.consume(TokenType::LeftBracket, "Expected '{' after module name")?;
+    let mut functions = Vec::new();
+    while !parser.check(TokenType::RightBracket) && !parser.is_at_end() {
+        if !parser.is_keyword("function") {
+            parser.error("Only functions can be declared inside a module");
+            return None;
+        }
+        functions.push(parser_function_decl(parser)?);
+    }
+    parser.consume(TokenType::RightBracket, "Expected '}' after module body")?;
+
+    // The calls are qualified once every function of the module is known
+    let members: Vec<Name> = functions.iter().map(|f| f.name).collect();
+    for func_decl in &mut functions {
+        func_decl.name = Name::intern(&format!("{}::{}", name, func_decl.name));
+        qualify_calls_in_statements(&mut func_decl.body, &name, &members);
+    }
+    Some(ModuleDeclaration { name, functions })
+}
+
+/// Qualifies the calls to the functions of `module` (`members`) in a block of statements.
+fn qualify_calls_in_statements(statements: &mut [Statement], module: &str, members: &[Name]) {
+    for stmt in statements {
+        qualify_calls_in_statement(stmt, module, members);
+    }
+}
+
+fn qualify_calls_in_statement(stmt: &mut Statement, module: &str, members: &[Name]) {
+    match stmt {
+        Statement::VarDeclaration(var_decl) => qualify_calls_in_declaration(var_decl, module, members),
+        Statement::VarDeclarations(declarations) => {
+            for var_decl in declarations {
+                qualify_calls_in_declaration(var_decl, module, members);
+            }
+        }
+        Statement::VarAffection(var_affection) => {
+            if let Some(index) = &mut var_affection.index {
+                qualify_calls(index, module, members);
+            }
+            qualify_calls(&mut var_affection.value, module, members);
+        }
+        Statement::Return(return_stmt) => {
+            if let Some(value) = &mut return_stmt.value {
+                qualify_calls(value, module, members);
+            }
+        }
+        Statement::If(if_stmt) => {
+            qualify_calls(&mut if_stmt.condition, module, members);
+            qualify_calls_in_statements(&mut if_stmt.then_branch, module, members);
+            if let Some(else_branch) = &mut if_stmt.else_branch {
+                qualify_calls_in_statements(else_branch, module, members);
+            }
+        }
+        Statement::Switch(switch_stmt) => {
+            qualify_calls(&mut switch_stmt.condition, module, members);
+            for case in &mut switch_stmt.cases {
+                qualify_calls_in_statements(&mut case.body, module, members);
+            }
+            if let Some(default) = &mut switch_stmt.default {
+                qualify_calls_in_statements(default, module, members);
+            }
+        }
+        Statement::While(while_stmt) => {
+            qualify_calls(&mut while_stmt.condition, module, members);
+            qualify_calls_in_statements(&mut while_stmt.body, module, members);
+        }
+        Statement::For(for_stmt) => {
+            if let Some(init) = &mut for_stmt.init {
+                qualify_calls_in_statement(init, module, members);
+            }
+            if let Some(cond) = &mut for_stmt.cond {
+                qualify_calls_in_statement(cond, module, members);
+            }
+            qualify_calls_in_statements(&mut for_stmt.incr, module, members);
+            qualify_calls_in_statements(&mut for_stmt.body, module, members);
+        }
+        Statement::ExpressionStatement(expr) => qualify_calls(expr, module, members),
+        Statement::Block(body) => qualify_calls_in_statements(body, module, members),
+        // Only functions are declared in a module, and never inside a function body
+        Statement::FunctionDeclaration(_)
+        | Statement::Module(_)
+        | Statement::Break(_)
+        | Statement::Continue(_) => {}
+    }
+}
+
+fn qualify_calls_in_declaration(var_decl: &mut VarDeclaration, module: &str, members: &[Name]) {
+    if let Some(array_size) = &mut var_decl.array_size {
+        qualify_calls(array_size, module, members);
+    }
+    if let Some(init) = &mut var_decl.init {
+        qualify_calls(init, module, members);
+    }
+}
+
+fn qualify_calls(expr: &mut Expression, module: &str, members: &[Name]) {
+    match expr {
+        Expression::FunctionCall(call) => {
+            if members.contains(&call.name) {
+                call.name = Name::intern(&format!("{}::{}", module, call.name));
+            }
+            for argument in &mut call.arguments {
+                qualify_calls(argument, module, members);
+            }
+        }
+        Expression::Binary(bin_expr) => {
+            qualify_calls(&mut bin_expr.left, module, members);
+            qualify_calls(&mut bin_expr.right, module, members);
+        }
+        Expression::Unary(unary_expr) => qualify_calls(&mut unary_expr.operand, module, members),
+        Expression::Index(index_expr) => {
+            qualify_calls(&mut index_expr.target, module, members);
+            qualify_calls(&mut index_expr.index, module, members);
+        }
+        Expression::Comparison(chain) => {
+            for operand in &mut chain.operands {
+                qualify_calls(operand, module, members);
+            }
+        }
+        Expression::ArrayLiteral(elements) => {
+            for element in elements {
+                qualify_calls(element, module, members);
+            }
+        }
+        Expression::Ident(_)
+        | Expression::Int(_)
+        | Expression::Float(_)
+        | Expression::Str(_)
+        | Expression::Bool(_) => {}
+    }
+}
+
 fn parser_function_decl(parser: &mut Parser) -> Option<FunctionDeclaration> {
     parser.consume_keyword("function")?;
     let name_token = parser.consume(TokenType::Identifier, "Expected function name")?;
-    let name = name_token.value;
+    let name = name_token.interned_name();
 
     parser.consume(TokenType::LeftParen, "Expected '(' after function name")?;
     let mut parameters: Vec<Parameter> = Vec::new();
-    while !parser.check(TokenType::RightParen) {
+    while !parser.check(TokenType::RightParen) && !parser.is_at_end() {
+        if parser.check(TokenType::Comma) {
+            parser.error("Expected a parameter before ','");
+            return None;
+        }
         let param_name = parser.consume(TokenType::Identifier, "Expected parameter name")?;
         parser.consume(TokenType::Colon, "Expected ':' after parameter name")?;
         let param_type = parser.consume(TokenType::Type, "Expected parameter type")?;
         let parameter = Parameter {
-            name: param_name.value,
+            name: param_name.interned_name(),
             type_name: param_type.value,
         };
         parameters.push(parameter);
 
+        // Parameters are separated by exactly one comma; a single trailing comma is allowed
         if parser.check(TokenType::Comma) {
             parser.advance();
+        } else if !parser.check(TokenType::RightParen) {
+            parser.error("Expected ',' between parameters");
+            return None;
         }
     }
     parser.consume(
@@ -300,16 +605,22 @@ pub fn parse_block_like(parser: &mut Parser) -> Option<Vec<Statement>> {
     while !parser.check(TokenType::RightBracket) && !parser.is_at_end() {
         match parse_statement(parser) {
             Some(stmt) => statements.push(stmt),
-            None => {
-                eprintln!("Error while parsing statements in block");
-                break;
-            }
+            None => break,
         }
     }
 
     Some(statements)
 }
 
+/// Checks whether the next tokens start a labeled loop (`name:`).
+fn is_loop_label(parser: &Parser) -> bool {
+    parser.check(TokenType::Identifier)
+        && parser
+            .tokens
+            .get(parser.position + 1)
+            .is_some_and(|token| token.token_type == TokenType::Colon)
+}
+
 fn is_var_affection(parser: &Parser) -> bool {
     if parser.is_at_end() {
         return false;
@@ -319,11 +630,33 @@ fn is_var_affection(parser: &Parser) -> bool {
         return false;
     }
 
-    let current_position = parser.position;
-    let next_position = current_position + 1;
+    let mut next_position = parser.position + 1;
     if next_position >= parser.tokens.len() {
         return false;
     }
+
+    // Skip an index like `xs[i + 1]` to find the `=` of an element assignment
+    if parser.tokens[next_position].token_type == TokenType::LeftSquareBracket {
+        let mut depth = 0;
+        while next_position < parser.tokens.len() {
+            match parser.tokens[next_position].token_type {
+                TokenType::LeftSquareBracket => depth += 1,
+                TokenType::RightSquareBracket => {
+                    depth -= 1;
+                    if depth == 0 {
+                        break;
+                    }
+                }
+                _ => {}
+            }
+            next_position += 1;
+        }
+        next_position += 1;
+        if next_position >= parser.tokens.len() {
+            return false;
+        }
+    }
+
     let next_token = &parser.tokens[next_position];
     next_token.token_type == TokenType::Equals
 }
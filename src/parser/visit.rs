@@ -0,0 +1,200 @@
+use crate::parser::models::ast::AST;
+use crate::parser::models::expression::Expression;
+use crate::parser::models::statement::{
+    ForStatement, FunctionDeclaration, IfStatement, ModuleDeclaration, Return, Statement,
+    SwitchStatement, VarAffection, VarDeclaration, WhileStatement,
+};
+
+/// 🧭 A read-only traversal over the AST.
+///
+/// Every `visit_*` method defaults to the matching `walk_*` function, which visits the
+/// children of the node. Passes override only the nodes they care about, and call the
+/// `walk_*` function themselves when they still want to descend into the children.
+pub trait Visitor {
+    /// 🌳 Visits every top-level statement of the program.
+    fn visit_ast(&mut self, ast: &AST) {
+        for stmt in &ast.statements {
+            self.visit_statement(stmt);
+        }
+    }
+
+    /// 📜 Dispatches a statement to the `visit_*` method of its kind.
+    fn visit_statement(&mut self, stmt: &Statement) {
+        walk_statement(self, stmt);
+    }
+
+    fn visit_var_declaration(&mut self, var_decl: &VarDeclaration) {
+        walk_var_declaration(self, var_decl);
+    }
+
+    fn visit_var_affection(&mut self, var_affection: &VarAffection) {
+        walk_var_affection(self, var_affection);
+    }
+
+    fn visit_return(&mut self, return_stmt: &Return) {
+        if let Some(expr) = &return_stmt.value {
+            self.visit_expression(expr);
+        }
+    }
+
+    fn visit_if(&mut self, if_stmt: &IfStatement) {
+        walk_if(self, if_stmt);
+    }
+
+    fn visit_switch(&mut self, switch_stmt: &SwitchStatement) {
+        walk_switch(self, switch_stmt);
+    }
+
+    fn visit_while(&mut self, while_stmt: &WhileStatement) {
+        walk_while(self, while_stmt);
+    }
+
+    fn visit_for(&mut self, for_stmt: &ForStatement) {
+        walk_for(self, for_stmt);
+    }
+
+    fn visit_function_declaration(&mut self, func_decl: &FunctionDeclaration) {
+        walk_block(self, &func_decl.body);
+    }
+
+    fn visit_module(&mut self, module: &ModuleDeclaration) {
+        for func_decl in &module.functions {
+            self.visit_function_declaration(func_decl);
+        }
+    }
+
+    fn visit_expression_statement(&mut self, expr: &Expression) {
+        self.visit_expression(expr);
+    }
+
+    fn visit_break(&mut self, _label: Option<&str>) {}
+
+    fn visit_continue(&mut self, _label: Option<&str>) {}
+
+    /// 📦 Visits a bare `{ ... }` block.
+    fn visit_block(&mut self, statements: &[Statement]) {
+        walk_block(self, statements);
+    }
+
+    /// 🧮 Visits an expression and, by default, all of its sub-expressions.
+    fn visit_expression(&mut self, expr: &Expression) {
+        walk_expression(self, expr);
+    }
+}
+
+/// 🔀 Calls the `visit_*` method matching the kind of `stmt`.
+pub fn walk_statement<V: Visitor + ?Sized>(visitor: &mut V, stmt: &Statement) {
+    match stmt {
+        Statement::VarDeclaration(var_decl) => visitor.visit_var_declaration(var_decl),
+        Statement::VarDeclarations(declarations) => {
+            for var_decl in declarations {
+                visitor.visit_var_declaration(var_decl);
+            }
+        }
+        Statement::VarAffection(var_affection) => visitor.visit_var_affection(var_affection),
+        Statement::Return(return_stmt) => visitor.visit_return(return_stmt),
+        Statement::If(if_stmt) => visitor.visit_if(if_stmt),
+        Statement::Switch(switch_stmt) => visitor.visit_switch(switch_stmt),
+        Statement::While(while_stmt) => visitor.visit_while(while_stmt),
+        Statement::For(for_stmt) => visitor.visit_for(for_stmt),
+        Statement::FunctionDeclaration(func_decl) => visitor.visit_function_declaration(func_decl),
+        Statement::Module(module) => visitor.visit_module(module),
+        Statement::ExpressionStatement(expr) => visitor.visit_expression_statement(expr),
+        Statement::Break(label) => visitor.visit_break(label.as_deref()),
+        Statement::Continue(label) => visitor.visit_continue(label.as_deref()),
+        Statement::Block(statements) => visitor.visit_block(statements),
+    }
+}
+
+/// 📦 Visits each statement of a block in order.
+pub fn walk_block<V: Visitor + ?Sized>(visitor: &mut V, statements: &[Statement]) {
+    for stmt in statements {
+        visitor.visit_statement(stmt);
+    }
+}
+
+pub fn walk_var_declaration<V: Visitor + ?Sized>(visitor: &mut V, var_decl: &VarDeclaration) {
+    if let Some(size) = &var_decl.array_size {
+        visitor.visit_expression(size);
+    }
+    if let Some(init) = &var_decl.init {
+        visitor.visit_expression(init);
+    }
+}
+
+pub fn walk_var_affection<V: Visitor + ?Sized>(visitor: &mut V, var_affection: &VarAffection) {
+    if let Some(index) = &var_affection.index {
+        visitor.visit_expression(index);
+    }
+    visitor.visit_expression(&var_affection.value);
+}
+
+pub fn walk_if<V: Visitor + ?Sized>(visitor: &mut V, if_stmt: &IfStatement) {
+    visitor.visit_expression(&if_stmt.condition);
+    walk_block(visitor, &if_stmt.then_branch);
+    if let Some(else_branch) = &if_stmt.else_branch {
+        walk_block(visitor, else_branch);
+    }
+}
+
+pub fn walk_switch<V: Visitor + ?Sized>(visitor: &mut V, switch_stmt: &SwitchStatement) {
+    visitor.visit_expression(&switch_stmt.condition);
+    for case in &switch_stmt.cases {
+        visitor.visit_expression(&case.value);
+        if let Some(range_end) = &case.range_end {
+            visitor.visit_expression(range_end);
+        }
+        walk_block(visitor, &case.body);
+    }
+    if let Some(default) = &switch_stmt.default {
+        walk_block(visitor, default);
+    }
+}
+
+pub fn walk_while<V: Visitor + ?Sized>(visitor: &mut V, while_stmt: &WhileStatement) {
+    visitor.visit_expression(&while_stmt.condition);
+    walk_block(visitor, &while_stmt.body);
+}
+
+pub fn walk_for<V: Visitor + ?Sized>(visitor: &mut V, for_stmt: &ForStatement) {
+    for clause in [&for_stmt.init, &for_stmt.cond].into_iter().flatten() {
+        visitor.visit_statement(clause);
+    }
+    walk_block(visitor, &for_stmt.incr);
+    walk_block(visitor, &for_stmt.body);
+}
+
+/// 🌿 Visits the direct sub-expressions of `expr`.
+pub fn walk_expression<V: Visitor + ?Sized>(visitor: &mut V, expr: &Expression) {
+    match expr {
+        Expression::Binary(bin_expr) => {
+            visitor.visit_expression(&bin_expr.left);
+            visitor.visit_expression(&bin_expr.right);
+        }
+        Expression::Unary(unary_expr) => visitor.visit_expression(&unary_expr.operand),
+        Expression::FunctionCall(call) => {
+            for arg in &call.arguments {
+                visitor.visit_expression(arg);
+            }
+        }
+        Expression::Index(index_expr) => {
+            visitor.visit_expression(&index_expr.target);
+            visitor.visit_expression(&index_expr.index);
+        }
+        Expression::Comparison(chain) => {
+            for operand in &chain.operands {
+                visitor.visit_expression(operand);
+            }
+        }
+        Expression::ArrayLiteral(elements) => {
+            for element in elements {
+                visitor.visit_expression(element);
+            }
+        }
+        Expression::Ident(_)
+        | Expression::Int(_)
+        | Expression::Float(_)
+        | Expression::Str(_)
+        | Expression::Bool(_) => {}
+    }
+}
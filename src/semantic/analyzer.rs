@@ -1,94 +1,311 @@
 // semantic/analyzer.rs
 
+use std::collections::{HashMap, HashSet};
+
+use crate::lex::models::name::Name;
 use crate::parser::models::ast::AST;
 use crate::parser::models::expression::Expression;
-use crate::parser::models::statement::{ForStatement, FunctionDeclaration, Statement, SwitchStatement, VarAffection, WhileStatement};
+use crate::parser::models::statement::{ForStatement, FunctionDeclaration, ModuleDeclaration, Return, Statement, SwitchCase, SwitchStatement, VarAffection, WhileStatement};
+#[cfg(test)]
 use crate::parser::parser::Parser;
+use crate::parser::visit::Visitor;
+use crate::semantic::const_eval::{try_eval_const_with, ConstError, ConstValue};
+use crate::semantic::models::diagnostic::Diagnostic;
 use crate::semantic::models::semantic::{Symbol, SymbolType, SymbolTable};
+use crate::semantic::models::type_context::TypeContext;
+use crate::semantic::expression_analyzer::{element_type, ExpressionAnalyzer};
 use crate::semantic::statement_analyzer::StatementAnalyzer;
 
 pub struct SemanticAnalyzer {
     pub symbol_table: SymbolTable,
     pub errors: Vec<String>,
+    /// Avertissements : rapportés à l'utilisateur sans interrompre la compilation.
+    pub warnings: Vec<String>,
     pub current_function_return_type: Option<String>,
+    /// Valeurs des constantes globales calculables à la compilation.
+    pub constants: HashMap<Name, ConstValue>,
+    pub loop_depth: usize,
+    /// Étiquettes des boucles englobantes, de la plus externe à la plus interne.
+    pub loop_labels: Vec<String>,
+    /// Avertit des `switch` sans cas `default` (`--warn-missing-default`).
+    pub warn_missing_default: bool,
+    /// Type de chaque expression typée pendant l'analyse, transmis au générateur de code.
+    pub type_context: TypeContext,
+    /// Noms des fonctions appelées sans être définies à l'endroit de l'appel, dans l'ordre du
+    /// source : ils sont signalés par `check_unresolved_calls` une fois tout le programme vu.
+    pub unresolved_calls: Vec<Name>,
     pub ast: AST
 }
 
 impl SemanticAnalyzer {
     /// Crée un nouvel analyseur sémantique avec une table de symboles globale.
+    /// Raccourci pour les tests : le driver chronomètre le lexing et le parsing à part.
+    #[cfg(test)]
     pub fn new(input: String) -> Self {
         let mut parser= Parser::new(input);
         let ast = parser.parse_file();
-        let mut symbol_table = SymbolTable::new(None);
+        Self::from_ast(ast, parser.errors)
+    }
+
+    /// Crée un analyseur pour un AST déjà construit, avec les erreurs produites par le parser.
+    pub fn from_ast(ast: AST, parser_errors: Vec<String>) -> Self {
+        let mut symbol_table = SymbolTable::new();
         
-        // Déclarer la fonction built-in print : print(string) -> void
+        // Déclarer la fonction built-in print : print(string) -> int, le nombre d'octets
+        // écrits renvoyé par printf
         symbol_table.define(
-            "print".to_string(),
+            Name::intern("print"),
             Symbol {
-                name: "print".to_string(),
+                name: Name::intern("print"),
                 symbol_type: SymbolType::Function {
                     parameters: vec!["string".to_string()],
-                    return_type: "void".to_string(),
+                    return_type: "int".to_string(),
                 },
             }
         ).expect("Failed to add built-in function print");
 
+        // Déclarer la fonction built-in len : len(string) -> int
+        symbol_table.define(
+            Name::intern("len"),
+            Symbol {
+                name: Name::intern("len"),
+                symbol_type: SymbolType::Function {
+                    parameters: vec!["string".to_string()],
+                    return_type: "int".to_string(),
+                },
+            }
+        ).expect("Failed to add built-in function len");
+
+        // Déclarer la fonction built-in readint : readint() -> int, lu sur l'entrée standard
+        symbol_table.define(
+            Name::intern("readint"),
+            Symbol {
+                name: Name::intern("readint"),
+                symbol_type: SymbolType::Function {
+                    parameters: Vec::new(),
+                    return_type: "int".to_string(),
+                },
+            }
+        ).expect("Failed to add built-in function readint");
+
+        // Déclarer la fonction built-in todo : todo() arrête le programme, pour une fonction
+        // pas encore écrite
+        symbol_table.define(
+            Name::intern("todo"),
+            Symbol {
+                name: Name::intern("todo"),
+                symbol_type: SymbolType::Function {
+                    parameters: Vec::new(),
+                    return_type: "void".to_string(),
+                },
+            }
+        ).expect("Failed to add built-in function todo");
+
         SemanticAnalyzer {
             symbol_table,
-            errors: Vec::new(),
+            // Les erreurs de syntaxe sont rapportées en premier, avec les erreurs sémantiques
+            errors: parser_errors,
+            warnings: Vec::new(),
             current_function_return_type: None,
+            constants: HashMap::new(),
+            warn_missing_default: false,
+            loop_depth: 0,
+            loop_labels: Vec::new(),
+            type_context: TypeContext::default(),
+            unresolved_calls: Vec::new(),
             ast
         }
     }
 
-    /// Lance l'analyse sémantique sur l'AST.
-    pub fn analyze(&mut self) -> Vec<String> {
-        let statements = self.ast.statements.clone();
-        for stmt in &statements {
-            self.analyze_statement(stmt);
-        }
-        return self.errors.clone();
+    /// Lance l'analyse sémantique sur l'AST et retourne les erreurs puis les avertissements.
+    pub fn analyze(&mut self) -> Vec<Diagnostic> {
+        // L'AST est sorti de `self` le temps du parcours plutôt que cloné : les méthodes
+        // d'analyse peuvent alors modifier l'analyseur tout en empruntant les statements
+        let ast = std::mem::take(&mut self.ast);
+        self.visit_ast(&ast);
+        self.ast = ast;
+        self.check_unresolved_calls();
+        self.errors
+            .iter()
+            .cloned()
+            .map(Diagnostic::error)
+            .chain(self.warnings.iter().cloned().map(Diagnostic::warning))
+            .collect()
     }
 
-    /// Implémentation des autres méthodes comme `analyze_var_declaration`, `analyze_return_statement`, etc.
-    /// Vous pouvez les définir ici ou dans des modules séparés si vous préférez.
+    // Implémentation des autres méthodes comme `analyze_var_declaration`, `analyze_return_statement`, etc.
+    // Vous pouvez les définir ici ou dans des modules séparés si vous préférez.
     
     /// Analyse une déclaration de variable.
     pub fn analyze_var_declaration(&mut self, var_decl: &crate::parser::models::statement::VarDeclaration) {
+        // Les littéraux booléens et les noms de types ne peuvent pas servir de nom de variable
+        if var_decl.name == "true" || var_decl.name == "false" || self.is_type_defined(&var_decl.name) {
+            self.errors.push(format!(
+                "'{}' is a reserved word and cannot be used as a variable name.",
+                var_decl.name
+            ));
+        }
+
         // Vérifier si le type existe
         if !self.is_type_defined(&var_decl.type_name) {
             self.errors.push(format!("Type '{}' is not defined.", var_decl.type_name));
+        } else if var_decl.type_name == "void" {
+            self.errors.push(format!("Variable '{}' cannot have type 'void'.", var_decl.name));
+        }
+
+        // Pour un tableau, la taille doit être une expression constante positive
+        let mut declared_type = var_decl.type_name.clone();
+        if let Some(size_expr) = &var_decl.array_size {
+            match self.const_value(size_expr) {
+                Ok(ConstValue::Int(size)) if size > 0 => {
+                    declared_type = format!("{}[{}]", var_decl.type_name, size);
+                }
+                Ok(ConstValue::Int(size)) => {
+                    self.errors.push(format!("Array size must be positive, found '{}'.", size));
+                }
+                Ok(_) | Err(ConstError::NotConstant) => {
+                    self.errors.push(format!(
+                        "Array size of '{}' must be a constant expression.",
+                        var_decl.name
+                    ));
+                }
+                Err(err) => self.errors.push(err.to_string()),
+            }
+        }
+
+        // Une constante doit être globale et initialisée
+        if var_decl.is_const {
+            if !self.symbol_table.is_global() {
+                self.errors.push(format!(
+                    "Constant '{}' must be declared at the top level.",
+                    var_decl.name
+                ));
+            }
+            match &var_decl.init {
+                Some(init) => match self.const_value(init) {
+                    Ok(value) => {
+                        self.constants.insert(var_decl.name, value);
+                    }
+                    // Un littéral de chaîne est constant, même s'il n'est pas replié
+                    Err(ConstError::NotConstant) if matches!(init, Expression::Str(_)) => {}
+                    Err(ConstError::NotConstant) => {
+                        self.errors.push(format!(
+                            "Const initializer of '{}' must be a constant expression.",
+                            var_decl.name
+                        ));
+                    }
+                    Err(err) => self.errors.push(err.to_string()),
+                },
+                None => {
+                    self.errors.push(format!("Constant '{}' must be initialized.", var_decl.name));
+                }
+            }
+        }
+
+        // Masquer une variable d'un scope englobant est permis, mais souvent involontaire ;
+        // masquer une fonction la rendrait inappelable dans le reste du bloc
+        let outer = self.symbol_table.resolve_outer(var_decl.name);
+        if self.symbol_table.resolve_current(var_decl.name).is_none() {
+            match outer.map(|symbol| &symbol.symbol_type) {
+                Some(SymbolType::Variable(_)) | Some(SymbolType::Constant(_)) => {
+                    self.warnings.push(format!(
+                        "Variable '{}' shadows a variable from an outer scope.",
+                        var_decl.name
+                    ));
+                }
+                Some(SymbolType::Function { .. }) => {
+                    self.errors.push(format!(
+                        "Variable '{}' cannot shadow the function '{}'.",
+                        var_decl.name, var_decl.name
+                    ));
+                }
+                None => {}
+            }
         }
 
         // Vérifier si la variable est déjà définie dans le scope courant
         let symbol = Symbol {
-            name: var_decl.name.clone(),
-            symbol_type: SymbolType::Variable(var_decl.type_name.clone()),
+            name: var_decl.name,
+            symbol_type: if var_decl.is_const {
+                SymbolType::Constant(declared_type.clone())
+            } else {
+                SymbolType::Variable(declared_type.clone())
+            },
         };
-        if let Err(err) = self.symbol_table.define(var_decl.name.clone(), symbol) {
+        if let Err(err) = self.symbol_table.define(var_decl.name, symbol) {
             self.errors.push(err);
         }
 
+        // Une variable locale déclarée sans valeur ne peut pas être lue avant d'être affectée.
+        // Les globales (mises à zéro dans .bss) et les tableaux ne sont pas suivis.
+        let is_local = !self.symbol_table.is_global();
+        if is_local && var_decl.init.is_none() && var_decl.array_size.is_none() && !var_decl.is_const {
+            self.symbol_table.declare_unassigned(var_decl.name);
+        }
+
         // Vérifier l'initialisation si présente
-        if let Some(expr) = &var_decl.init {
+        if let (Some(Expression::ArrayLiteral(elements)), Some(_)) =
+            (&var_decl.init, &var_decl.array_size)
+        {
+            self.analyze_array_literal(var_decl, &declared_type, elements);
+        } else if let Some(expr) = &var_decl.init {
             let expr_type = self.get_expression_type(expr);
             if let Some(expr_type) = expr_type {
-                if &expr_type != &var_decl.type_name {
+                if expr_type != declared_type {
                     self.errors.push(format!(
                         "Type mismatch in variable declaration '{}': expected '{}', found '{}'.",
-                        var_decl.name, var_decl.type_name, expr_type
+                        var_decl.name, declared_type, expr_type
                     ));
                 }
             }
         }
     }
 
+    /// Vérifie le littéral `[a, b, ...]` qui initialise un tableau : il doit avoir autant
+    /// d'éléments que le tableau, chacun du type de ses éléments.
+    fn analyze_array_literal(
+        &mut self,
+        var_decl: &crate::parser::models::statement::VarDeclaration,
+        declared_type: &str,
+        elements: &[Expression],
+    ) {
+        // Une taille invalide a déjà été signalée : seuls les types sont alors vérifiés
+        if let Some((element_type, size)) = declared_type
+            .strip_suffix(']')
+            .and_then(|t| t.split_once('['))
+        {
+            if size != elements.len().to_string() {
+                self.errors.push(format!(
+                    "Array '{}' has {} elements, but its literal provides {}.",
+                    var_decl.name,
+                    size,
+                    elements.len()
+                ));
+            }
+            let element_type = element_type.to_string();
+            for element in elements {
+                if let Some(found) = self.get_expression_type(element) {
+                    if found != element_type {
+                        self.errors.push(format!(
+                            "Type mismatch in array literal of '{}': expected '{}', found '{}'.",
+                            var_decl.name, element_type, found
+                        ));
+                    }
+                }
+            }
+        } else {
+            for element in elements {
+                self.get_expression_type(element);
+            }
+        }
+    }
+
     /// Analyse une instruction `return`.
-    pub fn analyze_return_statement(&mut self, expr_opt: &Option<crate::parser::models::expression::Expression>) {
+    pub fn analyze_return_statement(&mut self, return_stmt: &Return) {
         // Vérifier si on est à l'intérieur d'une fonction
         let current_function_return_type = self.current_function_return_type.clone();
         if let Some(expected_return_type) = &current_function_return_type {
-            if let Some(expr) = expr_opt {
+            if let Some(expr) = &return_stmt.value {
                 // Analyser l'expression de retour pour déterminer son type
                 let expr_type = self.get_expression_type(expr);
                 if let Some(expr_type) = expr_type {
@@ -108,36 +325,95 @@ impl SemanticAnalyzer {
                     ));
                 }
             }
-        } else {
-            // Si on n'est pas à l'intérieur d'une fonction, une instruction `return` est invalide
-            self.errors.push("Return statement not inside a function.".to_string());
+        } else if let Some(expr) = &return_stmt.value {
+            // Hors de toute fonction, `return` termine le programme principal : la valeur
+            // éventuelle devient le code de sortie du processus (`true` pour un succès)
+            if let Some(expr_type) = self.get_expression_type(expr) {
+                if expr_type != "int" && expr_type != "bool" {
+                    self.errors.push(format!(
+                        "Return value of the main program must be of type 'int' or 'bool', found '{}'.",
+                        expr_type
+                    ));
+                }
+            }
         }
     }
 
     /// Analyse une affection de variable (assignment).
     pub fn analyze_var_affection(&mut self, var_affection: &VarAffection) {
         // Vérifier que la variable est déclarée
-        if self.symbol_table.resolve(&var_affection.name).is_none() {
+        if self.symbol_table.resolve(var_affection.name).is_none() {
             self.errors.push(format!("Undefined variable '{}'.", var_affection.name));
             // Continuer l'analyse pour détecter d'autres erreurs
+        } else if var_affection.index.is_none()
+            && matches!(&var_affection.value, Expression::Ident(name) if *name == var_affection.name)
+        {
+            // `x = x;` ne change rien : c'est presque toujours une faute de frappe
+            self.warnings
+                .push(format!("Self-assignment of '{}' has no effect.", var_affection.name));
         }
 
-        // Analyser l'expression assignée
+        // Analyser l'index éventuel (affectation d'un élément de tableau)
+        if let Some(index) = &var_affection.index {
+            if let Some(index_type) = self.get_expression_type(index) {
+                if index_type != "int" {
+                    self.errors.push(format!(
+                        "Array index must be of type 'int', found '{}'.",
+                        index_type
+                    ));
+                }
+            }
+        }
+
+        // Analyser l'expression assignée ; la variable n'est affectée qu'après son évaluation
         let expr_type = self.get_expression_type(&var_affection.value);
+        if var_affection.index.is_none() {
+            self.symbol_table.mark_assigned(var_affection.name);
+        }
 
         // Vérifier que le type de l'expression correspond au type de la variable
-        if let Some(var_symbol) = self.symbol_table.resolve(&var_affection.name) {
+        if let Some(var_symbol) = self.symbol_table.resolve(var_affection.name) {
             match &var_symbol.symbol_type {
                 SymbolType::Variable(var_type) => {
+                    let target_type = match &var_affection.index {
+                        Some(index) => match element_type(var_type) {
+                            Some(element) => {
+                                let element = element.to_string();
+                                let var_type = var_type.clone();
+                                self.check_index_from_end(&var_affection.name, &var_type, index);
+                                element
+                            }
+                            None => {
+                                self.errors.push(format!(
+                                    "Cannot index into a value of type '{}'.",
+                                    var_type
+                                ));
+                                return;
+                            }
+                        },
+                        None => {
+                            if element_type(var_type).is_some() {
+                                self.errors.push(format!(
+                                    "Cannot assign to array '{}' as a whole.",
+                                    var_affection.name
+                                ));
+                                return;
+                            }
+                            var_type.clone()
+                        }
+                    };
                     if let Some(expr_type) = expr_type {
-                        if expr_type != *var_type {
+                        if expr_type != target_type {
                             self.errors.push(format!(
                                 "Type mismatch in assignment to '{}': expected '{}', found '{}'.",
-                                var_affection.name, var_type, expr_type
+                                var_affection.name, target_type, expr_type
                             ));
                         }
                     }
                 }
+                SymbolType::Constant(_) => {
+                    self.errors.push(format!("Cannot assign to constant '{}'.", var_affection.name));
+                }
                 _ => {
                     self.errors.push(format!("'{}' is not a variable.", var_affection.name));
                 }
@@ -145,72 +421,121 @@ impl SemanticAnalyzer {
         }
     }
 
-    /// Analyse une instruction `if`.
-    pub fn analyze_if_statement(&mut self, if_stmt: &crate::parser::models::statement::IfStatement) {
-        // Analyser la condition
-        let cond_type = self.get_expression_type(&if_stmt.condition);
-        if let Some(cond_type) = cond_type {
-            if cond_type != "bool" {
+    /// Vérifie la condition d'une instruction `if`, `while` ou `for`.
+    ///
+    /// Le code généré teste `rax` contre zéro : seul un `bool` (0 ou 1) a un sens. Un `int`,
+    /// un `float` (dont les bits ne sont pas comparables à zéro) ou une `string` sont refusés.
+    fn analyze_condition(&mut self, condition: &Expression, statement: &str) {
+        match self.get_expression_type(condition) {
+            Some(cond_type) if cond_type != "bool" => {
                 self.errors.push(format!(
-                    "Condition in 'if' statement must be of type 'bool', found '{}'.",
-                    cond_type
+                    "Condition in '{}' statement must be of type 'bool', found '{}'.",
+                    statement, cond_type
+                ));
+            }
+            Some(_) => {}
+            None => {
+                self.errors.push(format!(
+                    "Unable to determine the type of the condition in '{}' statement.",
+                    statement
                 ));
             }
-        } else {
-            self.errors.push("Unable to determine the type of the condition in 'if' statement.".to_string());
         }
+    }
+
+    /// Analyse une instruction `if`.
+    pub fn analyze_if_statement(&mut self, if_stmt: &crate::parser::models::statement::IfStatement) {
+        // Analyser la condition
+        self.analyze_condition(&if_stmt.condition, "if");
+
+        // Analyser les blocs `then` et `else` ; sans `else`, la condition fausse ne modifie rien
+        let before = self.symbol_table.unassigned_by_scope();
+        let then_state = self.analyze_branch(&if_stmt.then_branch);
+        let else_state = match &if_stmt.else_branch {
+            Some(else_branch) => self.analyze_branch(else_branch),
+            None => Some(before.clone()),
+        };
+        self.merge_branches(before, vec![then_state, else_state]);
+    }
 
-        // Analyser le bloc `then`
+    /// Analyse un bloc exécuté de façon conditionnelle, dans son propre scope.
+    ///
+    /// Retourne l'état d'affectation à la sortie du bloc, puis remet l'état d'avant le bloc,
+    /// ou `None` si le bloc ne rend jamais la main (il se termine par `return`, `break`,
+    /// `continue` ou un appel à `todo()`, qui arrête le programme).
+    fn analyze_branch(&mut self, body: &[Statement]) -> Option<Vec<HashSet<Name>>> {
+        let before = self.symbol_table.unassigned_by_scope();
         self.enter_scope();
-        for stmt in &if_stmt.then_branch {
+        for stmt in body {
             self.analyze_statement(stmt);
         }
         self.exit_scope();
+        let after = self.symbol_table.unassigned_by_scope();
+        self.symbol_table.set_unassigned_by_scope(before);
+        match body.last() {
+            Some(Statement::Return(_)) | Some(Statement::Break(_)) | Some(Statement::Continue(_)) => None,
+            Some(Statement::ExpressionStatement(Expression::FunctionCall(call)))
+                if call.name == "todo" =>
+            {
+                None
+            }
+            _ => Some(after),
+        }
+    }
 
-        // Analyser le bloc `else` s'il existe
-        if let Some(else_branch) = &if_stmt.else_branch {
-            self.enter_scope();
-            for stmt in else_branch {
-                self.analyze_statement(stmt);
+    /// Fusionne les états d'affectation de branches alternatives : une variable n'est affectée
+    /// après l'instruction que si elle l'est dans chaque branche qui rend la main.
+    fn merge_branches(
+        &mut self,
+        before: Vec<HashSet<Name>>,
+        states: Vec<Option<Vec<HashSet<Name>>>>,
+    ) {
+        let mut reaching = states.into_iter().flatten();
+        // Si aucune branche ne rend la main, la suite est inatteignable : l'état est conservé
+        let Some(mut merged) = reaching.next() else {
+            self.symbol_table.set_unassigned_by_scope(before);
+            return;
+        };
+        for state in reaching {
+            for (level, unassigned) in merged.iter_mut().zip(state) {
+                level.extend(unassigned);
             }
-            self.exit_scope();
         }
+        self.symbol_table.set_unassigned_by_scope(merged);
     }
 
     pub fn analyze_for_statement(&mut self, for_stmt: &ForStatement) {
         self.enter_scope();
 
         // Analyser l'initialisation
-        self.analyze_statement(&for_stmt.init);
+        if let Some(init) = &for_stmt.init {
+            self.analyze_statement(init);
+        }
+        let after_init = self.symbol_table.unassigned_by_scope();
 
         // Analyser la condition
-        // La condition doit être une expression retournant un booléen
-        match &*for_stmt.cond {
-            Statement::ExpressionStatement(expr) => {
-                let cond_type = self.get_expression_type(expr);
-                if let Some(cond_type) = cond_type {
-                    if cond_type != "bool" {
-                        self.errors.push(format!(
-                            "Condition in 'for' statement must be of type 'bool', found '{}'.",
-                            cond_type
-                        ));
-                    }
-                } else {
-                    self.errors.push("Unable to determine the type of the condition in 'for' statement.".to_string());
-                }
-            }
-            _ => {
+        // La condition doit être une expression retournant un booléen ; absente, elle est toujours vraie
+        match for_stmt.cond.as_deref() {
+            Some(Statement::ExpressionStatement(expr)) => self.analyze_condition(expr, "for"),
+            Some(_) => {
                 self.errors.push("Condition in 'for' statement must be an expression statement.".to_string());
             }
+            None => {}
         }
 
-        // Analyser l'incrément
-        self.analyze_statement(&for_stmt.incr);
+        // Analyser chaque instruction de l'incrément, dans l'ordre
+        for incr in &for_stmt.incr {
+            self.analyze_statement(incr);
+        }
 
-        // Analyser le corps de la boucle
+        // Analyser le corps de la boucle : il peut ne jamais s'exécuter, ses affectations
+        // (comme celles de l'incrément) ne comptent donc pas après la boucle
+        self.enter_loop(&for_stmt.label);
         for stmt in &for_stmt.body {
             self.analyze_statement(stmt);
         }
+        self.exit_loop(&for_stmt.label);
+        self.symbol_table.set_unassigned_by_scope(after_init);
 
         self.exit_scope();
     }
@@ -218,33 +543,137 @@ impl SemanticAnalyzer {
     /// Analyse une boucle `while`.
     pub fn analyze_while_statement(&mut self, while_stmt: &WhileStatement) {
         // Analyser la condition
-        let cond_type = self.get_expression_type(&while_stmt.condition);
-        if let Some(cond_type) = cond_type {
-            if cond_type != "bool" {
+        self.analyze_condition(&while_stmt.condition, "while");
+
+        // Analyser le corps de la boucle dans un nouveau scope ; il peut ne jamais s'exécuter,
+        // ses affectations ne comptent donc pas après la boucle
+        let before = self.symbol_table.unassigned_by_scope();
+        self.enter_scope();
+        self.enter_loop(&while_stmt.label);
+        for stmt in &while_stmt.body {
+            self.analyze_statement(stmt);
+        }
+        self.exit_loop(&while_stmt.label);
+        self.exit_scope();
+        self.symbol_table.set_unassigned_by_scope(before);
+    }
+
+    /// Entre dans le corps d'une boucle, éventuellement étiquetée.
+    fn enter_loop(&mut self, label: &Option<String>) {
+        self.loop_depth += 1;
+        if let Some(label) = label {
+            // Une étiquette masquée ne pourrait plus être atteinte par `break`/`continue`
+            if self.loop_labels.contains(label) {
                 self.errors.push(format!(
-                    "Condition in 'while' statement must be of type 'bool', found '{}'.",
-                    cond_type
+                    "Loop label '{}' is already used by an enclosing loop.",
+                    label
                 ));
             }
-        } else {
-            self.errors.push("Unable to determine the type of the condition in 'while' statement.".to_string());
+            self.loop_labels.push(label.clone());
+        }
+    }
+
+    /// Sort du corps d'une boucle ouverte par `enter_loop`.
+    fn exit_loop(&mut self, label: &Option<String>) {
+        self.loop_depth -= 1;
+        if label.is_some() {
+            self.loop_labels.pop();
+        }
+    }
+
+    /// Vérifie que l'étiquette d'un `break`/`continue` désigne une boucle englobante.
+    fn check_loop_label(&mut self, label: Option<&str>) {
+        if let Some(label) = label {
+            if !self.loop_labels.iter().any(|name| name == label) {
+                self.errors.push(format!("Undefined loop label '{}'.", label));
+            }
         }
+    }
 
-        // Analyser le corps de la boucle dans un nouveau scope
+    /// Analyse un bloc `{ ... }` dans son propre scope. Le bloc est toujours exécuté : ses
+    /// affectations de variables extérieures restent donc valables après lui.
+    pub fn analyze_block(&mut self, statements: &[Statement]) {
         self.enter_scope();
-        for stmt in &while_stmt.body {
+        for stmt in statements {
             self.analyze_statement(stmt);
         }
         self.exit_scope();
     }
 
+    /// Analyse une instruction `break`, qui doit se trouver dans une boucle.
+    pub fn analyze_break_statement(&mut self, label: Option<&str>) {
+        if self.loop_depth == 0 {
+            self.errors.push("Break statement not inside a loop.".to_string());
+        } else {
+            self.check_loop_label(label);
+        }
+    }
+
+    /// Analyse une instruction `continue`, qui doit se trouver dans une boucle.
+    pub fn analyze_continue_statement(&mut self, label: Option<&str>) {
+        if self.loop_depth == 0 {
+            self.errors.push("Continue statement not inside a loop.".to_string());
+        } else {
+            self.check_loop_label(label);
+        }
+    }
+
     /// Analyse une instruction `switch`.
     pub fn analyze_switch_statement(&mut self, switch_stmt: &SwitchStatement) {
+        // Un `switch` vide n'exécute rien : c'est presque toujours un oubli
+        if switch_stmt.cases.is_empty() && switch_stmt.default.is_none() {
+            self.warnings.push("Switch statement has no cases.".to_string());
+        } else if switch_stmt.default.is_none() && self.warn_missing_default {
+            self.warnings.push("Switch statement has no 'default' case.".to_string());
+        }
+
         // Analyser l'expression du switch
         let switch_type = self.get_expression_type(&switch_stmt.condition);
         if let Some(switch_type) = switch_type {
+            // Les cas sont comparés par valeur (`cmp`) : seuls les types scalaires sont acceptés
+            if switch_type != "int" && switch_type != "bool" {
+                self.errors.push(format!(
+                    "Switch condition must be of type 'int' or 'bool', found '{}'.",
+                    switch_type
+                ));
+            }
+
             // Analyser chaque cas
+            let before = self.symbol_table.unassigned_by_scope();
+            let mut states = Vec::new();
+            let mut case_values: Vec<ConstValue> = Vec::new();
+            let mut case_ranges: Vec<(i64, i64)> = Vec::new();
             for case in &switch_stmt.cases {
+                if let Some(range_end) = &case.range_end {
+                    self.analyze_case_range(
+                        case,
+                        range_end,
+                        &switch_type,
+                        &case_values,
+                        &mut case_ranges,
+                    );
+                    states.push(self.analyze_branch(&case.body));
+                    continue;
+                }
+                // Deux cas de même valeur : le second ne pourrait jamais être atteint. Les valeurs
+                // sont comparées une fois calculées : `-1`, `0 - 1` et une constante valant -1
+                // sont le même cas
+                if let Ok(value) = self.const_value(&case.value) {
+                    if case_values.contains(&value) {
+                        self.errors.push(format!(
+                            "Duplicate case value '{}' in 'switch' statement.",
+                            case.value
+                        ));
+                    } else if let ConstValue::Int(value) = value {
+                        if case_ranges.iter().any(|&(low, high)| low <= value && value <= high) {
+                            self.warnings.push(format!(
+                                "Case '{}' overlaps a previous case in 'switch' statement.",
+                                case.value
+                            ));
+                        }
+                    }
+                    case_values.push(value);
+                }
                 let case_type = self.get_expression_type(&case.value);
                 if let Some(case_type) = case_type {
                     if case_type != switch_type {
@@ -258,26 +687,97 @@ impl SemanticAnalyzer {
                 }
 
                 // Analyser le corps du cas dans un nouveau scope
-                self.enter_scope();
-                for stmt in &case.body {
-                    self.analyze_statement(stmt);
-                }
-                self.exit_scope();
+                states.push(self.analyze_branch(&case.body));
             }
 
-            // Analyser le corps du `default` s'il existe
-            if let Some(default_body) = &switch_stmt.default {
-                self.enter_scope();
-                for stmt in default_body {
-                    self.analyze_statement(stmt);
-                }
-                self.exit_scope();
+            // Analyser le corps du `default` s'il existe ; sinon, aucun cas peut ne s'exécuter
+            match &switch_stmt.default {
+                Some(default_body) => states.push(self.analyze_branch(default_body)),
+                None => states.push(Some(before.clone())),
             }
+            self.merge_branches(before, states);
         } else {
             self.errors.push("Unable to determine the type of the condition in 'switch' statement.".to_string());
         }
     }
 
+    /// Analyse les bornes d'un cas `case low..high`.
+    ///
+    /// Les bornes doivent être des entiers connus à la compilation, avec `low <= high`. Un
+    /// intervalle qui recouvre un cas précédent n'est signalé que par un avertissement : le
+    /// premier cas l'emporte, comme le ferait une suite de `if`.
+    fn analyze_case_range(
+        &mut self,
+        case: &SwitchCase,
+        range_end: &Expression,
+        switch_type: &str,
+        case_values: &[ConstValue],
+        case_ranges: &mut Vec<(i64, i64)>,
+    ) {
+        let range = format!("{}..{}", case.value, range_end);
+        let low_type = self.get_expression_type(&case.value);
+        let high_type = self.get_expression_type(range_end);
+        let (Some(low_type), Some(high_type)) = (low_type, high_type) else {
+            self.errors.push("Unable to determine the type of a case in 'switch' statement.".to_string());
+            return;
+        };
+        if low_type != "int" || high_type != "int" {
+            self.errors.push(format!(
+                "Bounds of case range '{}' must be of type 'int', found '{}' and '{}'.",
+                range, low_type, high_type
+            ));
+            return;
+        }
+        if switch_type != "int" {
+            self.errors.push(format!(
+                "Case type 'int' does not match switch type '{}'.",
+                switch_type
+            ));
+            return;
+        }
+        let bounds = (self.const_value(&case.value), self.const_value(range_end));
+        let (Ok(ConstValue::Int(low)), Ok(ConstValue::Int(high))) = bounds else {
+            self.errors.push(format!(
+                "Bounds of case range '{}' must be constant.",
+                range
+            ));
+            return;
+        };
+        if low > high {
+            self.errors.push(format!(
+                "Case range '{}' is empty: its lower bound is greater than its upper bound.",
+                range
+            ));
+            return;
+        }
+        let overlaps_value = case_values
+            .iter()
+            .any(|value| matches!(value, ConstValue::Int(value) if low <= *value && *value <= high));
+        let overlaps_range = case_ranges
+            .iter()
+            .any(|&(other_low, other_high)| low <= other_high && other_low <= high);
+        if overlaps_value || overlaps_range {
+            self.warnings.push(format!(
+                "Case '{}' overlaps a previous case in 'switch' statement.",
+                range
+            ));
+        }
+        case_ranges.push((low, high));
+    }
+
+    /// Analyse un module : ses fonctions sont déclarées sous leur nom qualifié (`math::sqrt`).
+    pub fn analyze_module(&mut self, module: &ModuleDeclaration) {
+        if !self.symbol_table.is_global() {
+            self.errors.push(format!(
+                "Module '{}' must be declared at the top level.",
+                module.name
+            ));
+        }
+        for func_decl in &module.functions {
+            self.analyze_function_declaration(func_decl);
+        }
+    }
+
     /// Analyse une déclaration de fonction.
     pub fn analyze_function_declaration(&mut self, func_decl: &FunctionDeclaration) {
         // Construire le type de la fonction
@@ -287,17 +787,45 @@ impl SemanticAnalyzer {
             .map(|p| p.type_name.clone())
             .collect();
         let func_type = SymbolType::Function {
-            parameters: param_types,
+            parameters: param_types.clone(),
             return_type: func_decl.return_type.clone(),
         };
 
-        // Ajouter la fonction à la table des symboles
-        let symbol = Symbol {
-            name: func_decl.name.clone(),
-            symbol_type: func_type,
-        };
-        if let Err(err) = self.symbol_table.define(func_decl.name.clone(), symbol) {
-            self.errors.push(err);
+        // Le programme principal est généré sous le label `f_main`
+        if func_decl.name == "main" {
+            self.errors.push(
+                "Function name 'main' is reserved for the main program.".to_string(),
+            );
+        }
+
+        // Pas de surcharge : une seconde définition d'une fonction est rejetée, en précisant
+        // si sa signature diffère de la première
+        let existing = self
+            .symbol_table
+            .resolve_current(func_decl.name)
+            .map(|symbol| symbol.symbol_type.clone());
+        match existing {
+            Some(SymbolType::Function { parameters, return_type }) => {
+                if parameters == param_types && return_type == func_decl.return_type {
+                    self.errors
+                        .push(format!("Function '{}' is already defined.", func_decl.name));
+                } else {
+                    self.errors.push(format!(
+                        "Function '{}' is already defined with a different signature.",
+                        func_decl.name
+                    ));
+                }
+            }
+            _ => {
+                // Ajouter la fonction à la table des symboles
+                let symbol = Symbol {
+                    name: func_decl.name,
+                    symbol_type: func_type,
+                };
+                if let Err(err) = self.symbol_table.define(func_decl.name, symbol) {
+                    self.errors.push(err);
+                }
+            }
         }
 
         // Créer un nouveau scope pour les paramètres et le corps de la fonction
@@ -314,10 +842,10 @@ impl SemanticAnalyzer {
             }
 
             let param_symbol = Symbol {
-                name: param.name.clone(),
+                name: param.name,
                 symbol_type: SymbolType::Variable(param.type_name.clone()),
             };
-            if let Err(err) = self.symbol_table.define(param.name.clone(), param_symbol) {
+            if let Err(err) = self.symbol_table.define(param.name, param_symbol) {
                 self.errors.push(err);
             }
         }
@@ -337,84 +865,74 @@ impl SemanticAnalyzer {
         self.exit_scope();
     }
 
-    pub fn get_expression_type(&mut self, expr: &Expression) -> Option<String> {
-        match expr {
-            Expression::Ident(name) => {
-                self.symbol_table.resolve(name).map(|symbol| match &symbol.symbol_type {
-                    SymbolType::Variable(type_name) => type_name.clone(),
-                    SymbolType::Function { return_type, .. } => return_type.clone(),
-                    // Gérer d'autres types de symboles si nécessaire
-                })
-            },
-            Expression::Int(_) => Some("int".to_string()),
-            Expression::Float(_) => Some("float".to_string()),
-            Expression::Bool(_) => Some("bool".to_string()),
-            Expression::Str(_) => Some("string".to_string()),
-            Expression::Binary(bin_expr) => {
-                let left_type = self.get_expression_type(&bin_expr.left)?;
-                let right_type = self.get_expression_type(&bin_expr.right)?;
-                
-                // Vérifier que les types des opérandes correspondent
-                if left_type != right_type {
-                    self.errors.push(format!(
-                        "Type mismatch in binary expression: '{}' and '{}'.",
-                        left_type, right_type
-                    ));
-                    return None;
-                }
+    /// Calcule le type d'une expression déjà vérifiée par `analyze_expression`, pour le
+    /// contexte de types, sans répéter ses diagnostics.
+    fn record_expression_type(&mut self, expr: &Expression) {
+        let errors = self.errors.len();
+        let unresolved_calls = self.unresolved_calls.len();
+        self.get_expression_type(expr);
+        self.errors.truncate(errors);
+        self.unresolved_calls.truncate(unresolved_calls);
+    }
 
-                // Déterminer le type résultant basé sur l'opérateur
-                match bin_expr.op.as_str() {
-                    "+" | "-" | "*" | "/" => Some(left_type.clone()), // Supposons que ces opérateurs retournent le même type que les opérandes
-                    "==" | "!=" | "<" | "<=" | ">" | ">=" => Some("bool".to_string()), // Comparaisons retournent bool
-                    _ => {
-                        self.errors.push(format!(
-                            "Unknown binary operator '{}'.",
-                            bin_expr.op
-                        ));
-                        None
-                    }
-                }
-            },
-            Expression::FunctionCall(call) => {
-                if let Some(symbol) = self.symbol_table.resolve(&call.name) {
-                    match &symbol.symbol_type {
-                        SymbolType::Function { return_type, .. } => Some(return_type.clone()),
-                        _ => {
-                            self.errors.push(format!("'{}' is not a function.", call.name));
-                            None
-                        }
-                    }
-                } else {
-                    self.errors.push(format!("Undefined function '{}'.", call.name));
-                    None
-                }
-            },
-            // Gérer d'autres types d'expressions si nécessaire
-            _ => {
-                self.errors.push(format!(
-                    "Unsupported expression type: {:?}.",
-                    expr
-                ));
-                None
+    /// Vérifie, avant la génération de code, que chaque fonction appelée est définie : sinon
+    /// le générateur émettrait un `call` vers un symbole que l'édition de liens ne trouverait
+    /// pas. Une fonction définie plus loin dans le programme n'est pas encore visible à
+    /// l'endroit de l'appel, ce qui est signalé à part.
+    fn check_unresolved_calls(&mut self) {
+        for name in std::mem::take(&mut self.unresolved_calls) {
+            let defined_later = matches!(
+                self.symbol_table.resolve(name).map(|symbol| &symbol.symbol_type),
+                Some(SymbolType::Function { .. })
+            );
+            if defined_later {
+                self.errors
+                    .push(format!("Function '{}' is called before its declaration.", name));
+            } else {
+                self.errors.push(format!("Call to undefined function '{}'.", name));
             }
         }
     }
 
     pub fn analyze_expression(&mut self, expr: &Expression) {
         match expr {
-            Expression::Ident(name) => {
-                if self.symbol_table.resolve(name).is_none() {
-                    self.errors.push(format!("Undefined variable '{}'.", name));
-                }
+            Expression::Ident(name) if self.is_type_defined(name) => {
+                self.errors.push(format!("Type '{}' cannot be used as a value.", name));
+            }
+            Expression::Ident(name) if self.symbol_table.resolve(*name).is_none() => {
+                self.errors.push(format!("Undefined variable '{}'.", name));
+            }
+            Expression::Ident(name) if self.symbol_table.is_unassigned(*name) => {
+                self.errors.push(format!(
+                    "Variable '{}' may be used before being assigned.",
+                    name
+                ));
             }
             Expression::Binary(bin_expr) => {
                 self.analyze_expression(&bin_expr.left);
                 self.analyze_expression(&bin_expr.right);
                 // Ici, vous pourriez vérifier que les opérandes sont compatibles avec l'opérateur
             }
+            Expression::Unary(unary_expr) => {
+                self.analyze_expression(&unary_expr.operand);
+            }
+            Expression::Index(index_expr) => {
+                self.analyze_expression(&index_expr.target);
+                self.analyze_expression(&index_expr.index);
+                if let Expression::Ident(name) = &index_expr.target {
+                    let symbol_type = self.symbol_table.resolve(*name).map(|s| s.symbol_type.clone());
+                    if let Some(SymbolType::Variable(array_type)) = symbol_type {
+                        self.check_index_from_end(name, &array_type, &index_expr.index);
+                    }
+                }
+            }
+            Expression::Comparison(chain) => {
+                for operand in &chain.operands {
+                    self.analyze_expression(operand);
+                }
+            }
             Expression::FunctionCall(call) => {
-                if let Some(symbol) = self.symbol_table.resolve(&call.name) {
+                if let Some(symbol) = self.symbol_table.resolve(call.name) {
                     match &symbol.symbol_type {
                         SymbolType::Function { parameters, return_type: _ } => {
                             if parameters.len() != call.arguments.len() {
@@ -428,6 +946,7 @@ impl SemanticAnalyzer {
                             // Vérifier les types des arguments si vous avez un système de types
                             for arg in &call.arguments {
                                 self.analyze_expression(arg);
+                                self.record_expression_type(arg);
                             }
                         }
                         _ => {
@@ -436,34 +955,64 @@ impl SemanticAnalyzer {
                         }
                     }
                 } else {
-                    self.errors
-                        .push(format!("Undefined function '{}'.", call.name));
+                    self.unresolved_calls.push(call.name);
                 }
             }
+            Expression::ArrayLiteral(_) => {
+                self.errors
+                    .push("An array literal can only initialize an array variable.".to_string());
+            }
             // Gérez d'autres types d'expressions (Int, Float, Str, Bool, etc.) si nécessaire
             _ => {}
         }
     }
 
+    /// Avertit lorsqu'un appel utilisé comme instruction produit une valeur qui est perdue.
+    /// `print` en est exempté : on l'appelle pour son affichage, rarement pour sa valeur.
+    pub fn check_ignored_return_value(&mut self, expr: &Expression) {
+        if let Expression::FunctionCall(call) = expr {
+            if call.name == "print" {
+                return;
+            }
+            if let Some(Symbol {
+                symbol_type: SymbolType::Function { return_type, .. },
+                ..
+            }) = self.symbol_table.resolve(call.name)
+            {
+                if return_type != "void" {
+                    self.warnings
+                        .push(format!("Return value of '{}' is ignored.", call.name));
+                }
+            }
+        }
+    }
+
+    /// Évalue une expression constante dans le scope courant : un nom n'est remplacé par la
+    /// valeur d'une constante globale que s'il la désigne bien ici, et non un paramètre ou une
+    /// variable locale qui la masque.
+    pub(crate) fn const_value(&self, expr: &Expression) -> Result<ConstValue, ConstError> {
+        try_eval_const_with(expr, &|name| match self.symbol_table.resolve(name) {
+            Some(Symbol { symbol_type: SymbolType::Constant(_), .. }) => {
+                self.constants.get(&name).copied()
+            }
+            _ => None,
+        })
+    }
+
     /// Vérifie si un type est défini.
-    fn is_type_defined(&self, type_name: &str) -> bool {
+    pub(crate) fn is_type_defined(&self, type_name: &str) -> bool {
         // Liste des types de base, incluant 'void'
-        let predefined_types = vec!["int", "float", "bool", "string", "void"];
+        let predefined_types = ["int", "float", "bool", "string", "void"];
         predefined_types.contains(&type_name)
     }
 
-    /// Entre dans un nouveau scope en créant une nouvelle table de symboles.
+    /// Entre dans un nouveau scope en empilant un niveau dans la table des symboles.
     fn enter_scope(&mut self) {
-        let new_table = SymbolTable::new(Some(Box::new(self.symbol_table.clone())));
-        self.symbol_table = new_table;
+        self.symbol_table.enter_scope();
     }
 
-    /// Sorte du scope actuel en revenant à la table de symboles parente.
+    /// Sort du scope actuel en dépilant son niveau ; le scope global est conservé.
     fn exit_scope(&mut self) {
-        if let Some(parent) = self.symbol_table.parent.clone() {
-            self.symbol_table = *parent;
-        } else {
-            // Si pas de parent, on reste dans le scope global
-        }
+        self.symbol_table.exit_scope();
     }
 }
@@ -4,12 +4,15 @@ mod analyzer_tests {
     use std::path::Path;
 
     
+    use crate::lex::models::name::Name;
     use crate::semantic::analyzer::SemanticAnalyzer;
+    use crate::semantic::models::diagnostic::{render_diagnostic, Diagnostic};
+    use crate::semantic::models::semantic::{Symbol, SymbolTable, SymbolType};
 
     // Helper function to read a source file and return its content as a String
     fn read_source_file(filename: &str) -> String {
         let path = Path::new("own_files").join(filename);
-        fs::read_to_string(path).expect(&format!("Failed to read file {}", filename))
+        fs::read_to_string(path).unwrap_or_else(|_| panic!("Failed to read file {}", filename))
     }
 
     // Helper function to perform semantic analysis on source code
@@ -113,8 +116,7 @@ mod analyzer_tests {
 
     #[test]
     fn test_if_statement() {
-        // Pour éviter l'erreur "Return statement not inside a function", le fichier if_good.own
-        // doit être enveloppé dans une fonction.
+        // Le fichier if_good.own est enveloppé dans une fonction pour vérifier le type des `return`.
         let good_source = read_source_file("if/if_good.own");
         let good_analyzer = analyze_source(&good_source);
         assert!(
@@ -234,6 +236,35 @@ mod analyzer_tests {
             bad_analyzer.errors[0],
             "Case type 'string' does not match switch type 'int'."
         );
+
+        let duplicate_source = read_source_file("switch/switch_duplicate_bad.own");
+        let duplicate_analyzer = analyze_source(&duplicate_source);
+        assert_eq!(
+            duplicate_analyzer.errors,
+            vec!["Duplicate case value '(1 + 1)' in 'switch' statement.".to_string()]
+        );
+
+        // Les cas négatifs sont dédoublonnés d'après leur valeur, constantes comprises
+        let negative_source = read_source_file("switch/switch_negative.own");
+        let negative_analyzer = analyze_source(&negative_source);
+        assert!(
+            negative_analyzer.errors.is_empty(),
+            "No semantic errors expected in switch_negative.own, got: {:?}",
+            negative_analyzer.errors
+        );
+        let negative_duplicate_source = read_source_file("switch/switch_negative_duplicate_bad.own");
+        let negative_duplicate_analyzer = analyze_source(&negative_duplicate_source);
+        assert_eq!(
+            negative_duplicate_analyzer.errors,
+            vec!["Duplicate case value 'LOW' in 'switch' statement.".to_string()]
+        );
+
+        let string_source = read_source_file("switch/switch_string_bad.own");
+        let string_analyzer = analyze_source(&string_source);
+        assert_eq!(
+            string_analyzer.errors,
+            vec!["Switch condition must be of type 'int' or 'bool', found 'string'.".to_string()]
+        );
     }
 
     #[test]
@@ -270,4 +301,1069 @@ mod analyzer_tests {
             .iter()
             .any(|e| e.contains("Type mismatch in return statement")));
     }
-}
+
+    #[test]
+    fn test_bool_comparison() {
+        let good_source = read_source_file("bool/bool_good.own");
+        let good_analyzer = analyze_source(&good_source);
+        assert!(
+            good_analyzer.errors.is_empty(),
+            "No semantic errors expected in bool_good.own, got: {:?}",
+            good_analyzer.errors
+        );
+
+        let bad_source = read_source_file("bool/bool_bad.own");
+        let bad_analyzer = analyze_source(&bad_source);
+        assert_eq!(
+            bad_analyzer.errors.len(),
+            1,
+            "Expected 1 error in bool_bad.own, got: {:?}",
+            bad_analyzer.errors
+        );
+        assert_eq!(
+            bad_analyzer.errors[0],
+            "Ordering comparison not allowed on 'bool'."
+        );
+    }
+
+    #[test]
+    fn test_const_array_size() {
+        let good_source = read_source_file("array/array_good.own");
+        let good_analyzer = analyze_source(&good_source);
+        assert!(
+            good_analyzer.errors.is_empty(),
+            "No semantic errors expected in array_good.own, got: {:?}",
+            good_analyzer.errors
+        );
+
+        let bad_source = read_source_file("array/array_bad.own");
+        let bad_analyzer = analyze_source(&bad_source);
+        assert_eq!(
+            bad_analyzer.errors.len(),
+            2,
+            "Expected 2 errors in array_bad.own, got: {:?}",
+            bad_analyzer.errors
+        );
+        assert_eq!(
+            bad_analyzer.errors[0],
+            "Array size of 'xs' must be a constant expression."
+        );
+        assert_eq!(bad_analyzer.errors[1], "Division by zero in constant expression.");
+    }
+
+    #[test]
+    fn test_array_size_shadowed_by_a_parameter() {
+        // Dans la fonction, `N` est le paramètre et non la constante globale
+        let analyzer = analyze_source(
+            "const N: int = 4;\nfunction f(N: int): int {\n    let xs: int[N];\n    return N;\n}\nprint(f(1));",
+        );
+        assert_eq!(
+            analyzer.errors,
+            vec!["Array size of 'xs' must be a constant expression.".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_const_initializer_must_be_constant() {
+        let source = read_source_file("const/const_initializer_bad.own");
+        let analyzer = analyze_source(&source);
+        // Un littéral de chaîne reste accepté ; une variable ne l'est pas
+        assert_eq!(
+            analyzer.errors,
+            vec![
+                "Const initializer of 'SIZE' must be a constant expression.".to_string(),
+                "Const initializer of 'LIMIT' must be a constant expression.".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_break_statement() {
+        let good_source = read_source_file("break/break_good.own");
+        let good_analyzer = analyze_source(&good_source);
+        assert!(
+            good_analyzer.errors.is_empty(),
+            "No semantic errors expected in break_good.own, got: {:?}",
+            good_analyzer.errors
+        );
+
+        let bad_source = read_source_file("break/break_bad.own");
+        let bad_analyzer = analyze_source(&bad_source);
+        assert_eq!(
+            bad_analyzer.errors,
+            vec!["Break statement not inside a loop.".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_loop_labels() {
+        let good_source = read_source_file("label/label_good.own");
+        let good_analyzer = analyze_source(&good_source);
+        assert!(
+            good_analyzer.errors.is_empty(),
+            "No semantic errors expected in label_good.own, got: {:?}",
+            good_analyzer.errors
+        );
+
+        let bad_source = read_source_file("label/label_bad.own");
+        let bad_analyzer = analyze_source(&bad_source);
+        assert_eq!(
+            bad_analyzer.errors,
+            vec![
+                "Undefined loop label 'inner'.".to_string(),
+                "Loop label 'outer' is already used by an enclosing loop.".to_string(),
+                "Continue statement not inside a loop.".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_continue_statement() {
+        let good_source = read_source_file("continue/continue_good.own");
+        let good_analyzer = analyze_source(&good_source);
+        assert!(
+            good_analyzer.errors.is_empty(),
+            "No semantic errors expected in continue_good.own, got: {:?}",
+            good_analyzer.errors
+        );
+
+        let bad_source = read_source_file("continue/continue_bad.own");
+        let bad_analyzer = analyze_source(&bad_source);
+        assert_eq!(
+            bad_analyzer.errors,
+            vec!["Continue statement not inside a loop.".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_arithmetic_operand_types() {
+        let good_source = read_source_file("arithmetic/arithmetic_good.own");
+        let good_analyzer = analyze_source(&good_source);
+        assert!(
+            good_analyzer.errors.is_empty(),
+            "No semantic errors expected in arithmetic_good.own, got: {:?}",
+            good_analyzer.errors
+        );
+
+        let bad_source = read_source_file("arithmetic/arithmetic_bad.own");
+        let bad_analyzer = analyze_source(&bad_source);
+        assert_eq!(
+            bad_analyzer.errors,
+            vec![
+                "Operator '*' requires numeric operands, found 'string'.".to_string(),
+                "Operator '/' requires numeric operands, found 'bool'.".to_string(),
+                "Operator '%' requires 'int' operands, found 'float'.".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_float_literal_range() {
+        let good_source = read_source_file("float/float_good.own");
+        let good_analyzer = analyze_source(&good_source);
+        assert!(
+            good_analyzer.errors.is_empty(),
+            "No semantic errors expected in float_good.own, got: {:?}",
+            good_analyzer.errors
+        );
+
+        let bad_source = read_source_file("float/float_bad.own");
+        let bad_analyzer = analyze_source(&bad_source);
+        assert_eq!(
+            bad_analyzer.errors,
+            vec![
+                "Float literal is out of range for a 64-bit float.".to_string(),
+                "Special float value 'NaN' is not supported.".to_string(),
+            ]
+        );
+
+        // Toute orthographe que `parse::<f64>()` accepte est refusée, pas seulement `inf` et `NaN`
+        let analyzer = analyze_source("let a: float = Infinity;\nlet b: float = NAN;\nlet inform: int = 1;");
+        assert_eq!(
+            analyzer.errors,
+            vec![
+                "Special float value 'Infinity' is not supported.".to_string(),
+                "Special float value 'NAN' is not supported.".to_string(),
+            ]
+        );
+    }
+
+    // Helper function building a variable symbol
+    fn variable(name: &str, type_name: &str) -> Symbol {
+        Symbol {
+            name: Name::intern(name),
+            symbol_type: SymbolType::Variable(type_name.to_string()),
+        }
+    }
+
+    #[test]
+    fn test_symbol_table_visibility() {
+        let mut table = SymbolTable::new();
+        table.define(Name::intern("x"), variable("x", "int")).unwrap();
+        table.define(Name::intern("y"), variable("y", "int")).unwrap();
+        table.enter_scope();
+        table.define(Name::intern("x"), variable("x", "bool")).unwrap();
+        table.define(Name::intern("z"), variable("z", "float")).unwrap();
+        table.enter_scope();
+        table.define(Name::intern("w"), variable("w", "string")).unwrap();
+
+        // Seuls les noms du scope courant sont listés
+        assert_eq!(table.names().collect::<Vec<_>>(), vec!["w"]);
+        assert_eq!(table.depth(), 3);
+
+        // Le `x` interne masque le `x` global
+        let visible = table.all_visible();
+        let names: Vec<&str> = visible.iter().map(|symbol| symbol.name.as_str()).collect();
+        assert_eq!(names, vec!["w", "x", "y", "z"]);
+        match &visible[1].symbol_type {
+            SymbolType::Variable(type_name) => assert_eq!(type_name, "bool"),
+            other => panic!("Expected a variable, got {:?}", other),
+        }
+
+        // Sortir d'un scope ne dépile que ce niveau
+        table.exit_scope();
+        let mut function_names: Vec<&str> = table.names().collect();
+        function_names.sort();
+        assert_eq!(function_names, vec!["x", "z"]);
+        table.exit_scope();
+        assert!(table.is_global());
+        match &table.resolve(Name::intern("x")).unwrap().symbol_type {
+            SymbolType::Variable(type_name) => assert_eq!(type_name, "int"),
+            other => panic!("Expected a variable, got {:?}", other),
+        }
+        // Le scope global n'est jamais dépilé
+        table.exit_scope();
+        assert!(table.resolve(Name::intern("y")).is_some());
+    }
+
+    #[test]
+    fn test_deeply_nested_scopes() {
+        // 50 blocs `if` imbriqués, chacun déclarant une variable lue dans le bloc le plus interne
+        let depth = 50;
+        let mut source = String::new();
+        for level in 0..depth {
+            source.push_str(&format!("if (true) {{\nlet v{}: int = {};\n", level, level));
+        }
+        let sum: Vec<String> = (0..depth).map(|level| format!("v{}", level)).collect();
+        source.push_str(&format!("let total: int = {};\n", sum.join(" + ")));
+        for _ in 0..depth {
+            source.push_str("};\n");
+        }
+
+        let analyzer = analyze_source(&source);
+        assert!(
+            analyzer.errors.is_empty(),
+            "No semantic errors expected for nested blocks, got: {:?}",
+            analyzer.errors
+        );
+        assert!(analyzer.symbol_table.is_global());
+        assert!(analyzer.symbol_table.resolve(Name::intern("v0")).is_none());
+    }
+
+    #[test]
+    fn test_reserved_words() {
+        let good_source = read_source_file("reserved/reserved_good.own");
+        let good_analyzer = analyze_source(&good_source);
+        assert!(
+            good_analyzer.errors.is_empty(),
+            "No semantic errors expected in reserved_good.own, got: {:?}",
+            good_analyzer.errors
+        );
+
+        let bad_source = read_source_file("reserved/reserved_bad.own");
+        let bad_analyzer = analyze_source(&bad_source);
+        assert_eq!(
+            bad_analyzer.errors,
+            vec![
+                "'true' is a reserved word and cannot be used as a variable name.".to_string(),
+                "Type 'void' cannot be used as a value.".to_string(),
+                "Variable 'nothing' cannot have type 'void'.".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_function_redefinition() {
+        let good_source = read_source_file("overload/overload_good.own");
+        let good_analyzer = analyze_source(&good_source);
+        assert!(
+            good_analyzer.errors.is_empty(),
+            "No semantic errors expected in overload_good.own, got: {:?}",
+            good_analyzer.errors
+        );
+
+        let bad_source = read_source_file("overload/overload_bad.own");
+        let bad_analyzer = analyze_source(&bad_source);
+        assert_eq!(
+            bad_analyzer.errors,
+            vec![
+                "Function 'area' is already defined with a different signature.".to_string(),
+                "Function 'perimeter' is already defined.".to_string(),
+                "Symbol 'counter' already defined in the current scope.".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parameter_shadows_global() {
+        let good_source = read_source_file("shadowing/shadowing_good.own");
+        let good_analyzer = analyze_source(&good_source);
+        assert!(
+            good_analyzer.errors.is_empty(),
+            "No semantic errors expected in shadowing_good.own, got: {:?}",
+            good_analyzer.errors
+        );
+        // Le paramètre disparaît avec le scope de la fonction : la globale garde son type
+        assert!(good_analyzer.symbol_table.is_global());
+        match &good_analyzer.symbol_table.resolve(Name::intern("x")).unwrap().symbol_type {
+            SymbolType::Variable(type_name) => assert_eq!(type_name, "bool"),
+            other => panic!("Expected a variable, got {:?}", other),
+        }
+
+        let bad_source = read_source_file("shadowing/shadowing_bad.own");
+        let bad_analyzer = analyze_source(&bad_source);
+        assert_eq!(
+            bad_analyzer.errors,
+            vec!["Type mismatch in return statement: expected 'int', found 'bool'.".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_comma_separators() {
+        let good_source = read_source_file("commas/commas_good.own");
+        let good_analyzer = analyze_source(&good_source);
+        assert!(
+            good_analyzer.errors.is_empty(),
+            "No errors expected in commas_good.own, got: {:?}",
+            good_analyzer.errors
+        );
+
+        let cases = [
+            ("commas/commas_missing_bad.own", "Parser error: Expected ',' between parameters."),
+            ("commas/commas_double_bad.own", "Parser error: Expected a parameter before ','."),
+            ("commas/commas_args_bad.own", "Parser error: Expected ',' between arguments."),
+            ("commas/commas_args_double_bad.own", "Parser error: Expected an argument before ','."),
+        ];
+        for (file, expected) in cases {
+            let bad_analyzer = analyze_source(&read_source_file(file));
+            assert_eq!(
+                bad_analyzer.errors.first().map(String::as_str),
+                Some(expected),
+                "Unexpected errors for {}: {:?}",
+                file,
+                bad_analyzer.errors
+            );
+        }
+    }
+
+    #[test]
+    fn test_logical_operators() {
+        let good_source = read_source_file("logical/logical_good.own");
+        let good_analyzer = analyze_source(&good_source);
+        assert!(
+            good_analyzer.errors.is_empty(),
+            "No semantic errors expected in logical_good.own, got: {:?}",
+            good_analyzer.errors
+        );
+
+        let bad_source = read_source_file("logical/logical_bad.own");
+        let bad_analyzer = analyze_source(&bad_source);
+        assert_eq!(
+            bad_analyzer.errors,
+            vec![
+                "Operator '&&' requires 'bool' operands, found 'int'.".to_string(),
+                "Type mismatch in variable declaration 'c': expected 'int', found 'bool'.".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_use_before_assignment() {
+        let good_source = read_source_file("assignment/assignment_good.own");
+        let good_analyzer = analyze_source(&good_source);
+        assert!(
+            good_analyzer.errors.is_empty(),
+            "No semantic errors expected in assignment_good.own, got: {:?}",
+            good_analyzer.errors
+        );
+
+        let bad_source = read_source_file("assignment/assignment_bad.own");
+        let bad_analyzer = analyze_source(&bad_source);
+        assert_eq!(
+            bad_analyzer.errors,
+            vec![
+                "Variable 'name' may be used before being assigned.".to_string(),
+                "Variable 'total' may be used before being assigned.".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_assignment_across_branches() {
+        let good_source = read_source_file("assignment/assignment_branches_good.own");
+        let good_analyzer = analyze_source(&good_source);
+        assert!(
+            good_analyzer.errors.is_empty(),
+            "No semantic errors expected in assignment_branches_good.own, got: {:?}",
+            good_analyzer.errors
+        );
+
+        let bad_source = read_source_file("assignment/assignment_branches_bad.own");
+        let bad_analyzer = analyze_source(&bad_source);
+        assert_eq!(
+            bad_analyzer.errors,
+            vec![
+                "Variable 'result' may be used before being assigned.".to_string(),
+                "Variable 'result' may be used before being assigned.".to_string(),
+                "Variable 'result' may be used before being assigned.".to_string(),
+            ]
+        );
+    }
+    #[test]
+    fn test_return_from_main_program() {
+        let good_source = read_source_file("main_return/main_return_good.own");
+        let good_analyzer = analyze_source(&good_source);
+        assert!(
+            good_analyzer.errors.is_empty(),
+            "No semantic errors expected in main_return_good.own, got: {:?}",
+            good_analyzer.errors
+        );
+
+        let bool_source = read_source_file("main_return/main_return_bool.own");
+        let bool_analyzer = analyze_source(&bool_source);
+        assert!(
+            bool_analyzer.errors.is_empty(),
+            "No semantic errors expected in main_return_bool.own, got: {:?}",
+            bool_analyzer.errors
+        );
+
+        let bad_source = read_source_file("main_return/main_return_bad.own");
+        let bad_analyzer = analyze_source(&bad_source);
+        assert_eq!(
+            bad_analyzer.errors,
+            vec![
+                "Return value of the main program must be of type 'int' or 'bool', found 'string'.".to_string(),
+                "Function name 'main' is reserved for the main program.".to_string(),
+            ]
+        );
+    }
+    #[test]
+    fn test_len_builtin() {
+        let good_source = read_source_file("len/len_good.own");
+        let good_analyzer = analyze_source(&good_source);
+        assert!(
+            good_analyzer.errors.is_empty(),
+            "No semantic errors expected in len_good.own, got: {:?}",
+            good_analyzer.errors
+        );
+
+        let bad_source = read_source_file("len/len_bad.own");
+        let bad_analyzer = analyze_source(&bad_source);
+        assert_eq!(
+            bad_analyzer.errors,
+            vec![
+                "Function 'len' expects a 'string' argument, found 'int'.".to_string(),
+                "Type mismatch in variable declaration 'b': expected 'bool', found 'int'.".to_string(),
+            ]
+        );
+    }
+    #[test]
+    fn test_warnings_do_not_fail_analysis() {
+        let source = read_source_file("shadowing/shadowing_warning.own");
+        let mut analyzer = SemanticAnalyzer::new(source);
+        let diagnostics = analyzer.analyze();
+        assert!(analyzer.errors.is_empty(), "Unexpected errors: {:?}", analyzer.errors);
+        assert_eq!(
+            diagnostics,
+            vec![Diagnostic::warning(
+                "Variable 'count' shadows a variable from an outer scope.".to_string()
+            )]
+        );
+        assert!(!diagnostics.iter().any(|d| d.is_error()));
+        assert_eq!(
+            diagnostics[0].to_string(),
+            "warning: Variable 'count' shadows a variable from an outer scope."
+        );
+    }
+    #[test]
+    fn test_ignored_return_value_warning() {
+        let source = read_source_file("function/function_ignored_return.own");
+        let analyzer = analyze_source(&source);
+        assert!(analyzer.errors.is_empty(), "Unexpected errors: {:?}", analyzer.errors);
+        assert_eq!(
+            analyzer.warnings,
+            vec!["Return value of 'bump' is ignored.".to_string()]
+        );
+    }
+    #[test]
+    fn test_conditions_must_be_bool() {
+        let good_source = read_source_file("condition/condition_good.own");
+        let good_analyzer = analyze_source(&good_source);
+        assert!(
+            good_analyzer.errors.is_empty(),
+            "No semantic errors expected in condition_good.own, got: {:?}",
+            good_analyzer.errors
+        );
+
+        let bad_source = read_source_file("condition/condition_bad.own");
+        let bad_analyzer = analyze_source(&bad_source);
+        assert_eq!(
+            bad_analyzer.errors,
+            vec![
+                "Condition in 'if' statement must be of type 'bool', found 'float'.".to_string(),
+                "Condition in 'while' statement must be of type 'bool', found 'float'.".to_string(),
+                "Condition in 'for' statement must be of type 'bool', found 'float'.".to_string(),
+                "Condition in 'if' statement must be of type 'bool', found 'int'.".to_string(),
+                "Condition in 'if' statement must be of type 'bool', found 'string'.".to_string(),
+            ]
+        );
+    }
+    #[test]
+    fn test_comparison_between_incompatible_types() {
+        let source = read_source_file("condition/condition_compare_mismatch_bad.own");
+        let analyzer = analyze_source(&source);
+        // Les comparaisons ont leur propre message ; l'addition garde l'erreur arithmétique
+        assert_eq!(
+            analyzer.errors,
+            vec![
+                "Cannot compare 'int' with 'string'; consider converting.".to_string(),
+                "Unable to determine the type of the condition in 'if' statement.".to_string(),
+                "Cannot compare 'int' with 'string'; consider converting.".to_string(),
+                "Cannot compare 'int' with 'string'; consider converting.".to_string(),
+                "Unable to determine the type of the condition in 'if' statement.".to_string(),
+                "Type mismatch in binary expression: 'int' and 'string'.".to_string(),
+            ]
+        );
+    }
+    #[test]
+    fn test_modules() {
+        let good_source = read_source_file("module/module_good.own");
+        let good_analyzer = analyze_source(&good_source);
+        assert!(
+            good_analyzer.errors.is_empty(),
+            "No semantic errors expected in module_good.own, got: {:?}",
+            good_analyzer.errors
+        );
+
+        let bad_source = read_source_file("module/module_bad.own");
+        let bad_analyzer = analyze_source(&bad_source);
+        assert_eq!(
+            bad_analyzer.errors,
+            vec![
+                "Module 'inner' must be declared at the top level.".to_string(),
+                "Call to undefined function 'math::cube'.".to_string(),
+                "Call to undefined function 'square'.".to_string(),
+            ]
+        );
+    }
+    #[test]
+    fn test_large_program_is_analyzed_in_place() {
+        let mut source = String::new();
+        for i in 0..5000 {
+            source.push_str(&format!("let v{}: int = {} * 2;\nprint(v{} + 1);\n", i, i, i));
+        }
+        let mut analyzer = SemanticAnalyzer::new(source);
+        let statements_ptr = analyzer.ast.statements.as_ptr();
+        let diagnostics = analyzer.analyze();
+        assert!(diagnostics.is_empty(), "Unexpected diagnostics: {:?}", diagnostics);
+        assert_eq!(analyzer.ast.statements.len(), 10000);
+        // L'analyse rend le même vecteur de statements, et non une copie
+        assert_eq!(analyzer.ast.statements.as_ptr(), statements_ptr);
+    }
+
+    #[test]
+    fn test_untaken_constant_branch_is_analyzed() {
+        // La branche jamais exécutée n'est pas générée, mais ses erreurs sont signalées
+        let analyzer = analyze_source("if (false) {\n    let y: int = true;\n};");
+        assert_eq!(
+            analyzer.errors,
+            vec!["Type mismatch in variable declaration 'y': expected 'int', found 'bool'.".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_string_indexing() {
+        let good_source = read_source_file("string_index/string_index_good.own");
+        let good_analyzer = analyze_source(&good_source);
+        assert!(
+            good_analyzer.errors.is_empty(),
+            "No semantic errors expected in string_index_good.own, got: {:?}",
+            good_analyzer.errors
+        );
+
+        let bad_source = read_source_file("string_index/string_index_bad.own");
+        let bad_analyzer = analyze_source(&bad_source);
+        assert_eq!(
+            bad_analyzer.errors,
+            vec![
+                "Type mismatch in variable declaration 'b': expected 'bool', found 'int'.".to_string(),
+                "Array index must be of type 'int', found 'bool'.".to_string(),
+                "Cannot index into a value of type 'string'.".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_empty_switch_warning() {
+        let source = read_source_file("switch/switch_empty.own");
+        let analyzer = analyze_source(&source);
+        assert!(analyzer.errors.is_empty(), "Unexpected errors: {:?}", analyzer.errors);
+        assert_eq!(analyzer.warnings, vec!["Switch statement has no cases.".to_string()]);
+
+        // Les `switch` sans `default` ne sont signalés que sur demande
+        let mut analyzer = SemanticAnalyzer::new(source);
+        analyzer.warn_missing_default = true;
+        analyzer.analyze();
+        assert_eq!(
+            analyzer.warnings,
+            vec![
+                "Switch statement has no cases.".to_string(),
+                "Switch statement has no 'default' case.".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_switch_range_cases() {
+        let good_source = read_source_file("switch/switch_range.own");
+        let good_analyzer = analyze_source(&good_source);
+        assert!(
+            good_analyzer.errors.is_empty() && good_analyzer.warnings.is_empty(),
+            "No diagnostics expected in switch_range.own, got: {:?} {:?}",
+            good_analyzer.errors,
+            good_analyzer.warnings
+        );
+
+        let bad_source = read_source_file("switch/switch_range_bad.own");
+        let bad_analyzer = analyze_source(&bad_source);
+        assert_eq!(
+            bad_analyzer.errors,
+            vec![
+                "Case range '5..1' is empty: its lower bound is greater than its upper bound.".to_string(),
+                "Bounds of case range '1..n' must be constant.".to_string(),
+                "Bounds of case range '0.5..2.5' must be of type 'int', found 'float' and 'float'.".to_string(),
+            ]
+        );
+
+        // Un recouvrement n'est qu'un avertissement : le premier cas qui correspond l'emporte
+        let overlap_source = read_source_file("switch/switch_range_overlap.own");
+        let overlap_analyzer = analyze_source(&overlap_source);
+        assert!(overlap_analyzer.errors.is_empty(), "Unexpected errors: {:?}", overlap_analyzer.errors);
+        assert_eq!(
+            overlap_analyzer.warnings,
+            vec![
+                "Case '4..8' overlaps a previous case in 'switch' statement.".to_string(),
+                "Case '3' overlaps a previous case in 'switch' statement.".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_block_statement() {
+        let good_source = read_source_file("block/block_good.own");
+        let good_analyzer = analyze_source(&good_source);
+        assert!(
+            good_analyzer.errors.is_empty(),
+            "No semantic errors expected in block_good.own, got: {:?}",
+            good_analyzer.errors
+        );
+        // Le `x` du bloc a disparu avec son scope : le `x` global garde son type
+        match &good_analyzer.symbol_table.resolve(Name::intern("x")).unwrap().symbol_type {
+            SymbolType::Variable(type_name) => assert_eq!(type_name, "int"),
+            other => panic!("Expected a variable, got {:?}", other),
+        }
+        assert!(good_analyzer.symbol_table.resolve(Name::intern("y")).is_none());
+
+        let bad_source = read_source_file("block/block_bad.own");
+        let bad_analyzer = analyze_source(&bad_source);
+        assert_eq!(
+            bad_analyzer.errors,
+            vec![
+                "Undefined variable 'y'.".to_string(),
+                "Type mismatch in variable declaration 'z': expected 'bool', found 'int'.".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_for_increment_with_commas() {
+        let good_source = read_source_file("for/for_two_counters.own");
+        let good_analyzer = analyze_source(&good_source);
+        assert!(
+            good_analyzer.errors.is_empty(),
+            "No semantic errors expected in for_two_counters.own, got: {:?}",
+            good_analyzer.errors
+        );
+
+        // Chaque instruction de l'incrément est vérifiée
+        let bad_analyzer = analyze_source(
+            "let j: int = 10;\nfor (let i: int = 0; i < 3; i = i + 1, j = true;) {\n    print(i);\n};",
+        );
+        assert_eq!(
+            bad_analyzer.errors,
+            vec!["Type mismatch in assignment to 'j': expected 'int', found 'bool'.".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_type_context_records_expression_types() {
+        use crate::parser::models::expression::Expression;
+        use crate::parser::models::statement::Statement;
+
+        let analyzer = analyze_source("let a: float = 1.5;\nlet b: bool = a * 2.0 > a;\nprint(a * a);");
+        assert!(analyzer.errors.is_empty(), "Unexpected errors: {:?}", analyzer.errors);
+        // Chaque sous-expression de l'initialisation a son type
+        let Statement::VarDeclaration(var_decl) = &analyzer.ast.statements[1] else {
+            panic!("Expected a declaration");
+        };
+        let init = var_decl.init.as_ref().unwrap();
+        assert_eq!(analyzer.type_context.get(init), Some("bool"));
+        let Expression::Binary(comparison) = init else {
+            panic!("Expected a binary expression");
+        };
+        assert_eq!(analyzer.type_context.get(&comparison.left), Some("float"));
+        assert_eq!(analyzer.type_context.get(&comparison.right), Some("float"));
+
+        // Les arguments d'un appel sont typés eux aussi
+        let Statement::ExpressionStatement(Expression::FunctionCall(call)) = &analyzer.ast.statements[2] else {
+            panic!("Expected a call");
+        };
+        assert_eq!(analyzer.type_context.get(&call.arguments[0]), Some("float"));
+        // Une expression hors de l'AST n'a pas de type
+        assert_eq!(analyzer.type_context.get(&Expression::Float(1.5)), None);
+    }
+
+    #[test]
+    fn test_bool_variable_conditions() {
+        let source = read_source_file("condition/condition_bool_var.own");
+        let analyzer = analyze_source(&source);
+        assert!(
+            analyzer.errors.is_empty(),
+            "No semantic errors expected in condition_bool_var.own, got: {:?}",
+            analyzer.errors
+        );
+        // Une variable d'un autre type reste refusée
+        let analyzer = analyze_source("let n: int = 1;\nwhile (n) {\n    n = 0;\n};");
+        assert_eq!(
+            analyzer.errors,
+            vec!["Condition in 'while' statement must be of type 'bool', found 'int'.".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_assignment_in_condition() {
+        let source = read_source_file("condition/condition_assign_bad.own");
+        let analyzer = analyze_source(&source);
+        assert_eq!(
+            analyzer.errors[0],
+            "Parser error: Unexpected '=' in condition; did you mean '=='?"
+        );
+    }
+
+    #[test]
+    fn test_unary_plus() {
+        let analyzer = analyze_source(
+            "let n: int = +5;\nlet f: float = 1.5;\nlet g: float = +f;\nlet h: float = -+f;",
+        );
+        assert!(analyzer.errors.is_empty(), "Unexpected errors: {:?}", analyzer.errors);
+
+        // Le `+` unaire n'a de sens que sur un nombre
+        let analyzer = analyze_source("let s: string = +\"s\";\nlet b: bool = +true;");
+        assert_eq!(
+            analyzer.errors,
+            vec![
+                "Operator '+' requires a numeric operand, found 'string'.".to_string(),
+                "Operator '+' requires a numeric operand, found 'bool'.".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_power_operator() {
+        let analyzer = analyze_source("let a: int = 2 ** 10;\nlet b: float = 2.0 ** 0.5;\nlet n: int = 3;\nlet c: int = a ** n;");
+        assert!(analyzer.errors.is_empty(), "Unexpected errors: {:?}", analyzer.errors);
+
+        let analyzer = analyze_source(
+            "let a: bool = true ** false;\nlet b: int = 2 ** -1;\nlet c: float = 2.0 ** 2;",
+        );
+        assert_eq!(
+            analyzer.errors,
+            vec![
+                "Operator '**' requires numeric operands, found 'bool'.".to_string(),
+                "Negative exponent in integer power.".to_string(),
+                "Type mismatch in binary expression: 'float' and 'int'.".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_multiple_declarations() {
+        let good_source = read_source_file("var_decl/var_decl_multi_good.own");
+        let good_analyzer = analyze_source(&good_source);
+        assert!(
+            good_analyzer.errors.is_empty(),
+            "No semantic errors expected in var_decl_multi_good.own, got: {:?}",
+            good_analyzer.errors
+        );
+        // Chaque déclarateur est défini avec son propre type
+        for (name, expected) in [("a", "int"), ("b", "float"), ("c", "string"), ("xs", "int[6]")] {
+            match &good_analyzer.symbol_table.resolve(Name::intern(name)).unwrap().symbol_type {
+                SymbolType::Variable(type_name) => assert_eq!(type_name, expected),
+                other => panic!("Expected a variable, got {:?}", other),
+            }
+        }
+        assert!(matches!(
+            good_analyzer.symbol_table.resolve(Name::intern("M")).unwrap().symbol_type,
+            SymbolType::Constant(_)
+        ));
+
+        let bad_source = read_source_file("var_decl/var_decl_multi_bad.own");
+        let bad_analyzer = analyze_source(&bad_source);
+        assert_eq!(
+            bad_analyzer.errors,
+            vec![
+                "Type mismatch in variable declaration 'b': expected 'bool', found 'int'.".to_string(),
+                "Symbol 'c' already defined in the current scope.".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_variable_shadowing_a_function() {
+        let source = read_source_file("shadowing/shadowing_function_bad.own");
+        let analyzer = analyze_source(&source);
+        assert_eq!(
+            analyzer.errors,
+            vec![
+                "Variable 'square' cannot shadow the function 'square'.".to_string(),
+                "Variable 'len' cannot shadow the function 'len'.".to_string(),
+            ]
+        );
+        // Ce n'est pas un simple masquage de variable : aucun avertissement en plus
+        assert!(analyzer.warnings.is_empty(), "Unexpected warnings: {:?}", analyzer.warnings);
+    }
+
+    #[test]
+    fn test_repeated_identifiers_across_scopes() {
+        // Quelques noms réutilisés des milliers de fois, dans des fonctions et des blocs
+        // qui les redéfinissent : chaque scope garde ses propres symboles
+        let mut source = String::from("let total: int = 0;\n");
+        for i in 0..300 {
+            source.push_str(&format!(
+                "function f{i}(value: int): int {{\n    let total: int = value;\n    {{\n        let value: int = total + {i};\n        total = value;\n    }};\n    return total;\n}}\ntotal = total + f{i}(total);\n"
+            ));
+        }
+        source.push_str("print(totl);");
+        let analyzer = analyze_source(&source);
+        assert_eq!(analyzer.errors, vec!["Undefined variable 'totl'.".to_string()]);
+        // Les 300 fonctions, `total` et les fonctions intégrées `print`, `len`, `readint` et `todo`
+        assert_eq!(analyzer.symbol_table.all_visible().len(), 300 + 5);
+
+        let mut table = SymbolTable::new();
+        for _ in 0..1000 {
+            table.enter_scope();
+            table.define(Name::intern("value"), variable("value", "int")).unwrap();
+            table.declare_unassigned(Name::intern("value"));
+            assert!(table.is_unassigned(Name::intern("value")));
+            table.mark_assigned(Name::intern("value"));
+            assert!(!table.is_unassigned(Name::intern("value")));
+            assert!(table.resolve_outer(Name::intern("value")).is_none());
+            table.exit_scope();
+        }
+        // Un nom jamais défini n'est résolu dans aucun scope
+        assert!(table.resolve(Name::intern("never_defined")).is_none());
+        assert!(!table.is_unassigned(Name::intern("never_defined")));
+    }
+
+    #[test]
+    fn test_print_returns_the_byte_count() {
+        let source = read_source_file("print/print_count.own");
+        let analyzer = analyze_source(&source);
+        assert!(analyzer.errors.is_empty(), "Unexpected errors: {:?}", analyzer.errors);
+        // `print` renvoie un int, mais l'ignorer n'est pas signalé
+        assert!(analyzer.warnings.is_empty(), "Unexpected warnings: {:?}", analyzer.warnings);
+
+        let analyzer = analyze_source("let ok: bool = print(1);");
+        assert_eq!(
+            analyzer.errors,
+            vec!["Type mismatch in variable declaration 'ok': expected 'bool', found 'int'.".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_render_diagnostic() {
+        let source = "let x: int = 1;\nif (x == 1) {\n\tprint(x)\n};";
+        let rendered = render_diagnostic(source, 3, 10, "Expected ';' after expression statement.");
+        assert_eq!(
+            rendered,
+            "error: Expected ';' after expression statement.\n --> 3:10\n  |\n3 | \tprint(x)\n  | \t        ^\n"
+        );
+
+        // Une colonne en fin de ligne place le `^` juste après le dernier caractère
+        let rendered = render_diagnostic("print(1)", 1, 9, "Missing ';'.");
+        assert!(rendered.ends_with("1 | print(1)\n  |         ^\n"));
+
+        // Hors du source, seul le message est affiché
+        assert_eq!(render_diagnostic(source, 9, 1, "Oops."), "error: Oops.\n");
+        assert_eq!(render_diagnostic(source, 0, 0, "Oops."), "error: Oops.\n");
+    }
+
+    #[test]
+    fn test_void_function_without_return() {
+        let source = read_source_file("function/function_void_no_return.own");
+        let analyzer = analyze_source(&source);
+        assert!(analyzer.errors.is_empty(), "Unexpected errors: {:?}", analyzer.errors);
+        assert!(analyzer.warnings.is_empty(), "Unexpected warnings: {:?}", analyzer.warnings);
+    }
+
+    #[test]
+    fn test_for_counter_after_loop() {
+        // Le compteur est local à la boucle : le lire ensuite, même dans une initialisation,
+        // est une erreur sémantique et non une globale introuvable à l'édition de liens
+        let bad_source = read_source_file("for/for_counter_after_loop_bad.own");
+        let bad_analyzer = analyze_source(&bad_source);
+        assert_eq!(bad_analyzer.errors, vec!["Undefined variable 'i'.".to_string()]);
+
+        // Même chose dans une valeur affectée et dans un retour de fonction
+        let bad_analyzer = analyze_source(
+            "let x: int = 0;\nfor (let j: int = 0; j < 2; j = j + 1;) {\n    x = j;\n};\nx = j * 2;\nfunction f(): int {\n    return j;\n}",
+        );
+        assert_eq!(
+            bad_analyzer.errors,
+            vec![
+                "Undefined variable 'j'.".to_string(),
+                "Undefined variable 'j'.".to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn test_array_literal() {
+        let good_source = read_source_file("array/array_literal_good.own");
+        let good_analyzer = analyze_source(&good_source);
+        assert!(
+            good_analyzer.errors.is_empty(),
+            "No semantic errors expected in array_literal_good.own, got: {:?}",
+            good_analyzer.errors
+        );
+
+        let bad_source = read_source_file("array/array_literal_bad.own");
+        let bad_analyzer = analyze_source(&bad_source);
+        assert_eq!(
+            bad_analyzer.errors,
+            vec![
+                "Array 'xs' has 3 elements, but its literal provides 2.".to_string(),
+                "Type mismatch in array literal of 'ys': expected 'int', found 'string'.".to_string(),
+                "An array literal can only initialize an array variable.".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_calls_to_undefined_functions() {
+        // Chaque appel d'une fonction jamais définie est signalé une fois, après l'analyse
+        let bad_source = read_source_file("function/function_undefined_call_bad.own");
+        let bad_analyzer = analyze_source(&bad_source);
+        assert_eq!(
+            bad_analyzer.errors,
+            vec![
+                "Call to undefined function 'compute'.".to_string(),
+                "Call to undefined function 'compute'.".to_string(),
+                "Call to undefined function 'log_value'.".to_string(),
+            ]
+        );
+
+        // Une fonction définie plus loin n'est pas encore visible à l'endroit de l'appel
+        let forward_analyzer =
+            analyze_source("print(later(2));\nfunction later(n: int): int {\n    return n * 2;\n}");
+        assert_eq!(
+            forward_analyzer.errors,
+            vec!["Function 'later' is called before its declaration.".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_readint_builtin() {
+        let good_source = read_source_file("readint/readint_good.own");
+        let good_analyzer = analyze_source(&good_source);
+        assert!(
+            good_analyzer.errors.is_empty(),
+            "No semantic errors expected in readint_good.own, got: {:?}",
+            good_analyzer.errors
+        );
+
+        // `readint` renvoie un entier et ne prend aucun argument
+        let bad_analyzer = analyze_source("let s: string = readint();\nprint(readint(1));");
+        assert_eq!(
+            bad_analyzer.errors,
+            vec![
+                "Type mismatch in variable declaration 's': expected 'string', found 'int'.".to_string(),
+                "Function 'readint' expects 0 arguments, but 1 were provided.".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_self_assignment_warning() {
+        let source = read_source_file("var_affection/var_affection_self.own");
+        let mut analyzer = SemanticAnalyzer::new(source);
+        let diagnostics = analyzer.analyze();
+        assert!(analyzer.errors.is_empty(), "Unexpected errors: {:?}", analyzer.errors);
+        assert_eq!(
+            diagnostics,
+            vec![Diagnostic::warning("Self-assignment of 'x' has no effect.".to_string())]
+        );
+
+        // Un élément de tableau ou une expression qui contient la variable n'est pas signalé ;
+        // des parenthèses seules ne changent rien à l'affectation
+        let analyzer = analyze_source("let xs: int[2];\nlet x: int = 1;\nxs[0] = xs[0];\nx = x + 0;\nx = (x);");
+        assert_eq!(analyzer.warnings, vec!["Self-assignment of 'x' has no effect.".to_string()]);
+    }
+
+    #[test]
+    fn test_todo_builtin() {
+        let source = read_source_file("todo/todo_good.own");
+        let analyzer = analyze_source(&source);
+        assert!(analyzer.errors.is_empty(), "Unexpected errors: {:?}", analyzer.errors);
+        assert!(analyzer.warnings.is_empty(), "Unexpected warnings: {:?}", analyzer.warnings);
+
+        // Une branche qui se termine par `todo()` ne rend pas la main, comme après un `return`
+        let analyzer = analyze_source(
+            "let ready: bool = true;\nlet x: int;\nif (ready) {\n    x = 1;\n} else {\n    todo();\n};\nprint(x);",
+        );
+        assert!(analyzer.errors.is_empty(), "Unexpected errors: {:?}", analyzer.errors);
+    }
+
+    #[test]
+    fn test_negative_array_index() {
+        let good_source = read_source_file("array/array_negative_index.own");
+        let good_analyzer = analyze_source(&good_source);
+        assert!(
+            good_analyzer.errors.is_empty(),
+            "No semantic errors expected in array_negative_index.own, got: {:?}",
+            good_analyzer.errors
+        );
+
+        // Un index constant négatif ne peut pas remonter avant le premier élément
+        let bad_source = read_source_file("array/array_negative_index_bad.own");
+        let bad_analyzer = analyze_source(&bad_source);
+        assert_eq!(
+            bad_analyzer.errors,
+            vec![
+                "Index -5 is out of bounds for array 'xs' of length 4.".to_string(),
+                "Index -8 is out of bounds for array 'xs' of length 4.".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parameter_shadowing_a_constant_is_not_constant() {
+        // `LOW` désigne le paramètre dans la fonction : ni l'index ni le cas ne valent -9
+        let analyzer = analyze_source(
+            "const LOW: int = -9;\n\
+             function f(LOW: int): int {\n    let xs: int[4];\n    print(xs[LOW]);\n    switch (0) {\n        case -9 {\n            return 1;\n        },\n        case LOW {\n            return 2;\n        },\n    };\n    return 0;\n}\n\
+             print(f(0));",
+        );
+        assert!(analyzer.errors.is_empty(), "Unexpected errors: {:?}", analyzer.errors);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,177 @@
+// semantic/const_eval.rs
+
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::lex::models::name::Name;
+use crate::parser::models::expression::Expression;
+
+/// Valeur d'une expression constante évaluée à la compilation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ConstValue {
+    Int(i64),
+    Float(f64),
+    Bool(bool),
+}
+
+/// Raison pour laquelle une expression n'a pas pu être évaluée à la compilation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ConstError {
+    /// L'expression contient un élément non constant (variable, appel, chaîne...).
+    NotConstant,
+    /// Division ou modulo par zéro.
+    DivisionByZero,
+    /// Le résultat entier dépasse 64 bits.
+    Overflow,
+    /// Puissance entière d'exposant négatif.
+    NegativeExponent,
+}
+
+impl fmt::Display for ConstError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConstError::NotConstant => write!(f, "Expression is not a compile-time constant."),
+            ConstError::DivisionByZero => write!(f, "Division by zero in constant expression."),
+            ConstError::Overflow => write!(f, "Integer overflow in constant expression."),
+            ConstError::NegativeExponent => write!(f, "Negative exponent in integer power."),
+        }
+    }
+}
+
+/// Évalue une expression constante formée de littéraux et d'opérateurs.
+///
+/// Retourne `None` si l'expression n'est pas constante ou si son évaluation échoue ;
+/// `try_eval_const` indique la raison de l'échec.
+pub fn eval_const(expr: &Expression) -> Option<ConstValue> {
+    try_eval_const(expr, &HashMap::new()).ok()
+}
+
+/// Évalue une expression entière constante à la compilation.
+///
+/// Seuls les littéraux, les constantes que `lookup` associe à un nom et les opérateurs sont
+/// acceptés. Retourne `None` si l'expression n'est pas une constante entière ou si son
+/// évaluation échoue (division par zéro, dépassement).
+pub fn eval_const_int(expr: &Expression, lookup: &dyn Fn(Name) -> Option<ConstValue>) -> Option<i64> {
+    match try_eval_const_with(expr, lookup) {
+        Ok(ConstValue::Int(value)) => Some(value),
+        _ => None,
+    }
+}
+
+/// Évalue une expression constante, les identifiants étant résolus dans `constants`.
+pub fn try_eval_const(
+    expr: &Expression,
+    constants: &HashMap<Name, ConstValue>,
+) -> Result<ConstValue, ConstError> {
+    try_eval_const_with(expr, &|name| constants.get(&name).copied())
+}
+
+/// Évalue une expression constante, chaque identifiant étant résolu par `lookup`.
+///
+/// `lookup` ne doit retourner une valeur que si le nom désigne bien une constante à l'endroit
+/// de l'expression : un paramètre ou une variable locale qui masque une constante globale de
+/// même nom n'est pas constant.
+pub fn try_eval_const_with(
+    expr: &Expression,
+    lookup: &dyn Fn(Name) -> Option<ConstValue>,
+) -> Result<ConstValue, ConstError> {
+    match expr {
+        Expression::Int(value) => Ok(ConstValue::Int(*value)),
+        Expression::Float(value) => Ok(ConstValue::Float(*value)),
+        Expression::Bool(value) => Ok(ConstValue::Bool(*value)),
+        Expression::Ident(name) => lookup(*name).ok_or(ConstError::NotConstant),
+        Expression::Unary(unary_expr) => {
+            let operand = try_eval_const_with(&unary_expr.operand, lookup)?;
+            match (unary_expr.op.as_str(), operand) {
+                ("-", ConstValue::Int(value)) => {
+                    value.checked_neg().map(ConstValue::Int).ok_or(ConstError::Overflow)
+                }
+                ("-", ConstValue::Float(value)) => Ok(ConstValue::Float(-value)),
+                ("+", ConstValue::Int(_) | ConstValue::Float(_)) => Ok(operand),
+                ("!", ConstValue::Bool(value)) => Ok(ConstValue::Bool(!value)),
+                _ => Err(ConstError::NotConstant),
+            }
+        }
+        // `len` d'un littéral : sa longueur en octets est connue à la compilation
+        Expression::FunctionCall(call) if call.name == "len" => match call.arguments.as_slice() {
+            [Expression::Str(literal)] => Ok(ConstValue::Int(literal.len() as i64)),
+            _ => Err(ConstError::NotConstant),
+        },
+        Expression::Binary(bin_expr) => {
+            let left = try_eval_const_with(&bin_expr.left, lookup)?;
+            // Comme à l'exécution, l'opérande droit de `&&`/`||` n'est pas évalué quand le
+            // gauche suffit : `false && 1 / 0 == 0` vaut `false`
+            match (bin_expr.op.as_str(), left) {
+                ("&&", ConstValue::Bool(false)) | ("||", ConstValue::Bool(true)) => return Ok(left),
+                _ => {}
+            }
+            let right = try_eval_const_with(&bin_expr.right, lookup)?;
+            eval_binary(&bin_expr.op, left, right)
+        }
+        Expression::Comparison(chain) => {
+            let mut left = try_eval_const_with(&chain.operands[0], lookup)?;
+            let mut result = true;
+            for (op, operand) in chain.ops.iter().zip(&chain.operands[1..]) {
+                let right = try_eval_const_with(operand, lookup)?;
+                match eval_binary(op, left, right)? {
+                    ConstValue::Bool(holds) => result &= holds,
+                    _ => return Err(ConstError::NotConstant),
+                }
+                left = right;
+            }
+            Ok(ConstValue::Bool(result))
+        }
+        _ => Err(ConstError::NotConstant),
+    }
+}
+
+/// Applique un opérateur binaire à deux valeurs constantes de même type.
+fn eval_binary(op: &str, left: ConstValue, right: ConstValue) -> Result<ConstValue, ConstError> {
+    match (left, right) {
+        (ConstValue::Int(l), ConstValue::Int(r)) => {
+            let checked = match op {
+                "+" => l.checked_add(r),
+                "-" => l.checked_sub(r),
+                "*" => l.checked_mul(r),
+                "/" | "%" if r == 0 => return Err(ConstError::DivisionByZero),
+                "/" => l.checked_div(r),
+                "%" => l.checked_rem(r),
+                "**" if r < 0 => return Err(ConstError::NegativeExponent),
+                "**" => u32::try_from(r).ok().and_then(|r| l.checked_pow(r)),
+                _ => return compare(op, l, r),
+            };
+            checked.map(ConstValue::Int).ok_or(ConstError::Overflow)
+        }
+        (ConstValue::Float(l), ConstValue::Float(r)) => match op {
+            "+" => Ok(ConstValue::Float(l + r)),
+            "-" => Ok(ConstValue::Float(l - r)),
+            "*" => Ok(ConstValue::Float(l * r)),
+            "/" if r == 0.0 => Err(ConstError::DivisionByZero),
+            "/" => Ok(ConstValue::Float(l / r)),
+            "**" => Ok(ConstValue::Float(l.powf(r))),
+            _ => compare(op, l, r),
+        },
+        (ConstValue::Bool(l), ConstValue::Bool(r)) => match op {
+            "&&" => Ok(ConstValue::Bool(l && r)),
+            "||" => Ok(ConstValue::Bool(l || r)),
+            "==" => Ok(ConstValue::Bool(l == r)),
+            "!=" => Ok(ConstValue::Bool(l != r)),
+            _ => Err(ConstError::NotConstant),
+        },
+        _ => Err(ConstError::NotConstant),
+    }
+}
+
+/// Évalue un opérateur de comparaison entre deux valeurs numériques.
+fn compare<T: PartialOrd>(op: &str, left: T, right: T) -> Result<ConstValue, ConstError> {
+    let result = match op {
+        "==" => left == right,
+        "!=" => left != right,
+        "<" => left < right,
+        "<=" => left <= right,
+        ">" => left > right,
+        ">=" => left >= right,
+        _ => return Err(ConstError::NotConstant),
+    };
+    Ok(ConstValue::Bool(result))
+}
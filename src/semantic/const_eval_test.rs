@@ -0,0 +1,85 @@
+#[cfg(test)]
+mod const_eval_tests {
+    use std::collections::HashMap;
+
+    use crate::lex::models::name::Name;
+    use crate::parser::models::expression::Expression;
+    use crate::parser::models::statement::Statement;
+    use crate::parser::parser::Parser;
+    use crate::semantic::const_eval::{eval_const, eval_const_int, try_eval_const, ConstError, ConstValue};
+
+    // Helper function parsing the initializer of `let x: int = <expr>;`
+    fn parse_expression(source: &str) -> Expression {
+        let mut parser = Parser::new(format!("let x: int = {};", source));
+        let ast = parser.parse_file();
+        match ast.statements.into_iter().next() {
+            Some(Statement::VarDeclaration(decl)) => decl.init.expect("Missing initializer"),
+            other => panic!("Expected a variable declaration, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_integer_arithmetic() {
+        assert_eq!(eval_const(&parse_expression("2 + 3 * 4")), Some(ConstValue::Int(14)));
+        assert_eq!(eval_const(&parse_expression("-(7 % 4)")), Some(ConstValue::Int(-3)));
+        assert_eq!(eval_const(&parse_expression("1.5 * 2.0")), Some(ConstValue::Float(3.0)));
+        assert_eq!(eval_const(&parse_expression("len(\"hello\") + 1")), Some(ConstValue::Int(6)));
+    }
+
+    #[test]
+    fn test_boolean_operators() {
+        assert_eq!(eval_const(&parse_expression("true && false")), Some(ConstValue::Bool(false)));
+        assert_eq!(eval_const(&parse_expression("!(1 < 2) || 3 == 3")), Some(ConstValue::Bool(true)));
+    }
+
+    #[test]
+    fn test_short_circuit() {
+        // The right operand is not evaluated when the left one decides the result
+        assert_eq!(eval_const(&parse_expression("false && 1 / 0 == 0")), Some(ConstValue::Bool(false)));
+        assert_eq!(eval_const(&parse_expression("true || 1 / 0 == 0")), Some(ConstValue::Bool(true)));
+        assert_eq!(
+            try_eval_const(&parse_expression("true && 1 / 0 == 0"), &HashMap::new()),
+            Err(ConstError::DivisionByZero)
+        );
+        assert_eq!(
+            try_eval_const(&parse_expression("false || 1 / 0 == 0"), &HashMap::new()),
+            Err(ConstError::DivisionByZero)
+        );
+    }
+
+    #[test]
+    fn test_division_by_zero() {
+        let expr = parse_expression("1 / 0");
+        assert_eq!(eval_const(&expr), None);
+        assert_eq!(try_eval_const(&expr, &HashMap::new()), Err(ConstError::DivisionByZero));
+        assert_eq!(
+            ConstError::DivisionByZero.to_string(),
+            "Division by zero in constant expression."
+        );
+    }
+
+    #[test]
+    fn test_non_constant_expressions() {
+        let mut constants = HashMap::new();
+        constants.insert(Name::intern("N"), ConstValue::Int(8));
+        assert_eq!(eval_const_int(&parse_expression("N * 2"), &|name| constants.get(&name).copied()), Some(16));
+        assert_eq!(eval_const(&parse_expression("N * 2")), None);
+        assert_eq!(eval_const(&parse_expression("1 + true")), None);
+        assert_eq!(eval_const_int(&parse_expression("1 < 2"), &|name| constants.get(&name).copied()), None);
+    }
+
+    #[test]
+    fn test_power() {
+        assert_eq!(eval_const(&parse_expression("2 ** 10")), Some(ConstValue::Int(1024)));
+        assert_eq!(eval_const(&parse_expression("2 ** 3 ** 2")), Some(ConstValue::Int(512)));
+        assert_eq!(eval_const(&parse_expression("4.0 ** 0.5")), Some(ConstValue::Float(2.0)));
+        assert_eq!(
+            try_eval_const(&parse_expression("2 ** -1"), &HashMap::new()),
+            Err(ConstError::NegativeExponent)
+        );
+        assert_eq!(
+            try_eval_const(&parse_expression("2 ** 64"), &HashMap::new()),
+            Err(ConstError::Overflow)
+        );
+    }
+}
\ No newline at end of file
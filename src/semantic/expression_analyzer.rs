@@ -1,6 +1,7 @@
 // semantic/expression_analyzer.rs
 
 use crate::parser::models::expression::Expression;
+use crate::semantic::const_eval::{ConstError, ConstValue};
 use crate::semantic::models::semantic::SymbolType;
 use crate::semantic::analyzer::SemanticAnalyzer;
 
@@ -11,14 +12,62 @@ pub trait ExpressionAnalyzer {
 
 impl ExpressionAnalyzer for SemanticAnalyzer {
     fn get_expression_type(&mut self, expr: &Expression) -> Option<String> {
+        // Chaque type calculé est conservé pour le générateur de code
+        let type_name = self.infer_expression_type(expr);
+        if let Some(type_name) = &type_name {
+            self.type_context.record(expr, type_name.clone());
+        }
+        type_name
+    }
+}
+
+impl SemanticAnalyzer {
+    /// Calcule le type d'une expression ; les sous-expressions passent par
+    /// `get_expression_type` et sont donc enregistrées elles aussi.
+    fn infer_expression_type(&mut self, expr: &Expression) -> Option<String> {
         match expr {
+            Expression::Ident(name) if self.is_type_defined(name) => {
+                self.errors.push(format!("Type '{}' cannot be used as a value.", name));
+                None
+            }
+            // Les noms que `parse::<f64>()` lit comme l'infini ou NaN (`inf`, `Infinity`, `NAN`...)
+            Expression::Ident(name)
+                if self.symbol_table.resolve(*name).is_none()
+                    && name.parse::<f64>().is_ok_and(|value| !value.is_finite()) =>
+            {
+                self.errors.push(format!(
+                    "Special float value '{}' is not supported.",
+                    name
+                ));
+                None
+            }
+            Expression::Ident(name) if self.symbol_table.resolve(*name).is_none() => {
+                // Un nom hors de portée (comme le compteur d'une boucle `for` terminée) serait
+                // sinon lu comme une globale inexistante, et l'édition de liens échouerait
+                self.errors.push(format!("Undefined variable '{}'.", name));
+                None
+            }
             Expression::Ident(name) => {
-                self.symbol_table.resolve(name).map(|symbol| match &symbol.symbol_type {
-                    SymbolType::Variable(type_name) => type_name.clone(),
+                if self.symbol_table.is_unassigned(*name) {
+                    self.errors.push(format!(
+                        "Variable '{}' may be used before being assigned.",
+                        name
+                    ));
+                }
+                self.symbol_table.resolve(*name).map(|symbol| match &symbol.symbol_type {
+                    SymbolType::Variable(type_name) | SymbolType::Constant(type_name) => {
+                        type_name.clone()
+                    }
                     SymbolType::Function { return_type, .. } => return_type.clone(),
                 })
             },
             Expression::Int(_) => Some("int".to_string()),
+            // Un littéral trop grand est arrondi à l'infini par `parse::<f64>()`
+            Expression::Float(val) if !val.is_finite() => {
+                self.errors
+                    .push("Float literal is out of range for a 64-bit float.".to_string());
+                None
+            }
             Expression::Float(_) => Some("float".to_string()),
             Expression::Bool(_) => Some("bool".to_string()),
             Expression::Str(_) => Some("string".to_string()),
@@ -26,6 +75,17 @@ impl ExpressionAnalyzer for SemanticAnalyzer {
                 let left_type = self.get_expression_type(&bin_expr.left)?;
                 let right_type = self.get_expression_type(&bin_expr.right)?;
 
+                // Une comparaison entre types différents n'est jamais vraie ou fausse par
+                // conversion implicite : le message le dit, plutôt qu'une erreur arithmétique
+                if left_type != right_type
+                    && matches!(bin_expr.op.as_str(), "==" | "!=" | "<" | "<=" | ">" | ">=")
+                {
+                    self.errors.push(format!(
+                        "Cannot compare '{}' with '{}'; consider converting.",
+                        left_type, right_type
+                    ));
+                    return None;
+                }
                 if left_type != right_type {
                     self.errors.push(format!(
                         "Type mismatch in binary expression: '{}' and '{}'.",
@@ -36,8 +96,66 @@ impl ExpressionAnalyzer for SemanticAnalyzer {
 
 
                 match bin_expr.op.as_str() {
-                    "+" | "-" | "*" | "/" => Some(left_type.clone()),
-                    "==" | "!=" | "<" | "<=" | ">" | ">=" => Some("bool".to_string()),
+                    "+" | "-" | "*" | "/" => {
+                        if left_type != "int" && left_type != "float" {
+                            self.errors.push(format!(
+                                "Operator '{}' requires numeric operands, found '{}'.",
+                                bin_expr.op, left_type
+                            ));
+                            return None;
+                        }
+                        Some(left_type.clone())
+                    }
+                    "**" => {
+                        if left_type != "int" && left_type != "float" {
+                            self.errors.push(format!(
+                                "Operator '**' requires numeric operands, found '{}'.",
+                                left_type
+                            ));
+                            return None;
+                        }
+                        // Un exposant entier négatif connu à la compilation est refusé ; à
+                        // l'exécution, il donne 0
+                        if let Ok(ConstValue::Int(exponent)) = self.const_value(&bin_expr.right) {
+                            if exponent < 0 {
+                                self.errors.push(ConstError::NegativeExponent.to_string());
+                                return None;
+                            }
+                        }
+                        Some(left_type.clone())
+                    }
+                    "%" => {
+                        // Le modulo est calculé par `idiv`, il n'a de sens que sur des entiers
+                        if left_type != "int" {
+                            self.errors.push(format!(
+                                "Operator '%' requires 'int' operands, found '{}'.",
+                                left_type
+                            ));
+                            return None;
+                        }
+                        Some(left_type.clone())
+                    }
+                    "==" | "!=" => Some("bool".to_string()),
+                    "&&" | "||" => {
+                        if left_type != "bool" {
+                            self.errors.push(format!(
+                                "Operator '{}' requires 'bool' operands, found '{}'.",
+                                bin_expr.op, left_type
+                            ));
+                            return None;
+                        }
+                        Some("bool".to_string())
+                    }
+                    "<" | "<=" | ">" | ">=" => {
+                        if left_type == "bool" {
+                            self.errors.push(format!(
+                                "Ordering comparison not allowed on '{}'.",
+                                left_type
+                            ));
+                            return None;
+                        }
+                        Some("bool".to_string())
+                    }
                     _ => {
                         self.errors.push(format!(
                             "Unknown binary operator '{}'.",
@@ -47,8 +165,81 @@ impl ExpressionAnalyzer for SemanticAnalyzer {
                     }
                 }
             },
+            Expression::Comparison(chain) => {
+                // Chaque opérande n'est typé qu'une fois, puis chaque paire voisine est vérifiée
+                // comme une comparaison binaire
+                let mut operand_types = Vec::new();
+                for operand in &chain.operands {
+                    operand_types.push(self.get_expression_type(operand)?);
+                }
+                for pair in operand_types.windows(2) {
+                    if pair[0] != pair[1] {
+                        self.errors.push(format!(
+                            "Cannot compare '{}' with '{}'; consider converting.",
+                            pair[0], pair[1]
+                        ));
+                        return None;
+                    }
+                }
+                if operand_types[0] == "bool" {
+                    self.errors.push(format!(
+                        "Ordering comparison not allowed on '{}'.",
+                        operand_types[0]
+                    ));
+                    return None;
+                }
+                Some("bool".to_string())
+            }
+            // Un tableau n'est pas une valeur : son littéral n'est accepté que par
+            // `analyze_var_declaration`, qui vérifie chaque élément
+            Expression::ArrayLiteral(_) => {
+                self.errors
+                    .push("An array literal can only initialize an array variable.".to_string());
+                None
+            }
+            Expression::Unary(unary_expr) => {
+                let operand_type = self.get_expression_type(&unary_expr.operand)?;
+                match unary_expr.op.as_str() {
+                    "-" | "+" if operand_type == "int" || operand_type == "float" => {
+                        Some(operand_type)
+                    }
+                    "-" | "+" => {
+                        self.errors.push(format!(
+                            "Operator '{}' requires a numeric operand, found '{}'.",
+                            unary_expr.op, operand_type
+                        ));
+                        None
+                    }
+                    "!" if operand_type == "bool" => Some(operand_type),
+                    "!" => {
+                        self.errors.push(format!(
+                            "Operator '!' requires a 'bool' operand, found '{}'.",
+                            operand_type
+                        ));
+                        None
+                    }
+                    _ => {
+                        self.errors.push(format!(
+                            "Unknown unary operator '{}'.",
+                            unary_expr.op
+                        ));
+                        None
+                    }
+                }
+            }
+            Expression::FunctionCall(call) if call.name == "len" && call.arguments.len() == 1 => {
+                // `len` mesure une chaîne avec strlen : tout autre type est refusé
+                let arg_type = self.get_expression_type(&call.arguments[0])?;
+                if arg_type != "string" {
+                    self.errors.push(format!(
+                        "Function 'len' expects a 'string' argument, found '{}'.",
+                        arg_type
+                    ));
+                }
+                Some("int".to_string())
+            }
             Expression::FunctionCall(call) => {
-                if let Some(symbol) = self.symbol_table.resolve(&call.name) {
+                if let Some(symbol) = self.symbol_table.resolve(call.name) {
                     match &symbol.symbol_type {
                         SymbolType::Function { return_type, .. } => Some(return_type.clone()),
                         _ => {
@@ -57,18 +248,77 @@ impl ExpressionAnalyzer for SemanticAnalyzer {
                         }
                     }
                 } else {
-                    self.errors.push(format!("Undefined function '{}'.", call.name));
+                    // Signalé par `check_unresolved_calls`, une fois tout le programme analysé
+                    self.unresolved_calls.push(call.name);
                     None
                 }
             },
-            // Gérer d'autres types d'expressions si nécessaire
-            _ => {
+            Expression::Index(index_expr) => {
+                let target_type = self.get_expression_type(&index_expr.target)?;
+                let index_type = self.get_expression_type(&index_expr.index)?;
+                if index_type != "int" {
+                    self.errors.push(format!(
+                        "Array index must be of type 'int', found '{}'.",
+                        index_type
+                    ));
+                    return None;
+                }
+                // Indexer une chaîne donne l'octet à cette position, sous forme d'entier
+                if target_type == "string" {
+                    return Some("int".to_string());
+                }
+                let target = index_expr.target.to_string();
+                self.check_index_from_end(&target, &target_type, &index_expr.index);
+                match element_type(&target_type) {
+                    Some(element_type) => Some(element_type.to_string()),
+                    None => {
+                        self.errors.push(format!(
+                            "Cannot index into a value of type '{}'.",
+                            target_type
+                        ));
+                        None
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl SemanticAnalyzer {
+    /// Vérifie un index constant négatif, qui compte à partir de la fin du tableau (`xs[-1]`
+    /// est le dernier élément) : il ne doit pas remonter avant le premier élément.
+    pub(crate) fn check_index_from_end(
+        &mut self,
+        name: &str,
+        array_type: &str,
+        index: &Expression,
+    ) {
+        let Some(length) = array_length(array_type) else {
+            return;
+        };
+        if let Ok(ConstValue::Int(index)) = self.const_value(index) {
+            if index < -length {
                 self.errors.push(format!(
-                    "Unsupported expression type: {:?}.",
-                    expr
+                    "Index {} is out of bounds for array '{}' of length {}.",
+                    index, name, length
                 ));
-                None
             }
         }
     }
 }
+
+/// Retourne la longueur d'un type tableau (`int[8]` -> 8), ou `None` sinon.
+pub fn array_length(type_name: &str) -> Option<i64> {
+    type_name
+        .strip_suffix(']')
+        .and_then(|t| t.split_once('['))
+        .and_then(|(_, length)| length.parse().ok())
+}
+
+/// Retourne le type des éléments d'un type tableau (`int[8]` -> `int`), ou `None` sinon.
+pub fn element_type(type_name: &str) -> Option<&str> {
+    type_name
+        .strip_suffix(']')
+        .and_then(|t| t.split_once('['))
+        .map(|(element, _)| element)
+}
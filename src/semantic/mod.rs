@@ -1,5 +1,9 @@
 pub mod analyzer;
 pub mod analyzer_test;
+pub mod const_eval;
+pub mod const_eval_test;
 pub mod expression_analyzer;
 pub mod statement_analyzer;
-pub mod models;
\ No newline at end of file
+pub mod models;
+pub mod simplify;
+pub mod simplify_test;
\ No newline at end of file
@@ -0,0 +1,67 @@
+use std::fmt;
+
+/// Gravité d'un diagnostic : seules les erreurs interrompent la compilation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+/// Message produit par l'analyse, accompagné de sa gravité.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub message: String,
+}
+
+impl Diagnostic {
+    /// Crée un diagnostic d'erreur.
+    pub fn error(message: String) -> Self {
+        Diagnostic { severity: Severity::Error, message }
+    }
+
+    /// Crée un avertissement.
+    pub fn warning(message: String) -> Self {
+        Diagnostic { severity: Severity::Warning, message }
+    }
+
+    /// Indique si le diagnostic doit interrompre la compilation.
+    pub fn is_error(&self) -> bool {
+        self.severity == Severity::Error
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.severity {
+            Severity::Error => write!(f, "error: {}", self.message),
+            Severity::Warning => write!(f, "warning: {}", self.message),
+        }
+    }
+}
+
+/// Met en forme un message à la manière de rustc : le message, sa position, puis la ligne
+/// `line` de `source` avec un `^` sous la colonne `column` (toutes deux comptées à partir
+/// de 1, la colonne en octets). Une position hors du source n'affiche que le message.
+pub fn render_diagnostic(source: &str, line: usize, column: usize, message: &str) -> String {
+    let mut rendered = format!("error: {}\n", message);
+    let Some(text) = line.checked_sub(1).and_then(|index| source.lines().nth(index)) else {
+        return rendered;
+    };
+    let text = text.strip_suffix('\r').unwrap_or(text);
+    let gutter = " ".repeat(line.to_string().len());
+    rendered.push_str(&format!("{}--> {}:{}\n", gutter, line, column));
+    rendered.push_str(&format!("{} |\n", gutter));
+    rendered.push_str(&format!("{} | {}\n", line, text));
+
+    // Le décalage reprend les tabulations de la ligne pour que le `^` reste aligné
+    let before = column.saturating_sub(1);
+    let mut padding: String = text
+        .bytes()
+        .take(before)
+        .map(|byte| if byte == b'\t' { '\t' } else { ' ' })
+        .collect();
+    padding.push_str(&" ".repeat(before.saturating_sub(text.len())));
+    rendered.push_str(&format!("{} | {}^\n", gutter, padding));
+    rendered
+}
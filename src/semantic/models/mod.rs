@@ -1 +1,3 @@
-pub mod semantic;
\ No newline at end of file
+pub mod diagnostic;
+pub mod semantic;
+pub mod type_context;
\ No newline at end of file
@@ -1,9 +1,11 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+
+use crate::lex::models::name::Name;
 
 /// Représente un symbole dans la table des symboles.
 #[derive(Debug, Clone)]
 pub struct Symbol {
-    pub name: String,
+    pub name: Name,
     pub symbol_type: SymbolType,
 }
 
@@ -11,45 +13,163 @@ pub struct Symbol {
 #[derive(Debug, Clone)]
 pub enum SymbolType {
     Variable(String),
+    Constant(String),
     Function {
         parameters: Vec<String>,
         return_type: String,
     },
 }
 
-/// Représente une table de symboles avec un environnement parent pour gérer les scopes.
+/// Un niveau de portée : les symboles qui y sont définis et leur état d'affectation.
+/// Les noms sont internés : une recherche compare des identifiants entiers.
+#[derive(Debug, Clone, Default)]
+struct Scope {
+    symbols: HashMap<Name, Symbol>,
+    /// Variables de ce scope déclarées sans valeur et pas encore affectées.
+    unassigned: HashSet<Name>,
+}
+
+/// Représente la table des symboles sous forme de pile de scopes.
+///
+/// Le premier niveau est le scope global ; entrer ou sortir d'un bloc empile ou dépile un
+/// seul niveau, sans copier les scopes englobants.
 #[derive(Debug, Clone)]
 pub struct SymbolTable {
-    pub symbols: HashMap<String, Symbol>,
-    pub parent: Option<Box<SymbolTable>>,
+    scopes: Vec<Scope>,
+}
+
+impl Default for SymbolTable {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl SymbolTable {
-    /// Crée une nouvelle table de symboles.
-    pub fn new(parent: Option<Box<SymbolTable>>) -> Self {
+    /// Crée une nouvelle table de symboles ne contenant que le scope global.
+    pub fn new() -> Self {
         SymbolTable {
-            symbols: HashMap::new(),
-            parent,
+            scopes: vec![Scope::default()],
+        }
+    }
+
+    /// Empile un nouveau scope vide.
+    pub fn enter_scope(&mut self) {
+        self.scopes.push(Scope::default());
+    }
+
+    /// Dépile le scope courant ; le scope global n'est jamais retiré.
+    pub fn exit_scope(&mut self) {
+        if self.scopes.len() > 1 {
+            self.scopes.pop();
         }
     }
 
-    /// Définit un nouveau symbole dans la table courante.
-    pub fn define(&mut self, name: String, symbol: Symbol) -> Result<(), String> {
-        if self.symbols.contains_key(&name) {
+    /// Indique si le scope courant est le scope global.
+    pub fn is_global(&self) -> bool {
+        self.scopes.len() == 1
+    }
+
+    /// Nombre de scopes empilés, scope global compris.
+    pub fn depth(&self) -> usize {
+        self.scopes.len()
+    }
+
+    fn current(&self) -> &Scope {
+        self.scopes.last().expect("the global scope is never popped")
+    }
+
+    fn current_mut(&mut self) -> &mut Scope {
+        self.scopes.last_mut().expect("the global scope is never popped")
+    }
+
+    /// Retourne le scope le plus interne qui définit `name`.
+    fn scope_of(&mut self, name: Name) -> Option<&mut Scope> {
+        self.scopes
+            .iter_mut()
+            .rev()
+            .find(|scope| scope.symbols.contains_key(&name))
+    }
+
+    /// Définit un nouveau symbole dans le scope courant.
+    pub fn define(&mut self, name: Name, symbol: Symbol) -> Result<(), String> {
+        let scope = self.current_mut();
+        if scope.symbols.contains_key(&name) {
             return Err(format!("Symbol '{}' already defined in the current scope.", name));
         }
-        self.symbols.insert(name, symbol);
+        scope.symbols.insert(name, symbol);
         Ok(())
     }
 
-    /// Résout un symbole en cherchant dans la table courante et les tables parentes.
-    pub fn resolve(&self, name: &str) -> Option<&Symbol> {
-        if let Some(symbol) = self.symbols.get(name) {
-            return Some(symbol);
+    /// Résout un symbole dans le scope courant uniquement.
+    pub fn resolve_current(&self, name: Name) -> Option<&Symbol> {
+        self.current().symbols.get(&name)
+    }
+
+    /// Résout un symbole en parcourant les scopes du plus interne au scope global.
+    pub fn resolve(&self, name: Name) -> Option<&Symbol> {
+        self.scopes.iter().rev().find_map(|scope| scope.symbols.get(&name))
+    }
+
+    /// Résout un symbole dans les scopes englobants, en ignorant le scope courant.
+    pub fn resolve_outer(&self, name: Name) -> Option<&Symbol> {
+        let outer = &self.scopes[..self.scopes.len() - 1];
+        outer.iter().rev().find_map(|scope| scope.symbols.get(&name))
+    }
+
+    /// Marque une variable du scope courant comme déclarée sans valeur.
+    pub fn declare_unassigned(&mut self, name: Name) {
+        self.current_mut().unassigned.insert(name);
+    }
+
+    /// Indique si la variable désignée par `name` (résolue comme `resolve`) n'a pas encore
+    /// reçu de valeur.
+    pub fn is_unassigned(&self, name: Name) -> bool {
+        self.scopes
+            .iter()
+            .rev()
+            .find(|scope| scope.symbols.contains_key(&name))
+            .is_some_and(|scope| scope.unassigned.contains(&name))
+    }
+
+    /// Marque la variable désignée par `name` comme affectée, dans le scope qui la définit.
+    pub fn mark_assigned(&mut self, name: Name) {
+        if let Some(scope) = self.scope_of(name) {
+            scope.unassigned.remove(&name);
         }
-        if let Some(ref parent) = self.parent {
-            return parent.resolve(name);
+    }
+
+    /// Retourne l'état d'affectation de chaque scope, du scope courant jusqu'au scope global.
+    pub fn unassigned_by_scope(&self) -> Vec<HashSet<Name>> {
+        self.scopes.iter().rev().map(|scope| scope.unassigned.clone()).collect()
+    }
+
+    /// Restaure un état d'affectation obtenu par `unassigned_by_scope` sur la même pile de scopes.
+    pub fn set_unassigned_by_scope(&mut self, levels: Vec<HashSet<Name>>) {
+        for (scope, unassigned) in self.scopes.iter_mut().rev().zip(levels) {
+            scope.unassigned = unassigned;
         }
-        None
     }
-}
+
+    /// Parcourt les noms définis dans le scope courant, sans les scopes englobants.
+    /// L'ordre d'itération n'est pas garanti.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.current().symbols.keys().map(|name| name.as_str())
+    }
+
+    /// Retourne tous les symboles visibles depuis le scope courant.
+    /// Un symbole masqué par une définition plus interne n'est pas retourné ; le résultat
+    /// est trié par nom pour être stable.
+    pub fn all_visible(&self) -> Vec<&Symbol> {
+        let mut seen = HashSet::new();
+        let mut visible = Vec::new();
+        for scope in self.scopes.iter().rev() {
+            for (name, symbol) in &scope.symbols {
+                if seen.insert(*name) {
+                    visible.push(symbol);
+                }
+            }
+        }
+        visible.sort_by(|a, b| a.name.as_str().cmp(b.name.as_str()));
+        visible
+    }
+}
\ No newline at end of file
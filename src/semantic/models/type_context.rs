@@ -0,0 +1,31 @@
+use std::collections::HashMap;
+
+use crate::parser::models::expression::Expression;
+
+/// Types résolus par l'analyse sémantique, transmis au générateur de code.
+///
+/// Une expression est identifiée par son adresse dans l'AST analysé : le contexte n'est donc
+/// valable que pour cet AST, tant qu'il n'est pas cloné, et une réécriture doit le tenir à jour
+/// (comme le fait `simplify_ast`). Une expression construite ailleurs (par le générateur
+/// lui-même, par exemple) n'y figure jamais.
+#[derive(Debug, Clone, Default)]
+pub struct TypeContext {
+    types: HashMap<*const Expression, String>,
+}
+
+impl TypeContext {
+    /// Enregistre le type calculé pour une expression de l'AST.
+    pub fn record(&mut self, expr: &Expression, type_name: String) {
+        self.types.insert(expr as *const Expression, type_name);
+    }
+
+    /// Retire une expression qui quitte l'AST : son adresse pourra servir à une autre.
+    pub fn forget(&mut self, expr: &Expression) {
+        self.types.remove(&(expr as *const Expression));
+    }
+
+    /// Retourne le type d'une expression de l'AST, s'il a été calculé.
+    pub fn get(&self, expr: &Expression) -> Option<&str> {
+        self.types.get(&(expr as *const Expression)).map(String::as_str)
+    }
+}
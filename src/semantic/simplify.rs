@@ -0,0 +1,192 @@
+// semantic/simplify.rs
+
+use crate::parser::models::ast::AST;
+use crate::parser::models::expression::Expression;
+use crate::parser::models::statement::{FunctionDeclaration, Statement, VarDeclaration};
+use crate::semantic::models::type_context::TypeContext;
+
+/// Simplifie les négations `!` d'un AST analysé, avant la génération de code.
+///
+/// `!!x` devient `x`, `!true` devient `false` et la négation d'une comparaison devient la
+/// comparaison inverse (`!(a == b)` -> `a != b`), ce qui économise un test à l'exécution.
+/// `!(a && b)` n'est réécrite en `!a || !b` (De Morgan) que si les deux côtés se simplifient
+/// à leur tour, afin de ne jamais ajouter de négation.
+///
+/// Une réécriture n'a lieu que si elle préserve le sens du programme : l'analyse a vérifié que
+/// l'opérande de `!` est un booléen, et une comparaison d'ordre n'est inversée que sur des
+/// entiers (`types`), `!(a < b)` et `a >= b` différant pour un flottant NaN.
+///
+/// Les nœuds sont réécrits sur place et `types` est tenu à jour : une expression réécrite
+/// occupe la place d'une négation, de type `bool` comme elle, et l'opérande d'une négation
+/// supprimée, qui quitte l'AST avec elle, est retiré du contexte.
+pub fn simplify_ast(ast: &mut AST, types: &mut TypeContext) {
+    simplify_statements(&mut ast.statements, types);
+}
+
+fn simplify_statements(statements: &mut [Statement], types: &mut TypeContext) {
+    for stmt in statements {
+        simplify_statement(stmt, types);
+    }
+}
+
+fn simplify_statement(stmt: &mut Statement, types: &mut TypeContext) {
+    match stmt {
+        Statement::VarDeclaration(var_decl) => simplify_var_declaration(var_decl, types),
+        Statement::VarDeclarations(declarations) => {
+            for var_decl in declarations {
+                simplify_var_declaration(var_decl, types);
+            }
+        }
+        Statement::VarAffection(var_affection) => {
+            if let Some(index) = &mut var_affection.index {
+                simplify_expression(index, types);
+            }
+            simplify_expression(&mut var_affection.value, types);
+        }
+        Statement::Return(return_stmt) => {
+            if let Some(value) = &mut return_stmt.value {
+                simplify_expression(value, types);
+            }
+        }
+        Statement::If(if_stmt) => {
+            simplify_expression(&mut if_stmt.condition, types);
+            simplify_statements(&mut if_stmt.then_branch, types);
+            if let Some(else_branch) = &mut if_stmt.else_branch {
+                simplify_statements(else_branch, types);
+            }
+        }
+        Statement::Switch(switch_stmt) => {
+            simplify_expression(&mut switch_stmt.condition, types);
+            for case in &mut switch_stmt.cases {
+                simplify_statements(&mut case.body, types);
+            }
+            if let Some(default) = &mut switch_stmt.default {
+                simplify_statements(default, types);
+            }
+        }
+        Statement::While(while_stmt) => {
+            simplify_expression(&mut while_stmt.condition, types);
+            simplify_statements(&mut while_stmt.body, types);
+        }
+        Statement::For(for_stmt) => {
+            if let Some(init) = &mut for_stmt.init {
+                simplify_statement(init, types);
+            }
+            if let Some(cond) = &mut for_stmt.cond {
+                simplify_statement(cond, types);
+            }
+            simplify_statements(&mut for_stmt.incr, types);
+            simplify_statements(&mut for_stmt.body, types);
+        }
+        Statement::FunctionDeclaration(func_decl) => simplify_function(func_decl, types),
+        Statement::Module(module) => {
+            for func_decl in &mut module.functions {
+                simplify_function(func_decl, types);
+            }
+        }
+        Statement::ExpressionStatement(expr) => simplify_expression(expr, types),
+        Statement::Break(_) | Statement::Continue(_) => {}
+        Statement::Block(body) => simplify_statements(body, types),
+    }
+}
+
+fn simplify_var_declaration(var_decl: &mut VarDeclaration, types: &mut TypeContext) {
+    if let Some(init) = &mut var_decl.init {
+        simplify_expression(init, types);
+    }
+}
+
+fn simplify_function(func_decl: &mut FunctionDeclaration, types: &mut TypeContext) {
+    simplify_statements(&mut func_decl.body, types);
+}
+
+/// Simplifie une expression, en commençant par ses sous-expressions : `!!(a == b)` devient
+/// ainsi `!(a != b)`, puis `a == b`.
+pub fn simplify_expression(expr: &mut Expression, types: &mut TypeContext) {
+    match expr {
+        Expression::Binary(bin_expr) => {
+            simplify_expression(&mut bin_expr.left, types);
+            simplify_expression(&mut bin_expr.right, types);
+        }
+        Expression::Unary(unary_expr) => simplify_expression(&mut unary_expr.operand, types),
+        Expression::FunctionCall(call) => {
+            for argument in &mut call.arguments {
+                simplify_expression(argument, types);
+            }
+        }
+        Expression::Index(index_expr) => {
+            simplify_expression(&mut index_expr.target, types);
+            simplify_expression(&mut index_expr.index, types);
+        }
+        Expression::Comparison(chain) => {
+            for operand in &mut chain.operands {
+                simplify_expression(operand, types);
+            }
+        }
+        Expression::ArrayLiteral(elements) => {
+            for element in elements {
+                simplify_expression(element, types);
+            }
+        }
+        Expression::Ident(_)
+        | Expression::Int(_)
+        | Expression::Float(_)
+        | Expression::Str(_)
+        | Expression::Bool(_) => {}
+    }
+
+    if let Expression::Unary(unary_expr) = expr {
+        if unary_expr.op == "!" && can_negate(&unary_expr.operand, types) {
+            types.forget(&unary_expr.operand);
+            let mut operand = std::mem::replace(&mut unary_expr.operand, Expression::Bool(false));
+            negate(&mut operand, types);
+            *expr = operand;
+        }
+    }
+}
+
+/// Indique si la négation d'une expression booléenne s'écrit sans ajouter de `!`.
+fn can_negate(expr: &Expression, types: &TypeContext) -> bool {
+    match expr {
+        Expression::Bool(_) => true,
+        Expression::Unary(unary_expr) => unary_expr.op == "!",
+        Expression::Binary(bin_expr) => match bin_expr.op.as_str() {
+            "==" | "!=" => true,
+            "<" | "<=" | ">" | ">=" => types.get(&bin_expr.left) == Some("int"),
+            "&&" | "||" => can_negate(&bin_expr.left, types) && can_negate(&bin_expr.right, types),
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+/// Remplace une expression par sa négation ; `can_negate` doit l'avoir acceptée.
+fn negate(expr: &mut Expression, types: &mut TypeContext) {
+    match expr {
+        Expression::Bool(value) => *value = !*value,
+        Expression::Unary(unary_expr) => {
+            types.forget(&unary_expr.operand);
+            let operand = std::mem::replace(&mut unary_expr.operand, Expression::Bool(false));
+            *expr = operand;
+        }
+        Expression::Binary(bin_expr) => {
+            let inverse = match bin_expr.op.as_str() {
+                "==" => "!=",
+                "!=" => "==",
+                "<" => ">=",
+                "<=" => ">",
+                ">" => "<=",
+                ">=" => "<",
+                "&&" => "||",
+                "||" => "&&",
+                op => unreachable!("Operator '{}' cannot be negated", op),
+            };
+            if matches!(inverse, "&&" | "||") {
+                negate(&mut bin_expr.left, types);
+                negate(&mut bin_expr.right, types);
+            }
+            bin_expr.op = inverse.to_string();
+        }
+        _ => unreachable!("Expression '{}' cannot be negated", expr),
+    }
+}
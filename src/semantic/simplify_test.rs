@@ -0,0 +1,116 @@
+#[cfg(test)]
+mod simplify_tests {
+    use crate::lex::models::name::Name;
+    use crate::parser::models::expression::Expression;
+    use crate::parser::models::statement::Statement;
+    use crate::parser::parser::Parser;
+    use crate::semantic::analyzer::SemanticAnalyzer;
+    use crate::semantic::simplify::simplify_ast;
+
+    // Déclarations communes aux sources testées
+    const PRELUDE: &str = "let x: bool = true;\nlet a: int = 1;\nlet b: int = 2;\nlet f: float = 1.5;\n";
+
+    // Analyse `let r: bool = <expr>;`, simplifie l'AST et retourne l'initialiseur de `r`
+    fn simplify_init(source: &str) -> Expression {
+        let mut analyzer = SemanticAnalyzer::new(format!("{}let r: bool = {};", PRELUDE, source));
+        analyzer.analyze();
+        assert!(analyzer.errors.is_empty(), "Unexpected semantic errors: {:?}", analyzer.errors);
+        simplify_ast(&mut analyzer.ast, &mut analyzer.type_context);
+        last_init(analyzer.ast.statements)
+    }
+
+    // Initialiseur de `let r: bool = <expr>;` tel que le parseur le produit
+    fn parse_init(source: &str) -> Expression {
+        let mut parser = Parser::new(format!("{}let r: bool = {};", PRELUDE, source));
+        last_init(parser.parse_file().statements)
+    }
+
+    fn last_init(statements: Vec<Statement>) -> Expression {
+        match statements.into_iter().last() {
+            Some(Statement::VarDeclaration(decl)) => decl.init.expect("Missing initializer"),
+            other => panic!("Expected a variable declaration, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_double_negation_is_removed() {
+        assert_eq!(simplify_init("!!x"), Expression::Ident(Name::intern("x")));
+        assert_eq!(simplify_init("!!!x"), parse_init("!x"));
+    }
+
+    #[test]
+    fn test_negated_comparison_is_inverted() {
+        assert_eq!(simplify_init("!(a < b)"), parse_init("a >= b"));
+        assert_eq!(simplify_init("!(a >= b)"), parse_init("a < b"));
+        assert_eq!(simplify_init("!(a == b)"), parse_init("a != b"));
+        assert_eq!(simplify_init("!!(a != b)"), parse_init("a != b"));
+    }
+
+    #[test]
+    fn test_negated_literal_is_folded() {
+        assert_eq!(simplify_init("!true"), Expression::Bool(false));
+        assert_eq!(simplify_init("!false"), Expression::Bool(true));
+    }
+
+    #[test]
+    fn test_de_morgan_only_when_every_side_simplifies() {
+        assert_eq!(simplify_init("!(a < b && !x)"), parse_init("a >= b || x"));
+        // `x` ne se simplifie pas : réécrire ajouterait une négation
+        assert_eq!(simplify_init("!(a < b || x)"), parse_init("!(a < b || x)"));
+    }
+
+    #[test]
+    fn test_float_ordering_is_kept() {
+        // `!(f < 2.0)` et `f >= 2.0` diffèrent pour NaN
+        assert_eq!(simplify_init("!(f < 2.0)"), parse_init("!(f < 2.0)"));
+        assert_eq!(simplify_init("!(f == 2.0)"), parse_init("f != 2.0"));
+    }
+
+    #[test]
+    fn test_nested_expressions_are_simplified() {
+        let mut analyzer = SemanticAnalyzer::new(
+            "function check(v: bool): int {\n    if (!(v == true)) {\n        return 0;\n    };\n    return 1;\n}".to_string(),
+        );
+        analyzer.analyze();
+        assert!(analyzer.errors.is_empty(), "Unexpected semantic errors: {:?}", analyzer.errors);
+        simplify_ast(&mut analyzer.ast, &mut analyzer.type_context);
+        let Some(Statement::FunctionDeclaration(func)) = analyzer.ast.statements.first() else {
+            panic!("Expected a function declaration");
+        };
+        let Statement::If(if_stmt) = &func.body[0] else {
+            panic!("Expected an if statement");
+        };
+        assert_eq!(if_stmt.condition.to_string(), "(v != true)");
+    }
+
+    #[test]
+    fn test_types_follow_the_rewritten_expressions() {
+        let mut analyzer = SemanticAnalyzer::new(format!(
+            "{}let r: bool = !!(a < b);\nlet s: bool = !(!x && a == b);",
+            PRELUDE
+        ));
+        analyzer.analyze();
+        assert!(analyzer.errors.is_empty(), "Unexpected semantic errors: {:?}", analyzer.errors);
+        simplify_ast(&mut analyzer.ast, &mut analyzer.type_context);
+
+        // Chaque expression réécrite garde un type, sans nouvelle analyse
+        let types = &analyzer.type_context;
+        let inits: Vec<&Expression> = analyzer.ast.statements[4..]
+            .iter()
+            .map(|stmt| match stmt {
+                Statement::VarDeclaration(decl) => decl.init.as_ref().expect("Missing initializer"),
+                other => panic!("Expected a variable declaration, got {:?}", other),
+            })
+            .collect();
+        assert_eq!(inits[0].to_string(), "(a < b)");
+        assert_eq!(inits[1].to_string(), "(x || (a != b))");
+        for init in inits {
+            assert_eq!(types.get(init), Some("bool"));
+            let Expression::Binary(bin_expr) = init else {
+                panic!("Expected a binary expression, got {:?}", init);
+            };
+            assert_eq!(types.get(&bin_expr.left), Some(if bin_expr.op == "<" { "int" } else { "bool" }));
+            assert_eq!(types.get(&bin_expr.right), Some(if bin_expr.op == "<" { "int" } else { "bool" }));
+        }
+    }
+}
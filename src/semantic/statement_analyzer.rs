@@ -1,6 +1,11 @@
 // semantic/statement_analyzer.rs
 
-use crate::parser::models::statement::Statement;
+use crate::parser::models::expression::Expression;
+use crate::parser::models::statement::{
+    ForStatement, FunctionDeclaration, IfStatement, ModuleDeclaration, Return, Statement,
+    SwitchStatement, VarAffection, VarDeclaration, WhileStatement,
+};
+use crate::parser::visit::Visitor;
 use crate::semantic::analyzer::SemanticAnalyzer;
 
 pub trait StatementAnalyzer {
@@ -10,34 +15,63 @@ pub trait StatementAnalyzer {
 
 impl StatementAnalyzer for SemanticAnalyzer {
     fn analyze_statement(&mut self, stmt: &Statement) {
-        match stmt {
-            Statement::VarDeclaration(var_decl) => {
-                self.analyze_var_declaration(var_decl);
-            }
-            Statement::Return(expr_opt) => {
-                self.analyze_return_statement(expr_opt);
-            }
-            Statement::ExpressionStatement(expr) => {
-                self.analyze_expression(expr);
-            }
-            Statement::If(if_stmt) => {
-                self.analyze_if_statement(if_stmt);
-            }
-            Statement::For(for_stmt) => {
-                self.analyze_for_statement(for_stmt);
-            }
-            Statement::While(while_stmt) => {
-                self.analyze_while_statement(while_stmt);
-            }
-            Statement::Switch(switch_stmt) => {
-                self.analyze_switch_statement(switch_stmt);
-            }
-            Statement::VarAffection(var_affection) => {
-                self.analyze_var_affection(var_affection);
-            }
-            Statement::FunctionDeclaration(func_decl) => {
-                self.analyze_function_declaration(func_decl);
-            }
-        }
+        self.visit_statement(stmt);
+    }
+}
+
+/// Le dispatch des statements passe par le `Visitor` : chaque type de statement est confié à
+/// la méthode d'analyse correspondante, qui gère elle-même les scopes et le parcours des blocs.
+impl Visitor for SemanticAnalyzer {
+    fn visit_var_declaration(&mut self, var_decl: &VarDeclaration) {
+        self.analyze_var_declaration(var_decl);
+    }
+
+    fn visit_var_affection(&mut self, var_affection: &VarAffection) {
+        self.analyze_var_affection(var_affection);
+    }
+
+    fn visit_return(&mut self, return_stmt: &Return) {
+        self.analyze_return_statement(return_stmt);
+    }
+
+    fn visit_if(&mut self, if_stmt: &IfStatement) {
+        self.analyze_if_statement(if_stmt);
+    }
+
+    fn visit_switch(&mut self, switch_stmt: &SwitchStatement) {
+        self.analyze_switch_statement(switch_stmt);
+    }
+
+    fn visit_while(&mut self, while_stmt: &WhileStatement) {
+        self.analyze_while_statement(while_stmt);
+    }
+
+    fn visit_for(&mut self, for_stmt: &ForStatement) {
+        self.analyze_for_statement(for_stmt);
+    }
+
+    fn visit_function_declaration(&mut self, func_decl: &FunctionDeclaration) {
+        self.analyze_function_declaration(func_decl);
+    }
+
+    fn visit_module(&mut self, module: &ModuleDeclaration) {
+        self.analyze_module(module);
+    }
+
+    fn visit_expression_statement(&mut self, expr: &Expression) {
+        self.analyze_expression(expr);
+        self.check_ignored_return_value(expr);
+    }
+
+    fn visit_break(&mut self, label: Option<&str>) {
+        self.analyze_break_statement(label);
+    }
+
+    fn visit_continue(&mut self, label: Option<&str>) {
+        self.analyze_continue_statement(label);
+    }
+
+    fn visit_block(&mut self, statements: &[Statement]) {
+        self.analyze_block(statements);
     }
 }
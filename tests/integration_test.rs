@@ -0,0 +1,1018 @@
+// Tests that assemble and link a program need nasm and ld on the PATH. Without them, such a
+// test returns early and says it was skipped; the CI sets `OWN_LANG_REQUIRE_TOOLCHAIN` so that
+// a missing toolchain fails there instead of passing without running.
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output, Stdio};
+
+// Helper function writing a source file in a per-test temporary directory
+fn write_source(test_name: &str, file_name: &str, source: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("own-lang-{}-{}", test_name, std::process::id()));
+    fs::create_dir_all(&dir).expect("Failed to create test directory");
+    let path = dir.join(file_name);
+    fs::write(&path, source).expect("Failed to write test source");
+    path
+}
+
+// Helper function returning the executable produced for a source file
+fn executable_path(source_path: &Path) -> PathBuf {
+    source_path.with_extension("owne")
+}
+
+// Helper function running a compiled program and returning its output
+fn run_executable(source_path: &Path) -> Output {
+    Command::new(executable_path(source_path))
+        .output()
+        .expect("Failed to run compiled program")
+}
+
+// Helper function telling whether nasm and ld can be run, or failing when the toolchain is
+// required
+fn toolchain_available() -> bool {
+    let missing: Vec<&str> = ["nasm", "ld"]
+        .into_iter()
+        .filter(|tool| {
+            Command::new(tool)
+                .arg("-v")
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .status()
+                .is_err()
+        })
+        .collect();
+    if missing.is_empty() {
+        return true;
+    }
+    assert!(
+        std::env::var_os("OWN_LANG_REQUIRE_TOOLCHAIN").is_none(),
+        "{} not found in PATH",
+        missing.join(" and ")
+    );
+    eprintln!("skipped: {} not found in PATH", missing.join(" and "));
+    false
+}
+
+fn compiler() -> Command {
+    Command::new(env!("CARGO_BIN_EXE_own-lang"))
+}
+
+// Helper function extracting the path printed after `prefix` by the compiler
+fn printed_path(output: &Output, prefix: &str) -> PathBuf {
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let line = stdout
+        .lines()
+        .find_map(|line| line.strip_prefix(prefix))
+        .unwrap_or_else(|| panic!("No '{}' line in {}", prefix, stdout));
+    PathBuf::from(line)
+}
+
+#[test]
+fn test_back_to_back_compilations_keep_separate_temps() {
+    if !toolchain_available() {
+        return;
+    }
+    // Same file name in two directories: only the unique part of the temp names tells them apart
+    let first = write_source("keep-first", "prog.own", "print(1);");
+    let second = write_source("keep-second", "prog.own", "print(2);");
+
+    let first_output = compiler()
+        .arg("--keep-temps")
+        .arg(&first)
+        .output()
+        .expect("Failed to run compiler");
+    let second_output = compiler()
+        .arg("--keep-temps")
+        .arg(&second)
+        .output()
+        .expect("Failed to run compiler");
+    assert!(first_output.status.success(), "{:?}", first_output);
+    assert!(second_output.status.success(), "{:?}", second_output);
+
+    let first_asm = printed_path(&first_output, "Assembly code written to ");
+    let second_asm = printed_path(&second_output, "Assembly code written to ");
+    let first_object = printed_path(&first_output, "Object file generated: ");
+    let second_object = printed_path(&second_output, "Object file generated: ");
+    assert_ne!(first_asm, second_asm);
+    assert_ne!(first_object, second_object);
+
+    // The second compilation did not overwrite the files kept by the first one
+    let first_code = fs::read_to_string(&first_asm).expect("Failed to read kept assembly");
+    let second_code = fs::read_to_string(&second_asm).expect("Failed to read kept assembly");
+    assert!(first_code.contains("    mov rax, 1\n"));
+    assert!(second_code.contains("    mov rax, 2\n"));
+    assert_eq!(String::from_utf8_lossy(&run_executable(&first).stdout), "1\n");
+    assert_eq!(String::from_utf8_lossy(&run_executable(&second).stdout), "2\n");
+
+    for path in [first_asm, second_asm, first_object, second_object] {
+        fs::remove_file(path).expect("Failed to remove kept file");
+    }
+}
+
+#[test]
+fn test_concurrent_compilations() {
+    if !toolchain_available() {
+        return;
+    }
+    let first = write_source("concurrent", "first.own", "print(1);");
+    let second = write_source("concurrent", "second.own", "print(2);");
+
+    // Both compilers run at the same time and must not share intermediate files
+    let first_child = compiler().arg(&first).spawn().expect("Failed to spawn compiler");
+    let second_child = compiler().arg(&second).spawn().expect("Failed to spawn compiler");
+    let first_output = first_child.wait_with_output().expect("Failed to wait for compiler");
+    let second_output = second_child.wait_with_output().expect("Failed to wait for compiler");
+    assert!(first_output.status.success(), "{:?}", first_output);
+    assert!(second_output.status.success(), "{:?}", second_output);
+
+    assert_eq!(String::from_utf8_lossy(&run_executable(&first).stdout), "1\n");
+    assert_eq!(String::from_utf8_lossy(&run_executable(&second).stdout), "2\n");
+}
+
+#[test]
+fn test_executable_is_position_independent() {
+    if !toolchain_available() {
+        return;
+    }
+    let source = write_source(
+        "pie",
+        "pie.own",
+        "let g: int = 5;\nfunction f(x: int): int {\n    return x + g;\n}\nprint(f(2));\nprint(\"hi\");",
+    );
+    let output = compiler().arg(&source).output().expect("Failed to run compiler");
+    assert!(output.status.success(), "{:?}", output);
+
+    // A PIE is an ELF shared object (e_type = ET_DYN), not a fixed-address executable
+    let executable = fs::read(executable_path(&source)).expect("Failed to read executable");
+    assert_eq!(&executable[..4], b"\x7fELF");
+    assert_eq!(u16::from_le_bytes([executable[16], executable[17]]), 3);
+    assert_eq!(String::from_utf8_lossy(&run_executable(&source).stdout), "7\nhi\n");
+}
+
+#[test]
+fn test_unary_with_nested_parentheses() {
+    if !toolchain_available() {
+        return;
+    }
+    let source = write_source(
+        "unary",
+        "unary.own",
+        "let a: int = 4;\nlet b: int = 6;\nprint(-(-(a + b)));\nprint(-((a - b) * 2));\nif (!(a == b)) {\n    print(1);\n};\nif (!!(a == b)) {\n    print(2);\n};",
+    );
+    let output = compiler().arg(&source).output().expect("Failed to run compiler");
+    assert!(output.status.success(), "{:?}", output);
+    assert_eq!(String::from_utf8_lossy(&run_executable(&source).stdout), "10\n4\n1\n");
+}
+
+#[test]
+fn test_float_negation() {
+    if !toolchain_available() {
+        return;
+    }
+    let source = write_source(
+        "float_negation",
+        "float_negation.own",
+        "let x: float = 2.5;\nprint(-x);\nprint(-(-x));\nlet y: float = -0.5;\nprint(-y);",
+    );
+    let output = compiler().arg(&source).output().expect("Failed to run compiler");
+    assert!(output.status.success(), "{:?}", output);
+    // The sign bit is flipped: `neg` would turn these bit patterns into unrelated values
+    assert_eq!(
+        String::from_utf8_lossy(&run_executable(&source).stdout),
+        "-2.500000\n2.500000\n0.500000\n"
+    );
+}
+
+#[test]
+fn test_float_arithmetic_and_ordering() {
+    if !toolchain_available() {
+        return;
+    }
+    let source = write_source(
+        "float_arithmetic",
+        "float_arithmetic.own",
+        "let a: float = 1.5;\nlet b: float = 2.0;\nprint(a + a);\nprint(a - b);\nprint(a * b);\n\
+         print(a / b);\nprint(a < b);\nprint(a >= b);\nprint(a == 1.5);\nprint(1.0 < a < b);",
+    );
+    let output = compiler().arg(&source).output().expect("Failed to run compiler");
+    assert!(output.status.success(), "{:?}", output);
+    // Computed on the float values, not on their bit patterns as integers
+    assert_eq!(
+        String::from_utf8_lossy(&run_executable(&source).stdout),
+        "3.000000\n-0.500000\n3.000000\n0.750000\ntrue\nfalse\ntrue\ntrue\n"
+    );
+}
+
+#[test]
+fn test_help_lists_flags() {
+    let output = compiler().arg("--help").output().expect("Failed to run compiler");
+    assert!(output.status.success(), "{:?}", output);
+    let help = String::from_utf8_lossy(&output.stdout);
+    for flag in ["--keep-temps", "--help", "--version"] {
+        assert!(help.contains(flag), "--help does not list {}: {}", flag, help);
+    }
+
+    let output = compiler().arg("--bogus").output().expect("Failed to run compiler");
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Unknown flag '--bogus'."));
+}
+
+#[test]
+fn test_argument_evaluation_order() {
+    if !toolchain_available() {
+        return;
+    }
+    let source = write_source(
+        "order",
+        "order.own",
+        "function tick(n: int): int {\n    print(n);\n    return n;\n}\n\
+         function sub(a: int, b: int): int {\n    return a - b;\n}\n\
+         print(sub(tick(1), tick(2)));\nprint(tick(3) - tick(4));",
+    );
+    let output = compiler().arg(&source).output().expect("Failed to run compiler");
+    assert!(output.status.success(), "{:?}", output);
+    assert_eq!(
+        String::from_utf8_lossy(&run_executable(&source).stdout),
+        "1\n2\n-1\n3\n4\n-1\n"
+    );
+}
+
+#[test]
+fn test_bounds_check() {
+    if !toolchain_available() {
+        return;
+    }
+    let source = write_source(
+        "bounds",
+        "bounds.own",
+        "let xs: int[2];\nlet ys: int[2];\nlet i: int = 2;\nxs[1] = 1;\nprint(xs[i]);",
+    );
+
+    // Without the flag the out-of-range read goes unnoticed (undefined behavior)
+    let output = compiler().arg(&source).output().expect("Failed to run compiler");
+    assert!(output.status.success(), "{:?}", output);
+    let unchecked = run_executable(&source);
+    assert!(!String::from_utf8_lossy(&unchecked.stderr).contains("Index out of bounds"));
+
+    let output = compiler()
+        .arg("--bounds-check")
+        .arg(&source)
+        .output()
+        .expect("Failed to run compiler");
+    assert!(output.status.success(), "{:?}", output);
+    let checked = run_executable(&source);
+    assert_eq!(checked.status.code(), Some(1));
+    assert_eq!(String::from_utf8_lossy(&checked.stderr), "Index out of bounds\n");
+    assert_eq!(String::from_utf8_lossy(&checked.stdout), "");
+}
+
+#[test]
+fn test_checked_arithmetic() {
+    if !toolchain_available() {
+        return;
+    }
+    let source = write_source(
+        "overflow",
+        "overflow.own",
+        "let big: int = 4611686018427387904;\nprint(1);\nprint(big * 2);",
+    );
+
+    // Without the flag the multiplication silently wraps around
+    let output = compiler().arg(&source).output().expect("Failed to run compiler");
+    assert!(output.status.success(), "{:?}", output);
+    let unchecked = run_executable(&source);
+    assert!(unchecked.status.success(), "{:?}", unchecked);
+    assert_eq!(String::from_utf8_lossy(&unchecked.stdout).lines().count(), 2);
+
+    let output = compiler()
+        .arg("--checked-arith")
+        .arg(&source)
+        .output()
+        .expect("Failed to run compiler");
+    assert!(output.status.success(), "{:?}", output);
+    let checked = run_executable(&source);
+    assert_eq!(checked.status.code(), Some(1));
+    assert_eq!(String::from_utf8_lossy(&checked.stderr), "Arithmetic overflow\n");
+    assert_eq!(String::from_utf8_lossy(&checked.stdout), "1\n");
+}
+
+#[test]
+fn test_identifiers_do_not_clash_with_generated_symbols() {
+    if !toolchain_available() {
+        return;
+    }
+    let fixture = fs::read_to_string("own_files/mangling/mangling_good.own")
+        .expect("Failed to read mangling fixture");
+    let source = write_source("mangling", "mangling_good.own", &fixture);
+    let output = compiler().arg(&source).output().expect("Failed to run compiler");
+    assert!(output.status.success(), "{:?}", output);
+    let run = run_executable(&source);
+    assert!(run.status.success(), "{:?}", run);
+    assert_eq!(String::from_utf8_lossy(&run.stdout), "42\n41\n1\n0\n");
+}
+
+#[test]
+fn test_parameter_shadows_global() {
+    if !toolchain_available() {
+        return;
+    }
+    let fixture = fs::read_to_string("own_files/shadowing/shadowing_good.own")
+        .expect("Failed to read shadowing fixture");
+    let source = write_source("shadowing", "shadowing_good.own", &fixture);
+    let output = compiler().arg(&source).output().expect("Failed to run compiler");
+    assert!(output.status.success(), "{:?}", output);
+    assert_eq!(String::from_utf8_lossy(&run_executable(&source).stdout), "3\n10\n1\n");
+}
+
+#[test]
+fn test_logical_operators() {
+    if !toolchain_available() {
+        return;
+    }
+    let source = write_source(
+        "logical",
+        "logical.own",
+        "function loud(v: bool): bool {\n    print(7);\n    return v;\n}\n\
+         let x: int = 3;\nlet y: int = -2;\n\
+         let b: bool = (x > 0) && (y > 0);\n\
+         let c: bool = (x > 0) || loud(false);\n\
+         let d: bool = (y > 0) && loud(true);\n\
+         if (b) {\n    print(1);\n};\n\
+         if (c) {\n    print(2);\n};\n\
+         if (d == false) {\n    print(3);\n};\n\
+         if (c == true) {\n    print(4);\n};",
+    );
+    let output = compiler().arg(&source).output().expect("Failed to run compiler");
+    assert!(output.status.success(), "{:?}", output);
+    // `loud` is never called: both right-hand sides are short-circuited
+    assert_eq!(String::from_utf8_lossy(&run_executable(&source).stdout), "2\n3\n4\n");
+}
+
+
+#[test]
+fn test_short_circuit_guards_division_by_zero() {
+    if !toolchain_available() {
+        return;
+    }
+    let fixture = fs::read_to_string("own_files/logical/logical_guard.own")
+        .expect("Failed to read logical guard fixture");
+    let source = write_source("logical_guard", "logical_guard.own", &fixture);
+    let output = compiler().arg(&source).output().expect("Failed to run compiler");
+    assert!(output.status.success(), "{:?}", output);
+    // Evaluating any guarded `b / a` or `b % a` with `a == 0` would kill the program with SIGFPE
+    let run = run_executable(&source);
+    assert_eq!(run.status.code(), Some(0));
+    assert_eq!(String::from_utf8_lossy(&run.stdout), "2\n3\nfalse\ntrue\n");
+}
+
+#[test]
+fn test_return_from_main_program() {
+    if !toolchain_available() {
+        return;
+    }
+    let fixture = fs::read_to_string("own_files/main_return/main_return_good.own")
+        .expect("Failed to read main_return fixture");
+    let source = write_source("main_return", "main_return_good.own", &fixture);
+    let output = compiler().arg(&source).output().expect("Failed to run compiler");
+    assert!(output.status.success(), "{:?}", output);
+    // The early `return` inside the `if` ends the program with its value as exit status
+    let run = run_executable(&source);
+    assert_eq!(run.status.code(), Some(7));
+    assert_eq!(String::from_utf8_lossy(&run.stdout), "1\n");
+}
+
+#[test]
+fn test_main_program_returning_bool() {
+    if !toolchain_available() {
+        return;
+    }
+    let fixture = fs::read_to_string("own_files/main_return/main_return_bool.own")
+        .expect("Failed to read main_return_bool fixture");
+    let source = write_source("main_return_bool", "main_return_bool.own", &fixture);
+    let output = compiler().arg(&source).output().expect("Failed to run compiler");
+    assert!(output.status.success(), "{:?}", output);
+    // `false` ends the program with exit status 1, `true` with 0
+    let run = run_executable(&source);
+    assert_eq!(run.status.code(), Some(1));
+    assert_eq!(String::from_utf8_lossy(&run.stdout), "3\n");
+
+    let source = write_source("main_return_bool", "main_return_true.own", &fixture.replace("3;", "4;"));
+    let output = compiler().arg(&source).output().expect("Failed to run compiler");
+    assert!(output.status.success(), "{:?}", output);
+    let run = run_executable(&source);
+    assert_eq!(run.status.code(), Some(0));
+    assert_eq!(String::from_utf8_lossy(&run.stdout), "4\n");
+}
+
+#[test]
+fn test_more_than_six_arguments() {
+    if !toolchain_available() {
+        return;
+    }
+    let source = write_source(
+        "many_args",
+        "many_args.own",
+        "function sum8(a: int, b: int, c: int, d: int, e: int, f: int, g: int, h: int): int {\n\
+         \x20   return a + 2 * b + 3 * c + 4 * d + 5 * e + 6 * f + 7 * g + 8 * h;\n}\n\
+         print(sum8(1, 2, 3, 4, 5, 6, 7, 8));",
+    );
+    let output = compiler().arg(&source).output().expect("Failed to run compiler");
+    assert!(output.status.success(), "{:?}", output);
+    // Weighting each parameter catches any argument landing in the wrong slot
+    assert_eq!(String::from_utf8_lossy(&run_executable(&source).stdout), "204\n");
+}
+
+#[test]
+fn test_string_length() {
+    if !toolchain_available() {
+        return;
+    }
+    let source = write_source(
+        "len",
+        "len.own",
+        "let s: string = \"hey\";\nprint(len(s));\nprint(len(\"hello\"));",
+    );
+    let output = compiler().arg(&source).output().expect("Failed to run compiler");
+    assert!(output.status.success(), "{:?}", output);
+    assert_eq!(String::from_utf8_lossy(&run_executable(&source).stdout), "3\n5\n");
+}
+
+#[test]
+fn test_warnings_do_not_abort_compilation() {
+    if !toolchain_available() {
+        return;
+    }
+    let fixture = fs::read_to_string("own_files/shadowing/shadowing_warning.own")
+        .expect("Failed to read shadowing fixture");
+    let source = write_source("warnings", "shadowing_warning.own", &fixture);
+    let output = compiler().arg(&source).output().expect("Failed to run compiler");
+    assert!(output.status.success(), "{:?}", output);
+    assert!(String::from_utf8_lossy(&output.stderr)
+        .contains("warning: Variable 'count' shadows a variable from an outer scope."));
+    assert_eq!(String::from_utf8_lossy(&run_executable(&source).stdout), "7\n");
+}
+
+#[test]
+fn test_syntax_error_shows_the_source_line() {
+    // Analysis fails before nasm or ld would run, so no toolchain is needed
+    let source = write_source("syntax_error", "missing_semicolon.own", "let x: int = 1;\nlet y: int = x\nprint(y);");
+    let output = compiler().arg(&source).output().expect("Failed to run compiler");
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8_lossy(&output.stderr),
+        "error: Parser error: Expected ';' at the end of variable declaration.\n --> 3:1\n  |\n3 | print(y);\n  | ^\n"
+    );
+}
+
+#[test]
+fn test_empty_programs() {
+    if !toolchain_available() {
+        return;
+    }
+    for (file_name, contents) in [
+        ("empty.own", ""),
+        ("blank.own", "  \n\n\t\n"),
+        ("comments.own", "// Nothing to run yet\n// print(1);\n"),
+    ] {
+        let source = write_source("empty", file_name, contents);
+        let output = compiler().arg(&source).output().expect("Failed to run compiler");
+        assert!(output.status.success(), "{:?}", output);
+        assert!(output.stderr.is_empty(), "Unexpected diagnostics: {:?}", output);
+        let run = run_executable(&source);
+        assert_eq!(run.status.code(), Some(0));
+        assert!(run.stdout.is_empty());
+    }
+}
+
+#[test]
+fn test_source_from_stdin() {
+    if !toolchain_available() {
+        return;
+    }
+    // Without an input file name the executable is written as `a.owne` in the working directory
+    let dir = write_source("stdin", "unused.own", "").parent().unwrap().to_path_buf();
+    let mut child = compiler()
+        .arg("-")
+        .current_dir(&dir)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("Failed to spawn compiler");
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(b"let x: int = 20;\nprint(x + 1);")
+        .expect("Failed to write source to stdin");
+    let output = child.wait_with_output().expect("Failed to wait for compiler");
+    assert!(output.status.success(), "{:?}", output);
+    let run = Command::new(dir.join("a.owne")).output().expect("Failed to run compiled program");
+    assert_eq!(String::from_utf8_lossy(&run.stdout), "21\n");
+}
+
+#[test]
+fn test_output_path_flag() {
+    if !toolchain_available() {
+        return;
+    }
+    // `-o` names the executable; without it, only one trailing `.own` is stripped
+    let source = write_source("output", "prog.own.own", "print(7);");
+    let output = compiler().arg(&source).output().expect("Failed to run compiler");
+    assert!(output.status.success(), "{:?}", output);
+    assert!(source.with_file_name("prog.own.owne").exists());
+
+    let target = source.with_file_name("custom-name");
+    let output = compiler()
+        .arg(&source)
+        .arg("-o")
+        .arg(&target)
+        .output()
+        .expect("Failed to run compiler");
+    assert!(output.status.success(), "{:?}", output);
+    let run = Command::new(&target).output().expect("Failed to run compiled program");
+    assert_eq!(String::from_utf8_lossy(&run.stdout), "7\n");
+}
+
+#[test]
+fn test_conditional_compilation() {
+    if !toolchain_available() {
+        return;
+    }
+    let fixture = fs::read_to_string("own_files/conditional/conditional_good.own")
+        .expect("Failed to read conditional fixture");
+    let source = write_source("conditional", "conditional_good.own", &fixture);
+
+    let output = compiler().arg(&source).output().expect("Failed to run compiler");
+    assert!(output.status.success(), "{:?}", output);
+    assert_eq!(String::from_utf8_lossy(&run_executable(&source).stdout), "1\n");
+
+    // Each `--define` enables the `#if` blocks of its flag, including inside functions
+    let output = compiler()
+        .args(["--define", "DEBUG", "--define=VERBOSE"])
+        .arg(&source)
+        .output()
+        .expect("Failed to run compiler");
+    assert!(output.status.success(), "{:?}", output);
+    assert_eq!(
+        String::from_utf8_lossy(&run_executable(&source).stdout),
+        "debug build\nverbose\n2\n"
+    );
+}
+
+#[test]
+fn test_print_returns_the_byte_count() {
+    if !toolchain_available() {
+        return;
+    }
+    let fixture = fs::read_to_string("own_files/print/print_count.own")
+        .expect("Failed to read print fixture");
+    let source = write_source("print_count", "print_count.own", &fixture);
+    let output = compiler().arg(&source).output().expect("Failed to run compiler");
+    assert!(output.status.success(), "{:?}", output);
+    // Every count includes the newline written after the value
+    assert_eq!(
+        String::from_utf8_lossy(&run_executable(&source).stdout),
+        "hello\n6\n12345\ntrue\n11\n-7\n3\n2\n"
+    );
+}
+
+#[test]
+fn test_void_function_without_return() {
+    if !toolchain_available() {
+        return;
+    }
+    let fixture = fs::read_to_string("own_files/function/function_void_no_return.own")
+        .expect("Failed to read void function fixture");
+    let source = write_source("void_no_return", "function_void_no_return.own", &fixture);
+    let output = compiler().arg(&source).output().expect("Failed to run compiler");
+    assert!(output.status.success(), "{:?}", output);
+    assert_eq!(
+        String::from_utf8_lossy(&run_executable(&source).stdout),
+        "hello\nworld\n3\n2\n1\nhello\nagain\n"
+    );
+}
+
+#[test]
+fn test_string_arguments() {
+    if !toolchain_available() {
+        return;
+    }
+    let fixture = fs::read_to_string("own_files/string_param/string_param_good.own")
+        .expect("Failed to read string parameter fixture");
+    let source = write_source("string_param", "string_param_good.own", &fixture);
+    let output = compiler().arg(&source).output().expect("Failed to run compiler");
+    assert!(output.status.success(), "{:?}", output);
+    // Literals, variables and returned strings all reach the callee intact
+    assert_eq!(
+        String::from_utf8_lossy(&run_executable(&source).stdout),
+        "Hello\nWorld\nHello\nagain\ntwo\none\n5\nHello\nright\n"
+    );
+}
+
+#[test]
+fn test_nested_for_loops() {
+    if !toolchain_available() {
+        return;
+    }
+    let fixture = fs::read_to_string("own_files/for/for_nested.own")
+        .expect("Failed to read nested for fixture");
+    let source = write_source("for_nested", "for_nested.own", &fixture);
+    let output = compiler().arg(&source).output().expect("Failed to run compiler");
+    assert!(output.status.success(), "{:?}", output);
+    assert_eq!(
+        String::from_utf8_lossy(&run_executable(&source).stdout),
+        "0\n1\n2\n10\n11\n104\n20\n21\n206\n"
+    );
+}
+
+#[test]
+fn test_for_counters_in_recursive_calls() {
+    if !toolchain_available() {
+        return;
+    }
+    let fixture = fs::read_to_string("own_files/for/for_recursive.own")
+        .expect("Failed to read recursive for fixture");
+    let source = write_source("for_recursive", "for_recursive.own", &fixture);
+    let output = compiler().arg(&source).output().expect("Failed to run compiler");
+    assert!(output.status.success(), "{:?}", output);
+    assert_eq!(String::from_utf8_lossy(&run_executable(&source).stdout), "2\n11\n");
+}
+
+#[test]
+fn test_for_counter_is_out_of_scope_after_the_loop() {
+    // Rejected by the analysis instead of failing at link time, so no toolchain is needed
+    let output = compiler()
+        .arg("own_files/for/for_counter_after_loop_bad.own")
+        .output()
+        .expect("Failed to run compiler");
+    assert_eq!(output.status.code(), Some(1));
+    assert!(
+        String::from_utf8_lossy(&output.stdout).contains("Undefined variable 'i'."),
+        "{:?}",
+        output
+    );
+}
+
+#[test]
+fn test_array_literal_initializers() {
+    if !toolchain_available() {
+        return;
+    }
+    let fixture = fs::read_to_string("own_files/array/array_literal_good.own")
+        .expect("Failed to read array literal fixture");
+    let source = write_source("array_literal", "array_literal_good.own", &fixture);
+
+    let output = compiler().arg(&source).output().expect("Failed to run compiler");
+    assert!(output.status.success(), "{:?}", output);
+    assert_eq!(
+        String::from_utf8_lossy(&run_executable(&source).stdout),
+        "6\n642\nsecond\n20\n"
+    );
+}
+
+#[test]
+fn test_negative_array_indexes() {
+    if !toolchain_available() {
+        return;
+    }
+    let fixture = fs::read_to_string("own_files/array/array_negative_index.own")
+        .expect("Failed to read negative index fixture");
+    let source = write_source("array_negative_index", "array_negative_index.own", &fixture);
+
+    let output = compiler()
+        .arg("--bounds-check")
+        .arg(&source)
+        .output()
+        .expect("Failed to run compiler");
+    assert!(output.status.success(), "{:?}", output);
+    assert_eq!(
+        String::from_utf8_lossy(&run_executable(&source).stdout),
+        "40\n10\n35\n40\n2\n"
+    );
+}
+
+#[test]
+fn test_negative_switch_cases() {
+    if !toolchain_available() {
+        return;
+    }
+    let fixture = fs::read_to_string("own_files/switch/switch_negative.own")
+        .expect("Failed to read negative switch fixture");
+    let source = write_source("switch_negative", "switch_negative.own", &fixture);
+
+    let output = compiler()
+        .arg("--checked-arith")
+        .arg(&source)
+        .output()
+        .expect("Failed to run compiler");
+    assert!(output.status.success(), "{:?}", output);
+    assert_eq!(
+        String::from_utf8_lossy(&run_executable(&source).stdout),
+        "minus one\nminus two\none\nother\n"
+    );
+}
+
+#[test]
+fn test_call_to_undefined_function_is_reported_before_linking() {
+    // Rejected by the analysis instead of failing at link time, so no toolchain is needed
+    let output = compiler()
+        .arg("own_files/function/function_undefined_call_bad.own")
+        .output()
+        .expect("Failed to run compiler");
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Call to undefined function 'compute'."), "{:?}", output);
+    assert!(stdout.contains("Call to undefined function 'log_value'."), "{:?}", output);
+}
+
+#[test]
+fn test_readint_reads_numbers_from_stdin() {
+    if !toolchain_available() {
+        return;
+    }
+    let fixture = fs::read_to_string("own_files/readint/readint_good.own")
+        .expect("Failed to read readint fixture");
+    let source = write_source("readint", "readint_good.own", &fixture);
+    let output = compiler().arg(&source).output().expect("Failed to run compiler");
+    assert!(output.status.success(), "{:?}", output);
+
+    // Numbers may be separated by spaces or newlines; once the input is exhausted, 0 is read
+    let mut child = Command::new(executable_path(&source))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("Failed to spawn compiled program");
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(b"3\n10 -4\n  7\n")
+        .expect("Failed to write program input");
+    let run = child.wait_with_output().expect("Failed to wait for compiled program");
+    assert!(run.status.success());
+    assert_eq!(String::from_utf8_lossy(&run.stdout), "6\n13\n0\n");
+}
+
+#[test]
+fn test_switch_range_cases() {
+    if !toolchain_available() {
+        return;
+    }
+    let fixture = fs::read_to_string("own_files/switch/switch_range.own")
+        .expect("Failed to read switch range fixture");
+    let source = write_source("switch_range", "switch_range.own", &fixture);
+
+    let output = compiler().arg(&source).output().expect("Failed to run compiler");
+    assert!(output.status.success(), "{:?}", output);
+    assert_eq!(
+        String::from_utf8_lossy(&run_executable(&source).stdout),
+        "negative\nnegative\nzero\nsmall\nsmall\nlarge\nlarge\nother\nother\n"
+    );
+}
+
+#[test]
+fn test_todo_aborts_the_program() {
+    if !toolchain_available() {
+        return;
+    }
+    let fixture =
+        fs::read_to_string("own_files/todo/todo_good.own").expect("Failed to read todo fixture");
+    let source = write_source("todo", "todo_good.own", &fixture);
+
+    let output = compiler().arg(&source).output().expect("Failed to run compiler");
+    assert!(output.status.success(), "{:?}", output);
+    let run = run_executable(&source);
+    assert_eq!(run.status.code(), Some(1));
+    assert_eq!(String::from_utf8_lossy(&run.stdout), "7\n");
+    assert_eq!(String::from_utf8_lossy(&run.stderr), "not yet implemented\n");
+}
+
+#[test]
+fn test_module_qualified_calls() {
+    if !toolchain_available() {
+        return;
+    }
+    let fixture = fs::read_to_string("own_files/module/module_good.own")
+        .expect("Failed to read module fixture");
+    let source = write_source("module", "module_good.own", &fixture);
+    let output = compiler().arg(&source).output().expect("Failed to run compiler");
+    assert!(output.status.success(), "{:?}", output);
+    // Each call reaches the function of its own module, not the global `square`
+    assert_eq!(String::from_utf8_lossy(&run_executable(&source).stdout), "25\n20\n6\n8\n");
+}
+
+#[test]
+fn test_time_passes() {
+    let source = write_source("time_passes", "timed.own", "let x: int = 1;\nprint(x);");
+    let output = compiler()
+        .args(["--time-passes", "--emit-asm"])
+        .arg(&source)
+        .output()
+        .expect("Failed to run compiler");
+    assert!(output.status.success(), "{:?}", output);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    for pass in ["lex", "parse", "semantic", "codegen"] {
+        assert!(
+            stderr.lines().any(|line| line.starts_with(&format!("time: {} ", pass))),
+            "Missing timing for {} in {:?}",
+            pass,
+            stderr
+        );
+    }
+    // `--emit-asm` stops before assembling and linking
+    assert!(!stderr.contains("time: assemble"));
+
+    let output = compiler().arg("--emit-asm").arg(&source).output().expect("Failed to run compiler");
+    assert!(!String::from_utf8_lossy(&output.stderr).contains("time:"));
+}
+
+#[test]
+fn test_continue_in_loops() {
+    if !toolchain_available() {
+        return;
+    }
+    let fixture = fs::read_to_string("own_files/continue/continue_good.own")
+        .expect("Failed to read continue fixture");
+    let source = write_source("continue", "continue_good.own", &fixture);
+    let output = compiler().arg(&source).output().expect("Failed to run compiler");
+    assert!(output.status.success(), "{:?}", output);
+    // 1 + 3 + 5 + 7 + 9 from the for loop, then 1 + 2 + 4 + 5 from the while loop
+    let run = run_executable(&source);
+    assert!(run.status.success(), "{:?}", run);
+    assert_eq!(String::from_utf8_lossy(&run.stdout), "37\n");
+}
+
+#[test]
+fn test_print_string_with_percent() {
+    if !toolchain_available() {
+        return;
+    }
+    let source = write_source(
+        "percent",
+        "percent.own",
+        "let s: string = \"50%s off\";\nprint(\"100%d done\");\nprint(s);",
+    );
+    let output = compiler().arg(&source).output().expect("Failed to run compiler");
+    assert!(output.status.success(), "{:?}", output);
+    // The `%` conversions in user strings are printed literally
+    let run = run_executable(&source);
+    assert!(run.status.success(), "{:?}", run);
+    assert_eq!(String::from_utf8_lossy(&run.stdout), "100%d done\n50%s off\n");
+}
+
+#[test]
+fn test_return_inside_loops() {
+    if !toolchain_available() {
+        return;
+    }
+    let fixture = fs::read_to_string("own_files/return/return_in_loops.own")
+        .expect("Failed to read return fixture");
+    let source = write_source("return_loops", "return_in_loops.own", &fixture);
+    let output = compiler().arg(&source).output().expect("Failed to run compiler");
+    assert!(output.status.success(), "{:?}", output);
+    let run = run_executable(&source);
+    assert!(run.status.success(), "{:?}", run);
+    assert_eq!(String::from_utf8_lossy(&run.stdout), "1\n2\n4\n13\n5\n2\n8\n");
+}
+
+#[test]
+fn test_string_indexing() {
+    if !toolchain_available() {
+        return;
+    }
+    let fixture = fs::read_to_string("own_files/string_index/string_index_good.own")
+        .expect("Failed to read string index fixture");
+    let source = write_source("string_index", "string_index_good.own", &fixture);
+    let output = compiler().arg(&source).output().expect("Failed to run compiler");
+    assert!(output.status.success(), "{:?}", output);
+    // Bytes are printed as their integer value
+    let run = run_executable(&source);
+    assert!(run.status.success(), "{:?}", run);
+    assert_eq!(String::from_utf8_lossy(&run.stdout), "104\n99\n102\nown\n119\n");
+}
+
+#[test]
+fn test_labeled_break_and_continue() {
+    if !toolchain_available() {
+        return;
+    }
+    let fixture = fs::read_to_string("own_files/label/label_good.own")
+        .expect("Failed to read label fixture");
+    let source = write_source("label", "label_good.own", &fixture);
+    let output = compiler().arg(&source).output().expect("Failed to run compiler");
+    assert!(output.status.success(), "{:?}", output);
+    // `break outer` leaves both loops at the first i * j == 12
+    let run = run_executable(&source);
+    assert!(run.status.success(), "{:?}", run);
+    assert_eq!(String::from_utf8_lossy(&run.stdout), "304\n");
+}
+
+#[test]
+fn test_emit_ast_json() {
+    let source = write_source("ast_json", "tree.own", "let x: int = 1;\nprint(x);");
+    let output = compiler().arg("--emit-ast-json").arg(&source).output().expect("Failed to run compiler");
+    assert!(output.status.success(), "{:?}", output);
+    // The JSON is written next to the source and nothing is assembled
+    let json = fs::read_to_string(source.with_extension("ast.json")).expect("Missing AST file");
+    assert!(json.starts_with(r#"{"statements":[{"kind":"VarDeclaration","name":"x""#));
+    assert!(!executable_path(&source).exists());
+}
+
+#[test]
+fn test_block_statement_scopes() {
+    if !toolchain_available() {
+        return;
+    }
+    let fixture = fs::read_to_string("own_files/block/block_good.own")
+        .expect("Failed to read block fixture");
+    let source = write_source("block", "block_good.own", &fixture);
+    let output = compiler().arg(&source).output().expect("Failed to run compiler");
+    assert!(output.status.success(), "{:?}", output);
+    // The block's `x` lives in main's frame and the global `x` is untouched
+    let run = run_executable(&source);
+    assert!(run.status.success(), "{:?}", run);
+    assert_eq!(String::from_utf8_lossy(&run.stdout), "inner\n42\n");
+}
+
+#[test]
+fn test_for_increment_with_two_counters() {
+    if !toolchain_available() {
+        return;
+    }
+    let fixture = fs::read_to_string("own_files/for/for_two_counters.own")
+        .expect("Failed to read for fixture");
+    let source = write_source("for_commas", "for_two_counters.own", &fixture);
+    let output = compiler().arg(&source).output().expect("Failed to run compiler");
+    assert!(output.status.success(), "{:?}", output);
+    // Both counters move on every iteration, in the order they are written
+    let run = run_executable(&source);
+    assert!(run.status.success(), "{:?}", run);
+    assert_eq!(String::from_utf8_lossy(&run.stdout), "10\n108\n206\n304\n2\n");
+}
+
+#[test]
+fn test_condition_calls_run_once_per_check() {
+    if !toolchain_available() {
+        return;
+    }
+    let fixture = fs::read_to_string("own_files/condition/condition_side_effects.own")
+        .expect("Failed to read condition fixture");
+    let source = write_source("condition_calls", "condition_side_effects.own", &fixture);
+    let output = compiler().arg(&source).output().expect("Failed to run compiler");
+    assert!(output.status.success(), "{:?}", output);
+    // `calls` counts the calls: 5 checks for the while, 4 for the for, 1 for the if
+    let run = run_executable(&source);
+    assert!(run.status.success(), "{:?}", run);
+    assert_eq!(String::from_utf8_lossy(&run.stdout), "5\n0\n0\n1\n2\n4\n1\n");
+}
+
+#[test]
+fn test_print_formats_each_type() {
+    if !toolchain_available() {
+        return;
+    }
+    let source = write_source(
+        "print_types",
+        "types.own",
+        "let i: int = 7;\nlet f: float = 2.5;\nlet s: string = \"hi\";\nlet b: bool = i > 3;\nprint(i);\nprint(f);\nprint(s);\nprint(b);\nprint(!b);",
+    );
+    let output = compiler().arg(&source).output().expect("Failed to run compiler");
+    assert!(output.status.success(), "{:?}", output);
+    let run = run_executable(&source);
+    assert!(run.status.success(), "{:?}", run);
+    assert_eq!(String::from_utf8_lossy(&run.stdout), "7\n2.500000\nhi\ntrue\nfalse\n");
+}
+
+#[test]
+fn test_bool_variable_conditions() {
+    if !toolchain_available() {
+        return;
+    }
+    let fixture = fs::read_to_string("own_files/condition/condition_bool_var.own")
+        .expect("Failed to read condition fixture");
+    let source = write_source("bool_conditions", "condition_bool_var.own", &fixture);
+    let output = compiler().arg(&source).output().expect("Failed to run compiler");
+    assert!(output.status.success(), "{:?}", output);
+    let run = run_executable(&source);
+    assert!(run.status.success(), "{:?}", run);
+    assert_eq!(String::from_utf8_lossy(&run.stdout), "3\n1\n0\n1\n0\n");
+}
+
+#[test]
+fn test_power_operator() {
+    if !toolchain_available() {
+        return;
+    }
+    let source = write_source(
+        "power",
+        "power.own",
+        "let two: int = 2;\nlet e: int = 0 - 1;\nprint(2 ** 10);\nprint(two ** 3 ** 2);\nprint(two ** e);\nlet f: float = 2.0;\nprint(f ** 0.5);",
+    );
+    let output = compiler().arg(&source).output().expect("Failed to run compiler");
+    assert!(output.status.success(), "{:?}", output);
+    // A negative integer exponent known only at run time gives 0
+    let run = run_executable(&source);
+    assert!(run.status.success(), "{:?}", run);
+    assert_eq!(String::from_utf8_lossy(&run.stdout), "1024\n512\n0\n1.414214\n");
+}
\ No newline at end of file